@@ -1,17 +1,140 @@
 use super::*;
 
 pub(crate) struct App {
+  click_targets: Vec<(Range<u16>, usize)>,
   client: Client,
+  clipboard: Option<Clipboard>,
   event_rx: UnboundedReceiver<Event>,
   event_tx: UnboundedSender<Event>,
   handle: Handle,
+  last_click: Option<(Instant, usize)>,
+  pager: Option<String>,
+  preview_visible: bool,
+  search_debounce_at: Option<Instant>,
+  spinner: Spinner,
   state: State,
 }
 
 impl App {
-  fn comment_list_item(entry: &CommentEntry, available_width: u16) -> ListItem {
-    let depth_indent = "  ".repeat(entry.depth);
-    let indent = format!("{BASE_INDENT}{depth_indent}");
+  const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+  const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+  const SEARCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+  const SPLIT_VIEW_MIN_WIDTH: u16 = 100;
+
+  fn click_target(&self, row: u16) -> Option<usize> {
+    self
+      .click_targets
+      .iter()
+      .find(|(rows, _)| rows.contains(&row))
+      .map(|&(_, target)| target)
+  }
+
+  fn click_targets(
+    area: Rect,
+    heights: &[usize],
+    targets: &[usize],
+    offset: usize,
+  ) -> Vec<(Range<u16>, usize)> {
+    let mut click_targets = Vec::new();
+
+    let mut top = area.y;
+
+    for (&height, &target) in heights.iter().zip(targets).skip(offset) {
+      if top >= area.bottom() {
+        break;
+      }
+
+      let bottom = top
+        .saturating_add(u16::try_from(height).unwrap_or(u16::MAX))
+        .min(area.bottom());
+
+      click_targets.push((top..bottom, target));
+
+      top = bottom;
+    }
+
+    click_targets
+  }
+
+  fn comment_indent(
+    entry: &CommentEntry,
+    available_width: u16,
+    indent_width: usize,
+    guides: &[Guide],
+    tree_guides: TreeGuides,
+  ) -> (String, String, usize) {
+    let (indent, body_indent) = if guides.is_empty() {
+      let depth_indent = " ".repeat(entry.depth.saturating_mul(indent_width));
+
+      (depth_indent.clone(), depth_indent)
+    } else {
+      let continuation = guides
+        .iter()
+        .map(|guide| guide.continuation())
+        .collect::<Vec<Guide>>();
+
+      (
+        tree_guides.render(guides, indent_width),
+        tree_guides.render(&continuation, indent_width),
+      )
+    };
+
+    let indent = format!("{BASE_INDENT}{indent}");
+    let body_indent = format!("{BASE_INDENT}{body_indent}");
+
+    let prefix_width = indent.chars().count();
+
+    let max_width = available_width as usize;
+    let wrap_width = max_width.saturating_sub(prefix_width).max(1);
+
+    (indent, body_indent, wrap_width)
+  }
+
+  fn comment_lines(
+    entry: &CommentEntry,
+    bookmarked: bool,
+    redacted: bool,
+    horizontal_offset: usize,
+    indent_width: usize,
+    guides: &[Guide],
+    reference: Option<String>,
+    context: &RenderContext,
+  ) -> Vec<Line<'static>> {
+    let RenderContext {
+      contrast,
+      followed_users,
+      now,
+      query,
+      separator,
+      theme,
+      tree_guides,
+      width: available_width,
+      wrap,
+    } = *context;
+
+    let (indent, body_indent, wrap_width) = Self::comment_indent(
+      entry,
+      available_width,
+      indent_width,
+      guides,
+      tree_guides,
+    );
+
+    if entry.is_fold() {
+      return vec![
+        Line::from(vec![
+          Span::raw(indent.clone()),
+          Span::styled(
+            format!("[+] {}", entry.body()),
+            Style::default().fg(theme.detail),
+          ),
+        ]),
+        separator.line(&body_indent, available_width),
+      ];
+    }
 
     let toggle = entry.has_children().then_some(if entry.expanded {
       "[-]"
@@ -19,6 +142,8 @@ impl App {
       "[+]"
     });
 
+    let followed = followed_users.follows(entry);
+
     let mut header = vec![Span::raw(indent.clone())];
 
     if let Some(symbol) = toggle {
@@ -26,31 +151,135 @@ impl App {
       header.push(Span::raw(" "));
     }
 
-    header.push(Span::styled(
-      entry.header(),
-      Style::default().fg(Color::White),
+    if bookmarked {
+      header.push(Span::raw(BOOKMARK_MARKER));
+    }
+
+    let header_style = if entry.is_op {
+      Style::default()
+        .fg(theme.tab_active)
+        .add_modifier(Modifier::BOLD)
+    } else if followed {
+      Style::default()
+        .fg(theme.highlight)
+        .add_modifier(Modifier::BOLD)
+    } else if entry.is_post {
+      Style::default()
+        .fg(theme.title)
+        .add_modifier(Modifier::BOLD)
+    } else {
+      Style::default().fg(theme.title)
+    };
+
+    header.extend(Self::highlight_spans(
+      entry.header(now),
+      query,
+      header_style,
+      &theme,
     ));
 
+    if followed {
+      header.push(Span::styled(
+        FOLLOWED_MARKER,
+        Style::default().fg(theme.highlight),
+      ));
+    }
+
+    if let Some(reference) = reference {
+      header.push(Span::raw(" "));
+      header.push(Span::styled(reference, Style::default().fg(theme.detail)));
+    }
+
     let mut lines = vec![Line::from(header)];
 
-    if !entry.body().is_empty() {
-      let body_indent = indent.clone();
-      let prefix_width = body_indent.chars().count();
+    if redacted {
+      lines.push(Line::from(vec![
+        Span::raw(body_indent.clone()),
+        Span::styled(
+          if entry.deleted { "[deleted]" } else { "[dead]" },
+          Style::default().fg(theme.detail),
+        ),
+      ]));
+    } else if !entry.body().is_empty() {
+      let body_lines = if wrap {
+        wrap_text(entry.body(), wrap_width)
+      } else {
+        entry
+          .body()
+          .split('\n')
+          .map(|line| line.chars().skip(horizontal_offset).collect())
+          .collect()
+      };
+
+      for line in body_lines {
+        let mut spans = vec![Span::raw(body_indent.clone())];
 
-      let max_width = available_width as usize;
-      let wrap_width = max_width.saturating_sub(prefix_width).max(1);
+        spans.extend(Self::highlight_spans(
+          line,
+          query,
+          if entry.is_post {
+            Style::default().fg(theme.title)
+          } else {
+            contrast.body_style(&theme)
+          },
+          &theme,
+        ));
 
-      for line in wrap_text(entry.body(), wrap_width) {
-        lines.push(Line::from(vec![
-          Span::raw(body_indent.clone()),
-          Span::styled(line, Style::default().fg(Color::DarkGray)),
-        ]));
+        lines.push(Line::from(spans));
       }
     }
 
-    lines.push(Line::from(Span::raw(indent.clone())));
+    if entry.expanded && !entry.loaded {
+      lines.push(Line::from(vec![
+        Span::raw(body_indent.clone()),
+        Span::raw(" ".repeat(indent_width)),
+        Span::styled(LOADING_REPLIES_ROW, Style::default().fg(theme.detail)),
+      ]));
+    }
+
+    lines.push(separator.line(&body_indent, available_width));
 
-    ListItem::new(lines)
+    lines
+  }
+
+  fn comment_list_item<'a>(
+    entry: &'a CommentEntry,
+    bookmarked: bool,
+    redacted: bool,
+    horizontal_offset: usize,
+    indent_width: usize,
+    guides: &[Guide],
+    reference: Option<String>,
+    context: &RenderContext,
+  ) -> ListItem<'a> {
+    ListItem::new(Self::comment_lines(
+      entry,
+      bookmarked,
+      redacted,
+      horizontal_offset,
+      indent_width,
+      guides,
+      reference,
+      context,
+    ))
+  }
+
+  fn comments_title(&self) -> Option<String> {
+    match self.state.mode() {
+      Mode::Comments(view) => view.title(),
+      Mode::List(_) => None,
+    }
+  }
+
+  fn copy_to_clipboard(&mut self, text: String) -> Result {
+    let clipboard = match self.clipboard.as_mut() {
+      Some(clipboard) => clipboard,
+      None => self.clipboard.insert(Clipboard::new()?),
+    };
+
+    clipboard.set_text(text)?;
+
+    Ok(())
   }
 
   fn draw(&mut self, frame: &mut Frame) {
@@ -64,28 +293,91 @@ impl App {
       ])
       .split(frame.area());
 
-    self.state.set_list_height(layout[1].height as usize);
+    let (list_area, preview_area) = if self.state.split_view()
+      && matches!(self.state.mode(), Mode::List(_))
+      && layout[1].width >= Self::SPLIT_VIEW_MIN_WIDTH
+    {
+      let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(layout[1]);
+
+      (panes[0], Some(panes[1]))
+    } else {
+      (layout[1], None)
+    };
+
+    self.preview_visible = preview_area.is_some();
+
+    let list_area = match self.comments_title() {
+      Some(title) => {
+        let areas = Layout::default()
+          .direction(Direction::Vertical)
+          .constraints([Constraint::Length(1), Constraint::Min(0)])
+          .split(list_area);
+
+        let width = usize::from(areas[0].width)
+          .saturating_sub(BASE_INDENT.len() + 3)
+          .max(1);
+
+        frame.render_widget(
+          Paragraph::new(Line::from(vec![
+            Span::raw(BASE_INDENT),
+            Span::styled(
+              truncate_words(&title, width),
+              Style::default().add_modifier(Modifier::BOLD),
+            ),
+          ])),
+          areas[0],
+        );
+
+        areas[1]
+      }
+      None => list_area,
+    };
+
+    self.state.set_list_height(list_area.height as usize);
 
     let tabs = self.state.tabs();
     let active_tab = self.state.resolved_active_tab().unwrap_or(0);
 
+    let accent = self.state.accent_color();
+
+    let theme = *self.state.config().theme();
+
+    let tab_loading = self.state.tab_loading();
+
     let tab_titles: Vec<Line> = tabs
       .iter()
-      .map(|tab| Line::from(tab.label.to_uppercase()))
+      .enumerate()
+      .map(|(index, tab)| {
+        let count = (index == active_tab && tab.loaded)
+          .then(|| self.state.list_view(index).map(ListView::len))
+          .flatten();
+
+        let loading = tab_loading.get(index).copied().unwrap_or(false);
+
+        Line::from(Self::tab_title(tab, count, loading))
+      })
       .collect();
 
     let tabs_widget = Tabs::new(tab_titles)
       .select(active_tab)
-      .style(Style::default().fg(Color::DarkGray))
-      .highlight_style(
-        Style::default()
-          .fg(Color::Cyan)
-          .add_modifier(Modifier::BOLD),
-      )
+      .style(Style::default().fg(theme.tab_inactive))
+      .highlight_style(Style::default().fg(accent).add_modifier(Modifier::BOLD))
       .divider(Span::raw(" "));
 
     frame.render_widget(tabs_widget, layout[0]);
 
+    if self.client.is_offline() {
+      frame.render_widget(
+        Paragraph::new("OFFLINE")
+          .alignment(Alignment::Right)
+          .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+        layout[0],
+      );
+    }
+
     let is_loading = self
       .state
       .tab_loading()
@@ -98,23 +390,89 @@ impl App {
       .tab(active_tab)
       .is_some_and(|tab| matches!(tab.category.kind, CategoryKind::Search));
 
-    let (list_items, selected_index, offset) = match self.state.mode_mut() {
+    let now = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map_or(0, |elapsed| elapsed.as_secs());
+
+    let comment_indent = self.state.config().comment_indent();
+
+    let separator = self.state.config().separator();
+
+    let width = list_area.width;
+
+    let mut line_heights = None;
+
+    let bookmarks = self.state.bookmarks();
+
+    let context = RenderContext {
+      contrast: self.state.contrast(),
+      followed_users: self.state.followed_users(),
+      now,
+      query: None,
+      separator: *separator,
+      theme,
+      tree_guides: self.state.config().tree_guides(),
+      width,
+      wrap: self.state.wrap_comments(),
+    };
+
+    let (list_items, targets, selected_index, offset) = match self.state.mode()
+    {
       Mode::List(view) => {
-        let items = view.items();
-        let selected_index = view.selected_index();
-        let offset = view.offset();
+        let min_score = self.state.min_score();
+
+        let filter = self.state.local_filter();
+
+        let targets = view
+          .items()
+          .iter()
+          .enumerate()
+          .filter(|(_, entry)| {
+            entry.meets_score(min_score) && entry.matches_filter(filter)
+          })
+          .map(|(index, _)| index)
+          .collect::<Vec<usize>>();
+
+        let items = view
+          .items()
+          .iter()
+          .filter(|entry| {
+            entry.meets_score(min_score) && entry.matches_filter(filter)
+          })
+          .collect::<Vec<_>>();
+
+        let selected_index = view.selected_index().map(|selected| {
+          view.items()[..selected]
+            .iter()
+            .filter(|entry| {
+              entry.meets_score(min_score) && entry.matches_filter(filter)
+            })
+            .count()
+        });
 
         let list_items: Vec<ListItem> = if items.is_empty() {
           let text = if is_loading {
-            if is_search_tab {
+            let text = if is_search_tab {
               LOADING_SEARCH_STATUS
             } else {
               LOADING_ENTRIES_STATUS
-            }
+            };
+
+            format!("{} {text}", self.spinner.glyph())
           } else if is_search_tab {
-            "No results yet. Try another query."
+            "No results yet. Try another query.".into()
+          } else if !view.is_empty() {
+            self
+              .state
+              .config()
+              .keymap()
+              .press(false, Command::AdjustScoreFilter(-10), "to lower it.")
+              .map_or_else(
+                || "Nothing above the score filter.".into(),
+                |hint| format!("Nothing above the score filter. {hint}"),
+              )
           } else {
-            "Nothing to show. Try another tab."
+            "Nothing to show. Try another tab.".into()
           };
 
           vec![ListItem::new(Line::from(vec![
@@ -122,56 +480,195 @@ impl App {
             Span::raw(text),
           ]))]
         } else {
-          items
+          let list_items = items
             .iter()
             .map(|entry| {
-              let mut lines = vec![Line::from(vec![
-                Span::raw(BASE_INDENT),
-                Span::styled(
-                  entry.title.clone(),
-                  Style::default().fg(Color::White),
-                ),
-              ])];
-
-              if let Some(detail) = &entry.detail {
+              let mut title = vec![Span::raw(BASE_INDENT)];
+
+              if self.state.is_pinned(&entry.id) {
+                title.push(Span::raw(PIN_MARKER));
+              }
+
+              if bookmarks.contains(&entry.id) {
+                title.push(Span::raw(BOOKMARK_MARKER));
+              }
+
+              let title_color = if self.state.is_read(&entry.id) {
+                theme.detail
+              } else {
+                title.push(Span::raw(UNREAD_MARKER));
+                theme.title
+              };
+
+              title.push(Span::styled(
+                entry.title.clone(),
+                Style::default().fg(title_color),
+              ));
+
+              let mut lines = vec![Line::from(title)];
+
+              if let Some(detail) = entry.detail_at(now) {
                 lines.push(Line::from(vec![
                   Span::raw(BASE_INDENT),
-                  Span::styled(
-                    detail.clone(),
-                    Style::default().fg(Color::DarkGray),
-                  ),
+                  Span::styled(detail, Style::default().fg(theme.detail)),
                 ]));
               }
 
-              lines.push(Line::from(Span::raw(BASE_INDENT)));
+              lines.push(separator.line(BASE_INDENT, width));
 
               ListItem::new(lines)
             })
-            .collect()
+            .collect();
+
+          Self::with_loading_row(list_items, is_loading, &theme)
+        };
+
+        let offset = if self.state.center_selection() {
+          let item_height =
+            list_items.iter().map(ListItem::height).max().unwrap_or(1);
+
+          view.centered_offset(
+            usize::from(list_area.height) / item_height.max(1),
+            |entry| {
+              entry.meets_score(min_score) && entry.matches_filter(filter)
+            },
+          )
+        } else {
+          Self::shown_offset(&targets, view.offset())
         };
 
-        (list_items, selected_index, offset)
+        (list_items, targets, selected_index, offset)
+      }
+      Mode::Comments(view)
+        if view.is_scrolling() && !view.visible_indexes().is_empty() =>
+      {
+        let (visible, selected_pos) = view.visible_with_selection();
+
+        let highlight =
+          Style::default().fg(accent).add_modifier(Modifier::BOLD);
+
+        let mut heights = Vec::with_capacity(visible.len());
+
+        let mut list_items = Vec::new();
+
+        let mut targets = Vec::new();
+
+        for (position, &idx) in visible.iter().enumerate() {
+          let (indent_width, guides, reference) = if view.is_flat() {
+            (0, Vec::new(), view.parent_reference(idx))
+          } else {
+            (comment_indent, view.guides(idx), None)
+          };
+
+          let lines = Self::comment_lines(
+            &view.entries[idx],
+            bookmarks.contains(&view.entries[idx].id.to_string()),
+            view.is_redacted(idx),
+            if selected_pos == Some(position) {
+              view.horizontal_offset()
+            } else {
+              0
+            },
+            indent_width,
+            &guides,
+            reference,
+            &RenderContext {
+              query: view.search_query(),
+              ..context
+            },
+          );
+
+          heights.push(lines.len());
+
+          targets.extend(std::iter::repeat_n(position, lines.len()));
+
+          let selected = selected_pos == Some(position);
+
+          list_items.extend(lines.into_iter().map(|line| {
+            if selected {
+              ListItem::new(Line::from(
+                line
+                  .spans
+                  .into_iter()
+                  .map(|span| span.patch_style(highlight))
+                  .collect::<Vec<_>>(),
+              ))
+            } else {
+              ListItem::new(line)
+            }
+          }));
+        }
+
+        line_heights = Some(heights);
+
+        (list_items, targets, None, view.line_offset())
       }
       Mode::Comments(view) => {
         let (visible, selected_pos) = view.visible_with_selection();
 
         let list_items: Vec<ListItem> = if visible.is_empty() {
+          let text = if view.is_listing() && !view.filter().is_empty() {
+            "No listings match the filter."
+          } else {
+            "No comments yet."
+          };
+
           vec![ListItem::new(Line::from(vec![
             Span::raw(BASE_INDENT),
-            Span::raw("No comments yet."),
+            Span::raw(text),
           ]))]
+        } else if view.is_listing() {
+          visible
+            .iter()
+            .map(|&idx| {
+              Self::listing_item(
+                &view.entries[idx],
+                bookmarks.contains(&view.entries[idx].id.to_string()),
+                list_area.width,
+                &theme,
+                separator,
+              )
+            })
+            .collect()
         } else {
           visible
             .iter()
             .map(|&idx| {
-              Self::comment_list_item(&view.entries[idx], layout[1].width)
+              let (indent_width, guides, reference) = if view.is_flat() {
+                (0, Vec::new(), view.parent_reference(idx))
+              } else {
+                (comment_indent, view.guides(idx), None)
+              };
+
+              Self::comment_list_item(
+                &view.entries[idx],
+                bookmarks.contains(&view.entries[idx].id.to_string()),
+                view.is_redacted(idx),
+                if view.selected == Some(idx) {
+                  view.horizontal_offset()
+                } else {
+                  0
+                },
+                indent_width,
+                &guides,
+                reference,
+                &RenderContext {
+                  query: view.search_query(),
+                  ..context
+                },
+              )
             })
             .collect()
         };
 
         let offset = view.offset.min(selected_pos.unwrap_or(0));
 
-        (list_items, selected_pos, offset)
+        (
+          list_items,
+          (0..visible.len()).collect(),
+          selected_pos,
+          offset,
+        )
       }
     };
 
@@ -179,29 +676,204 @@ impl App {
       .with_selected(selected_index)
       .with_offset(offset);
 
+    let item_heights = list_items
+      .iter()
+      .map(ListItem::height)
+      .collect::<Vec<usize>>();
+
     let list = List::new(list_items)
-      .highlight_style(
-        Style::default()
-          .fg(Color::Cyan)
-          .add_modifier(Modifier::BOLD),
-      )
+      .highlight_style(Style::default().fg(accent).add_modifier(Modifier::BOLD))
       .highlight_symbol("");
 
-    frame.render_stateful_widget(list, layout[1], &mut list_state);
+    frame.render_stateful_widget(list, list_area, &mut list_state);
+
+    if let Some(area) = preview_area {
+      self.draw_preview(frame, area, now);
+    }
+
+    self.click_targets = Self::click_targets(
+      list_area,
+      &item_heights,
+      &targets,
+      list_state.offset(),
+    );
+
+    let offset = match self.state.mode() {
+      Mode::List(_) => targets
+        .get(list_state.offset())
+        .copied()
+        .unwrap_or_default(),
+      Mode::Comments(_) => list_state.offset(),
+    };
+
+    self.state.mode_mut().set_offset(offset);
+
+    if let Some(heights) = line_heights
+      && let Mode::Comments(view) = self.state.mode_mut()
+    {
+      view.set_line_heights(heights);
+    }
+
+    let mut status = self.state.message().to_string();
+
+    if self.state.is_loading()
+      && [
+        LOADING_COMMENTS_STATUS,
+        LOADING_ENTRIES_STATUS,
+        LOADING_SEARCH_STATUS,
+      ]
+      .contains(&status.as_str())
+    {
+      status = format!("{} {status}", self.spinner.glyph());
+    }
+
+    if let Mode::List(_) = self.state.mode() {
+      if let Some(sort) = self.state.search_sort() {
+        status = format!("{status} • sorted by {sort}");
+      }
+
+      if let Some(tag) = self.state.tag_filter() {
+        status = format!("{status} • tag: {tag}");
+      }
+
+      if let Some(filter) = self.state.local_filter()
+        && !self.state.is_filtering()
+      {
+        status = format!("{status} • filter: {filter}");
+      }
+
+      let min_score = self.state.min_score();
 
-    self.state.mode_mut().set_offset(list_state.offset());
+      if min_score > 0 {
+        status = format!("{status} • score ≥ {min_score}");
+      }
+    }
 
-    let status = Paragraph::new(self.state.message().to_string())
-      .style(Style::default().fg(Color::DarkGray));
+    let status =
+      Paragraph::new(status).style(Style::default().fg(theme.status));
 
     frame.render_widget(status, layout[2]);
 
-    self.state.help().draw(frame);
+    if let Some(count) = self.state.count() {
+      let count = Paragraph::new(count.to_string())
+        .alignment(Alignment::Right)
+        .style(Style::default().fg(theme.highlight));
+
+      frame.render_widget(count, layout[2]);
+    }
+
+    if let Some(switcher) = self.state.tab_switcher() {
+      let tabs = self.state.switcher_tabs();
+
+      let titles = tabs
+        .iter()
+        .enumerate()
+        .map(|(index, tab)| {
+          let count = tab
+            .loaded
+            .then(|| self.state.list_view(index).map(ListView::len))
+            .flatten();
+
+          let loading = self
+            .state
+            .tab_loading()
+            .get(index)
+            .copied()
+            .unwrap_or(false);
+
+          Self::tab_title(tab, count, loading)
+        })
+        .collect::<Vec<String>>();
+
+      switcher.draw(frame, &tabs, &titles, &theme);
+    }
+
+    if let Some(palette) = self.state.command_palette() {
+      palette.draw(frame, &self.state.palette_entries(), &theme);
+    }
+
+    self.state.help_mut().draw(frame, &theme);
+  }
+
+  fn draw_preview(&self, frame: &mut Frame, area: Rect, now: u64) {
+    let theme = self.state.config().theme();
+
+    let block = Block::default()
+      .title("Preview")
+      .borders(Borders::LEFT)
+      .border_style(Style::default().fg(theme.detail));
+
+    let inner = block.inner(area);
+
+    frame.render_widget(block, area);
+
+    let placeholder = |text: &str| {
+      vec![ListItem::new(Line::from(vec![
+        Span::raw(BASE_INDENT),
+        Span::styled(text.to_string(), Style::default().fg(theme.detail)),
+      ]))]
+    };
+
+    let context = RenderContext {
+      contrast: self.state.contrast(),
+      followed_users: self.state.followed_users(),
+      now,
+      query: None,
+      separator: *self.state.config().separator(),
+      theme: *theme,
+      tree_guides: TreeGuides::None,
+      width: inner.width,
+      wrap: true,
+    };
+
+    let items = match self.state.preview() {
+      None => placeholder("Nothing selected"),
+      Some(preview) => match (preview.roots(), preview.error()) {
+        (_, Some(error)) => {
+          placeholder(&format!("Could not load preview: {error}"))
+        }
+        (None, None) => {
+          placeholder(&format!("{} Loading comments…", self.spinner.glyph()))
+        }
+        (Some(roots), None) if roots.is_empty() => {
+          placeholder("No comments yet")
+        }
+        (Some(roots), None) => roots
+          .into_iter()
+          .map(|entry| {
+            Self::comment_list_item(
+              entry,
+              self.state.bookmarks().contains(&entry.id.to_string()),
+              false,
+              0,
+              0,
+              &[],
+              None,
+              &context,
+            )
+          })
+          .collect(),
+      },
+    };
+
+    frame.render_widget(List::new(items), inner);
   }
 
   fn execute_effect(&mut self, effect: Effect) {
     match effect {
-      Effect::FetchComments {
+      Effect::CopyToClipboard { label, text } => {
+        match self.copy_to_clipboard(text) {
+          Ok(()) => {
+            self.state.set_transient_message(format!("Copied {label}"));
+          }
+          Err(error) => {
+            self.state.set_transient_message(format!(
+              "Could not copy {label}: {error}"
+            ));
+          }
+        }
+      }
+      Effect::FetchCommentChildren {
         item_id,
         request_id,
       } => {
@@ -209,32 +881,103 @@ impl App {
 
         let handle = self.handle.clone();
 
+        handle.spawn(async move {
+          let _ = sender.send(Event::CommentChildren {
+            request_id,
+            result: client.fresh().fetch_comment_children(item_id).await,
+          });
+        });
+      }
+      Effect::FetchComments {
+        fresh,
+        item_id,
+        request_id,
+        timeout,
+      } => {
+        let client = if fresh {
+          self.client.fresh()
+        } else {
+          self.client.clone()
+        };
+
+        let sender = self.event_tx.clone();
+
+        let handle = self.handle.clone();
+
         handle.spawn(async move {
           let _ = sender.send(Event::Comments {
             request_id,
-            result: client.fetch_thread(item_id).await,
+            result: client.fetch_thread(item_id, timeout).await,
           });
         });
       }
       Effect::FetchTabItems {
-        tab_index,
         category,
+        fresh,
         offset,
+        request_id,
+      } => {
+        let client = if fresh {
+          self.client.fresh()
+        } else {
+          self.client.clone()
+        };
+
+        let sender = self.event_tx.clone();
+
+        let handle = self.handle.clone();
+
+        handle.spawn(async move {
+          let result = match client
+            .fetch_category_items(category, offset, client.batch_size())
+            .await
+          {
+            Err(_) if client.is_offline() => Ok(Vec::new()),
+            result => result,
+          };
+
+          let _ = sender.send(Event::TabItems { request_id, result });
+        });
+      }
+      Effect::FetchNewerTabItems {
+        category,
+        request_id,
+      } => {
+        let (client, sender) = (self.client.clone(), self.event_tx.clone());
+
+        let handle = self.handle.clone();
+
+        handle.spawn(async move {
+          let _ = sender.send(Event::NewerTabItems {
+            request_id,
+            result: client
+              .fetch_category_items(category, 0, client.batch_size())
+              .await,
+          });
+        });
+      }
+      Effect::FetchPreview {
+        item_id,
+        request_id,
       } => {
         let (client, sender) = (self.client.clone(), self.event_tx.clone());
 
         let handle = self.handle.clone();
 
         handle.spawn(async move {
-          let _ = sender.send(Event::TabItems {
-            tab_index,
+          let _ = sender.send(Event::Preview {
+            request_id,
             result: client
-              .fetch_category_items(category, offset, INITIAL_BATCH_SIZE)
+              .fetch_top_comments(item_id, Preview::ROOT_COUNT)
               .await,
           });
         });
       }
-      Effect::FetchSearchResults { query, request_id } => {
+      Effect::FetchSearchResults {
+        query,
+        request_id,
+        sort,
+      } => {
         let (client, sender) = (self.client.clone(), self.event_tx.clone());
 
         let handle = self.handle.clone();
@@ -242,41 +985,280 @@ impl App {
         handle.spawn(async move {
           let _ = sender.send(Event::SearchResults {
             request_id,
-            result: client.search_stories(&query, 0, INITIAL_BATCH_SIZE).await,
+            result: client
+              .search_stories(&query, sort, 0, client.batch_size())
+              .await,
           });
         });
       }
-      Effect::OpenUrl { url } => match webbrowser::open(&url) {
-        Ok(()) => {
-          self.state.set_transient_message(format!(
-            "Opened in browser: {}",
-            truncate(&url, 80)
-          ));
-        }
-        Err(error) => {
-          self
-            .state
-            .set_transient_message(format!("Could not open link: {error}"));
-        }
-      },
+      Effect::OpenUrl { url } => self.open_urls(vec![strip_tracking(
+        url,
+        self.state.config().keep_tracking(),
+      )]),
+      Effect::RunPager { text } => self.pager = Some(text),
     }
   }
 
-  pub(crate) fn new(
-    client: Client,
-    tabs: Vec<(Tab, ListView<ListEntry>)>,
-    bookmarks: Bookmarks,
-  ) -> Self {
-    let (event_tx, event_rx) = mpsc::unbounded_channel();
+  fn execute_effects(&mut self, effects: Vec<Effect>) {
+    let mut urls = Vec::new();
 
-    let state = State::new(tabs, bookmarks);
+    for effect in effects {
+      match effect {
+        Effect::OpenUrl { url } => {
+          urls.push(strip_tracking(url, self.state.config().keep_tracking()));
+        }
+        effect => self.execute_effect(effect),
+      }
+    }
+
+    if !urls.is_empty() {
+      self.open_urls(urls);
+    }
+  }
+
+  fn highlight_spans(
+    text: String,
+    query: Option<&str>,
+    style: Style,
+    theme: &Theme,
+  ) -> Vec<Span<'static>> {
+    let matches = query
+      .map(|query| find_matches(&text, query))
+      .unwrap_or_default();
+
+    if matches.is_empty() {
+      return vec![Span::styled(text, style)];
+    }
+
+    let highlight = style.fg(Color::Black).bg(theme.highlight);
+
+    let mut spans = Vec::new();
+
+    let mut cursor = 0;
+
+    for range in matches {
+      if cursor < range.start {
+        spans.push(Span::styled(text[cursor..range.start].to_string(), style));
+      }
+
+      spans.push(Span::styled(text[range.clone()].to_string(), highlight));
+
+      cursor = range.end;
+    }
+
+    if cursor < text.len() {
+      spans.push(Span::styled(text[cursor..].to_string(), style));
+    }
+
+    spans
+  }
+
+  fn key_command(&mut self, key: KeyEvent) -> Command {
+    if self.state.help_is_visible() {
+      HelpView::handle_key(key, self.state.config().keymap())
+    } else if let Some(command) = self.state.tab_switcher_command(key) {
+      command
+    } else if let Some(command) = self
+      .state
+      .command_palette_command(key, self.state.list_height().max(1))
+    {
+      command
+    } else if let Some(command) = self.state.search_input_command(key) {
+      command
+    } else {
+      let page = self.state.list_height().max(1);
+      self.state.handle_key(key, page)
+    }
+  }
+
+  fn list_height(terminal_height: u16) -> usize {
+    // Mirrors the vertical layout in `draw`: a one-row margin on each side,
+    // two rows of tabs and one status row.
+    usize::from(terminal_height.saturating_sub(5))
+  }
+
+  fn listing_item<'a>(
+    entry: &'a CommentEntry,
+    bookmarked: bool,
+    available_width: u16,
+    theme: &Theme,
+    separator: &Separator,
+  ) -> ListItem<'a> {
+    let marker = if bookmarked { BOOKMARK_MARKER } else { "" };
+
+    let width = (available_width as usize)
+      .saturating_sub(BASE_INDENT.len() + marker.chars().count())
+      .max(1);
+
+    ListItem::new(vec![
+      Line::from(vec![
+        Span::raw(BASE_INDENT),
+        Span::raw(marker),
+        Span::styled(
+          truncate_words(entry.listing_title(), width),
+          Style::default().fg(theme.title),
+        ),
+      ]),
+      Line::from(vec![
+        Span::raw(BASE_INDENT),
+        Span::styled(entry.listing_detail(), Style::default().fg(theme.detail)),
+      ]),
+      separator.line(BASE_INDENT, available_width),
+    ])
+  }
+
+  pub(crate) fn load_tabs(&mut self, lazy: bool) -> Result {
+    self.state.start_loading_tabs(lazy)?;
+
+    let effects = self.state.take_pending_effects();
+
+    self.execute_effects(effects);
+
+    Ok(())
+  }
 
-    Self {
+  fn mouse_command(&mut self, mouse: MouseEvent) -> Command {
+    let command = match mouse.kind {
+      MouseEventKind::ScrollDown => Command::SelectNext,
+      MouseEventKind::ScrollUp => Command::SelectPrevious,
+      MouseEventKind::Down(MouseButton::Left) => {
+        let Some(target) = self.click_target(mouse.row) else {
+          return Command::None;
+        };
+
+        let now = Instant::now();
+
+        let double_click = self.last_click.is_some_and(|(time, previous)| {
+          previous == target
+            && now.duration_since(time) < Self::DOUBLE_CLICK_INTERVAL
+        });
+
+        self.last_click = (!double_click).then_some((now, target));
+
+        if double_click && matches!(self.state.mode(), Mode::List(_)) {
+          Command::OpenComments
+        } else {
+          Command::SelectIndex(target)
+        }
+      }
+      _ => return Command::None,
+    };
+
+    let page = self.state.list_height().max(1);
+
+    self.state.handle_mouse(command, page)
+  }
+
+  pub(crate) fn new(
+    client: Client,
+    tabs: Vec<(Tab, ListView<ListEntry>)>,
+    bookmarks: Bookmarks,
+    read_items: ReadItems,
+    saved_threads: SavedThreads,
+    followed_users: FollowedUsers,
+    config: Config,
+    session: &Session,
+  ) -> Result<Self> {
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+
+    let mut state = State::new(
+      tabs,
+      bookmarks,
+      read_items,
+      saved_threads,
+      followed_users,
+      config,
+    );
+
+    state.set_batch_size(client.batch_size());
+
+    state.restore_session(session)?;
+
+    let effects = state.take_pending_effects();
+
+    let mut app = Self {
+      click_targets: Vec::new(),
       client,
+      clipboard: None,
       event_rx,
       event_tx,
       handle: Handle::current(),
+      last_click: None,
+      pager: None,
+      preview_visible: false,
+      search_debounce_at: None,
+      spinner: Spinner::default(),
       state,
+    };
+
+    app.execute_effects(effects);
+
+    Ok(app)
+  }
+
+  pub(crate) fn open_item(&mut self, id: u64, comment: Option<u64>) {
+    self.state.open_item(id, comment);
+
+    let effects = self.state.take_pending_effects();
+
+    self.execute_effects(effects);
+  }
+
+  fn open_url(&mut self, url: &str) -> Result<(Opener, Vec<String>)> {
+    let mut failures = Vec::new();
+
+    for opener in self.state.config().openers().to_vec() {
+      let result = match opener {
+        Opener::Browser if Opener::browser_command().is_none() => {
+          webbrowser::open(url).map_err(anyhow::Error::from)
+        }
+        Opener::Browser | Opener::Command => Opener::run_command(url),
+        Opener::Clipboard => self.copy_to_clipboard(url.to_string()),
+      };
+
+      match result {
+        Ok(()) => return Ok((opener, failures)),
+        Err(error) => failures.push(format!("{}: {error}", opener.name())),
+      }
+    }
+
+    if failures.is_empty() {
+      return Err(anyhow!("no openers are configured"));
+    }
+
+    Err(anyhow!(failures.join("; ")))
+  }
+
+  fn open_urls(&mut self, urls: Vec<String>) {
+    let count = urls.len();
+
+    for url in urls {
+      match self.open_url(&url) {
+        Ok((opener, failures)) => {
+          if count == 1 {
+            let message = opener.success_message(&url);
+
+            self.state.set_transient_message(if failures.is_empty() {
+              message
+            } else {
+              format!("{message} ({})", failures.join("; "))
+            });
+          }
+        }
+        Err(error) => {
+          self
+            .state
+            .set_transient_message(format!("Could not open link ({error})"));
+
+          return;
+        }
+      }
+    }
+
+    if count > 1 {
+      self
+        .state
+        .set_transient_message(format!("Opened {count} links"));
     }
   }
 
@@ -286,61 +1268,666 @@ impl App {
     while let Ok(event) = self.event_rx.try_recv() {
       self.state.handle_event(event);
     }
+
+    if self
+      .search_debounce_at
+      .is_some_and(|deadline| deadline <= Instant::now())
+    {
+      self.search_debounce_at = None;
+      self.state.search_incrementally();
+    }
+
+    self.state.update_preview(self.preview_visible);
+
+    let effects = self.state.take_pending_effects();
+
+    self.execute_effects(effects);
   }
 
   pub(crate) fn run(
     &mut self,
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    events: &mut dyn EventSource,
   ) -> Result {
     loop {
       self.process_pending_events();
 
+      if let Some(text) = self.pager.take() {
+        self.run_pager(terminal, &text)?;
+      }
+
+      let size = terminal.size()?;
+
+      self
+        .state
+        .help_mut()
+        .resize(Rect::new(0, 0, size.width, size.height));
+
       terminal.draw(|frame| self.draw(frame))?;
 
-      if !crossterm_event::poll(Duration::from_millis(200))? {
-        self.process_pending_events();
-        continue;
+      if self.step(events)? {
+        break;
+      }
+    }
+
+    Ok(())
+  }
+
+  fn run_pager(
+    &mut self,
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    text: &str,
+  ) -> Result {
+    let path = match Self::write_pager_file(text) {
+      Ok(path) => path,
+      Err(error) => {
+        self
+          .state
+          .set_transient_message(format!("Could not write comment: {error}"));
+        return Ok(());
       }
+    };
+
+    let pager = env::var("PAGER")
+      .ok()
+      .filter(|pager| !pager.trim().is_empty())
+      .unwrap_or_else(|| "less".to_string());
+
+    let mut parts = pager.split_whitespace();
+
+    let program = parts.next().unwrap_or("less").to_string();
+
+    if let Err(error) = restore_terminal(terminal) {
+      let _ = fs::remove_file(&path);
+      return Err(error);
+    }
+
+    let status = process::Command::new(&program)
+      .args(parts)
+      .arg(&path)
+      .status();
+
+    let resumed = resume_terminal(terminal);
+
+    let _ = fs::remove_file(&path);
+
+    resumed?;
+
+    match status {
+      Ok(status) if status.success() => {}
+      Ok(status) => self
+        .state
+        .set_transient_message(format!("`{program}` exited with {status}")),
+      Err(error) => self
+        .state
+        .set_transient_message(format!("Could not run `{program}`: {error}")),
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn set_message_timeout(&mut self, timeout: Duration) {
+    self.state.set_message_timeout(timeout);
+  }
 
-      let CrosstermEvent::Key(key) = crossterm_event::read()? else {
+  fn shown_offset(shown: &[usize], offset: usize) -> usize {
+    shown.partition_point(|&index| index < offset)
+  }
+
+  fn step(&mut self, events: &mut dyn EventSource) -> Result<bool> {
+    let timeout = self.search_debounce_at.map_or(Self::POLL_INTERVAL, |at| {
+      at.saturating_duration_since(Instant::now())
+        .min(Self::POLL_INTERVAL)
+    });
+
+    if !events.poll(timeout)? {
+      if self.state.is_loading() {
+        self.spinner.advance();
+      }
+
+      self.process_pending_events();
+      return Ok(false);
+    }
+
+    let query = self.state.incremental_query().map(str::to_string);
+
+    let command = match events.read()? {
+      CrosstermEvent::Key(key) if key.kind == KeyEventKind::Press => {
+        self.key_command(key)
+      }
+      CrosstermEvent::Mouse(mouse) => self.mouse_command(mouse),
+      CrosstermEvent::Resize(_, height) => {
+        let header = usize::from(self.comments_title().is_some());
+
+        self
+          .state
+          .set_list_height(Self::list_height(height).saturating_sub(header));
+        return Ok(false);
+      }
+      _ => {
         self.process_pending_events();
-        continue;
-      };
+        return Ok(false);
+      }
+    };
+
+    if self.state.incremental_query() != query.as_deref() {
+      self.search_debounce_at = Some(Instant::now() + Self::SEARCH_DEBOUNCE);
+    }
 
-      if key.kind != KeyEventKind::Press {
+    match self.state.dispatch_command(command) {
+      Ok(dispatch) => {
+        self.execute_effects(dispatch.effects);
+
+        if dispatch.should_exit {
+          return Ok(true);
+        }
+
+        self.process_pending_events();
+      }
+      Err(error) => {
+        self.state.clear_pending_effects();
+        self.state.set_persistent_message(format!("error: {error}"));
         self.process_pending_events();
-        continue;
       }
+    }
 
-      let command = if self.state.help_is_visible() {
-        HelpView::handle_key(key)
-      } else if let Some(command) = self.state.search_input_command(key) {
-        command
-      } else {
-        let page = self.state.list_height().max(1);
-        self.state.mode_mut().handle_key(key, page)
-      };
+    Ok(false)
+  }
 
-      match self.state.dispatch_command(command) {
-        Ok(dispatch) => {
-          for effect in dispatch.effects {
-            self.execute_effect(effect);
-          }
+  pub(crate) fn store_session(&self, session: &mut Session) {
+    self.state.store_session(session);
+  }
 
-          if dispatch.should_exit {
-            break;
-          }
+  fn tab_title(tab: &Tab, count: Option<usize>, loading: bool) -> String {
+    let label = tab.label.to_uppercase();
 
-          self.process_pending_events();
-        }
-        Err(error) => {
-          self.state.clear_pending_effects();
-          self.state.set_transient_message(format!("error: {error}"));
-          self.process_pending_events();
+    if loading && !tab.loaded {
+      return format!("{label} (loading…)");
+    }
+
+    match count {
+      Some(count) if tab.has_more => format!("{label} ({count}+)"),
+      Some(count) => format!("{label} ({count})"),
+      None => label,
+    }
+  }
+
+  fn with_loading_row<'a>(
+    mut list_items: Vec<ListItem<'a>>,
+    is_loading: bool,
+    theme: &Theme,
+  ) -> Vec<ListItem<'a>> {
+    if is_loading {
+      list_items.push(ListItem::new(Line::from(vec![
+        Span::raw(BASE_INDENT),
+        Span::styled(LOADING_MORE_ROW, Style::default().fg(theme.detail)),
+      ])));
+    }
+
+    list_items
+  }
+
+  fn write_pager_file(text: &str) -> io::Result<PathBuf> {
+    let nanos = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .unwrap_or_default()
+      .as_nanos();
+
+    for attempt in 0.. {
+      let path = env::temp_dir()
+        .join(format!("hn-pager-{}-{nanos}-{attempt}.txt", process::id()));
+
+      match fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+      {
+        Ok(mut file) => {
+          file.write_all(text.as_bytes())?;
+          return Ok(path);
         }
+        Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(error) => return Err(error),
       }
     }
 
-    Ok(())
+    unreachable!()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, std::collections::VecDeque};
+
+  struct ScriptedEvents(VecDeque<CrosstermEvent>);
+
+  impl ScriptedEvents {
+    fn keys(keys: &str) -> Self {
+      Self(
+        keys
+          .chars()
+          .map(|ch| {
+            let code = match ch {
+              '\n' => KeyCode::Enter,
+              ch => KeyCode::Char(ch),
+            };
+
+            CrosstermEvent::Key(KeyEvent::new(code, KeyModifiers::NONE))
+          })
+          .collect(),
+      )
+    }
+  }
+
+  impl EventSource for ScriptedEvents {
+    fn poll(&mut self, _timeout: Duration) -> Result<bool> {
+      Ok(!self.0.is_empty())
+    }
+
+    fn read(&mut self) -> Result<CrosstermEvent> {
+      self
+        .0
+        .pop_front()
+        .ok_or_else(|| anyhow!("script exhausted"))
+    }
+  }
+
+  fn app() -> App {
+    let path = env::temp_dir().join(format!(
+      "hn_app_test_{}.json",
+      SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time before UNIX_EPOCH")
+        .as_nanos()
+    ));
+
+    let bookmarks = Bookmarks::load_from(path).expect("load bookmarks");
+
+    let mut client = Client::default();
+
+    client.set_offline(true);
+
+    App::new(
+      client,
+      vec![(Tab::new(Category::all()[0]), ListView::new(Vec::new()))],
+      bookmarks,
+      ReadItems::default(),
+      SavedThreads::default(),
+      FollowedUsers::default(),
+      Config::default(),
+      &Session::default(),
+    )
+    .expect("create app")
+  }
+
+  fn play(app: &mut App, events: &mut ScriptedEvents) -> bool {
+    while !events.0.is_empty() {
+      if app.step(events).expect("step succeeds") {
+        return true;
+      }
+    }
+
+    false
+  }
+
+  #[tokio::test]
+  async fn scripted_keys_search_then_submit() {
+    let mut app = app();
+
+    assert!(!play(&mut app, &mut ScriptedEvents::keys("/rust\n")));
+
+    let active = app.state.resolved_active_tab().unwrap();
+
+    assert!(matches!(
+      app.state.tab(active).unwrap().category.kind,
+      CategoryKind::Search
+    ));
+
+    assert_eq!(app.state.message(), "Searching for \"rust\"...");
+
+    assert!(play(&mut app, &mut ScriptedEvents::keys("q")));
+  }
+
+  fn context(followed_users: &FollowedUsers) -> RenderContext<'_> {
+    RenderContext {
+      contrast: Contrast::Dim,
+      followed_users,
+      now: 0,
+      query: None,
+      separator: Separator::default(),
+      theme: Theme::default(),
+      tree_guides: TreeGuides::None,
+      width: 40,
+      wrap: true,
+    }
+  }
+
+  fn entry(body: &str) -> CommentEntry {
+    entry_at_depth(body, 0)
+  }
+
+  fn entry_at_depth(body: &str, depth: usize) -> CommentEntry {
+    CommentEntry {
+      author: Some("user".to_string()),
+      body: body.to_string(),
+      children: Vec::new(),
+      dead: false,
+      deleted: false,
+      depth,
+      expanded: true,
+      folded: Vec::new(),
+      html: None,
+      id: 1,
+      is_op: false,
+      is_post: false,
+      links: Vec::new(),
+      loaded: true,
+      parent: None,
+      time: None,
+    }
+  }
+
+  #[test]
+  fn click_targets_map_rows_to_items_from_offset() {
+    let area = Rect::new(0, 2, 10, 5);
+
+    assert_eq!(
+      App::click_targets(area, &[2, 3, 1, 4], &[4, 5, 6, 7], 1),
+      vec![(2..5, 5), (5..6, 6), (6..7, 7)]
+    );
+
+    assert_eq!(
+      App::click_targets(area, &[1, 1, 1], &[0, 1], 0),
+      vec![(2..3, 0), (3..4, 1)]
+    );
+  }
+
+  #[test]
+  fn pager_files_are_unique_and_hold_the_text() {
+    let first = App::write_pager_file("a & b\n").unwrap();
+    let second = App::write_pager_file("a & b\n").unwrap();
+
+    assert_ne!(first, second);
+    assert_eq!(fs::read_to_string(&first).unwrap(), "a & b\n");
+
+    fs::remove_file(first).unwrap();
+    fs::remove_file(second).unwrap();
+  }
+
+  #[test]
+  fn shown_offset_maps_raw_offsets_onto_filtered_rows() {
+    let shown = [1, 4, 5, 9];
+
+    assert_eq!(App::shown_offset(&shown, 0), 0);
+    assert_eq!(App::shown_offset(&shown, 4), 1);
+    assert_eq!(App::shown_offset(&shown, 6), 3);
+    assert_eq!(App::shown_offset(&shown, 12), 4);
+  }
+
+  #[test]
+  fn tab_title_counts_loaded_entries_and_marks_more() {
+    let mut tab = Tab {
+      category: Category::all()[0],
+      has_more: true,
+      label: "top".into(),
+      loaded: true,
+    };
+
+    assert_eq!(App::tab_title(&tab, None, false), "TOP");
+    assert_eq!(App::tab_title(&tab, Some(30), true), "TOP (30+)");
+
+    tab.has_more = false;
+
+    assert_eq!(App::tab_title(&tab, Some(42), false), "TOP (42)");
+
+    assert_eq!(
+      App::tab_title(&Tab::new(Category::all()[0]), None, true),
+      "TOP (loading…)"
+    );
+  }
+
+  #[test]
+  fn loading_row_is_appended_only_while_loading() {
+    let items = || vec![ListItem::new("first"), ListItem::new("second")];
+
+    assert_eq!(
+      App::with_loading_row(items(), true, &Theme::default()).len(),
+      3
+    );
+    assert_eq!(
+      App::with_loading_row(items(), false, &Theme::default()).len(),
+      2
+    );
+  }
+
+  #[test]
+  fn comment_list_item_wraps_long_paragraphs_by_default() {
+    let entry = entry("one two three four five six");
+
+    let followed_users = FollowedUsers::default();
+
+    let item = App::comment_list_item(
+      &entry,
+      false,
+      false,
+      0,
+      2,
+      &[],
+      None,
+      &RenderContext {
+        width: 10,
+        ..context(&followed_users)
+      },
+    );
+
+    assert!(item.height() > 3);
+  }
+
+  #[test]
+  fn comment_list_item_keeps_paragraph_on_one_line_without_wrapping() {
+    let entry = entry("one two three four five six");
+
+    let followed_users = FollowedUsers::default();
+
+    let item = App::comment_list_item(
+      &entry,
+      false,
+      false,
+      0,
+      2,
+      &[],
+      None,
+      &RenderContext {
+        width: 10,
+        wrap: false,
+        ..context(&followed_users)
+      },
+    );
+
+    assert_eq!(item.height(), 3);
+  }
+
+  #[test]
+  fn comment_lines_shift_unwrapped_body_by_horizontal_offset() {
+    let entry = entry("let value = compute();");
+
+    let followed_users = FollowedUsers::default();
+
+    let lines = App::comment_lines(
+      &entry,
+      false,
+      false,
+      4,
+      2,
+      &[],
+      None,
+      &RenderContext {
+        width: 10,
+        wrap: false,
+        ..context(&followed_users)
+      },
+    );
+
+    let body = lines[1]
+      .spans
+      .iter()
+      .map(|span| span.content.as_ref())
+      .collect::<String>();
+
+    assert!(body.ends_with("value = compute();"));
+    assert!(!body.contains("let"));
+  }
+
+  #[test]
+  fn comment_lines_highlight_search_matches() {
+    let entry = entry("Rust and rust");
+
+    let followed_users = FollowedUsers::default();
+
+    let lines = App::comment_lines(
+      &entry,
+      false,
+      false,
+      0,
+      2,
+      &[],
+      None,
+      &RenderContext {
+        query: Some("rust"),
+        ..context(&followed_users)
+      },
+    );
+
+    let highlighted = lines[1]
+      .spans
+      .iter()
+      .filter(|span| span.style.bg == Some(Color::Yellow))
+      .map(|span| span.content.as_ref())
+      .collect::<Vec<&str>>();
+
+    assert_eq!(highlighted, ["Rust", "rust"]);
+
+    assert_eq!(lines[1].spans[2].content, " and ");
+    assert_eq!(
+      lines[1].spans[2].style,
+      Contrast::Dim.body_style(&Theme::default())
+    );
+  }
+
+  #[test]
+  fn comment_lines_append_parent_reference_to_header() {
+    let entry = entry_at_depth("body", 2);
+
+    let followed_users = FollowedUsers::default();
+
+    let lines = App::comment_lines(
+      &entry,
+      false,
+      false,
+      0,
+      0,
+      &[],
+      Some("↳ parent".to_string()),
+      &RenderContext {
+        contrast: Contrast::Normal,
+        ..context(&followed_users)
+      },
+    );
+
+    let header = lines[0]
+      .spans
+      .iter()
+      .map(|span| span.content.as_ref())
+      .collect::<String>();
+
+    assert!(header.starts_with(BASE_INDENT));
+    assert!(header.ends_with(" ↳ parent"));
+
+    assert_eq!(
+      lines[1].spans[1].style,
+      Contrast::Normal.body_style(&Theme::default())
+    );
+  }
+
+  #[test]
+  fn comment_lines_mark_and_style_followed_authors() {
+    let entry = entry("body");
+    let theme = Theme::default();
+
+    let mut followed_users = FollowedUsers::default();
+
+    let render = |followed_users: &FollowedUsers| {
+      App::comment_lines(
+        &entry,
+        false,
+        false,
+        0,
+        2,
+        &[],
+        None,
+        &RenderContext {
+          contrast: Contrast::Normal,
+          ..context(followed_users)
+        },
+      )
+    };
+
+    let header = |lines: &[Line]| {
+      lines[0]
+        .spans
+        .iter()
+        .map(|span| span.content.to_string())
+        .collect::<String>()
+    };
+
+    assert!(!header(&render(&followed_users)).contains(FOLLOWED_MARKER));
+
+    followed_users.toggle("user").unwrap();
+
+    let lines = render(&followed_users);
+
+    assert!(header(&lines).contains("user"));
+    assert!(header(&lines).ends_with(FOLLOWED_MARKER));
+    assert!(
+      lines[0]
+        .spans
+        .iter()
+        .filter(|span| span.content.contains("user"))
+        .all(|span| span.style.fg == Some(theme.highlight))
+    );
+  }
+
+  #[test]
+  fn comment_indent_draws_tree_guides_and_continues_them_in_the_body() {
+    let entry = entry_at_depth("body", 2);
+
+    let (indent, body_indent, wrap_width) = App::comment_indent(
+      &entry,
+      40,
+      2,
+      &[Guide::Pipe, Guide::Last],
+      TreeGuides::Unicode,
+    );
+
+    assert_eq!(indent, format!("{BASE_INDENT}│ └─"));
+    assert_eq!(body_indent, format!("{BASE_INDENT}│   "));
+    assert_eq!(wrap_width, 35);
+  }
+
+  #[test]
+  fn comment_indent_width_adjusts_indent_and_wrap_width() {
+    let entry = entry_at_depth("body", 2);
+
+    let (indent, _, wrap_width) =
+      App::comment_indent(&entry, 40, 2, &[], TreeGuides::None);
+
+    assert_eq!(indent, format!("{BASE_INDENT}    "));
+    assert_eq!(wrap_width, 35);
+
+    let (indent, _, wrap_width) =
+      App::comment_indent(&entry, 40, 4, &[], TreeGuides::None);
+
+    assert_eq!(indent, format!("{BASE_INDENT}        "));
+    assert_eq!(wrap_width, 31);
   }
 }
@@ -0,0 +1,111 @@
+use super::*;
+
+#[derive(Debug, Default, Parser)]
+#[command(about, version)]
+pub(crate) struct Arguments {
+  #[arg(
+    long,
+    help = "Only fetch the first tab at startup, loading others when opened"
+  )]
+  pub(crate) lazy: bool,
+  #[arg(long, help = "Only show cached content, never touching the network")]
+  offline: bool,
+  #[arg(
+    long,
+    value_delimiter = ',',
+    value_name = "TABS",
+    help = "Only load the given comma-separated tabs, e.g. `top,ask`"
+  )]
+  only: Vec<String>,
+  #[command(subcommand)]
+  pub(crate) subcommand: Option<Subcommand>,
+}
+
+impl Arguments {
+  pub(crate) fn categories(&self) -> Result<Vec<Category>> {
+    if self.only.is_empty() {
+      return Ok(Category::all().to_vec());
+    }
+
+    let mut categories = Vec::with_capacity(self.only.len());
+
+    for label in &self.only {
+      let category = Self::category(label)?;
+
+      if !categories
+        .iter()
+        .any(|existing: &Category| existing.label == category.label)
+      {
+        categories.push(category);
+      }
+    }
+
+    Ok(categories)
+  }
+
+  pub(crate) fn category(label: &str) -> Result<Category> {
+    let label = label.trim();
+
+    Category::all()
+      .iter()
+      .find(|category| category.label.eq_ignore_ascii_case(label))
+      .copied()
+      .ok_or_else(|| {
+        let known = Category::all()
+          .iter()
+          .map(|category| category.label)
+          .collect::<Vec<_>>()
+          .join(", ");
+
+        anyhow!("unknown tab `{label}`, expected one of: {known}")
+      })
+  }
+
+  pub(crate) fn offline(&self) -> bool {
+    self.offline || env::var("HN_OFFLINE").is_ok_and(|value| value == "1")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn labels(arguments: &[&str]) -> Result<Vec<&'static str>> {
+    Ok(
+      Arguments::try_parse_from(arguments)?
+        .categories()?
+        .iter()
+        .map(|category| category.label)
+        .collect(),
+    )
+  }
+
+  #[test]
+  fn categories_default_to_all_tabs() {
+    assert_eq!(
+      labels(&["hn"]).unwrap(),
+      Category::all()
+        .iter()
+        .map(|category| category.label)
+        .collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  fn only_restricts_categories_in_given_order() {
+    assert_eq!(
+      labels(&["hn", "--only", "ask,top"]).unwrap(),
+      ["ask", "top"]
+    );
+  }
+
+  #[test]
+  fn only_ignores_duplicates_and_case() {
+    assert_eq!(labels(&["hn", "--only", "TOP,top"]).unwrap(), ["top"]);
+  }
+
+  #[test]
+  fn only_rejects_unknown_tabs() {
+    assert!(labels(&["hn", "--only", "top,nope"]).is_err());
+  }
+}
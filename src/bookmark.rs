@@ -2,38 +2,31 @@ use super::*;
 
 #[derive(Debug)]
 pub(crate) struct Bookmarks {
-  entries: Vec<ListEntry>,
+  backup: Option<PathBuf>,
+  entries: Vec<BookmarkEntry>,
   ids: HashSet<String>,
   path: PathBuf,
 }
 
 impl Bookmarks {
-  fn bookmarks_path() -> Result<PathBuf> {
-    if let Ok(path) = env::var("HN_BOOKMARKS_FILE") {
-      return Ok(PathBuf::from(path));
-    }
-
-    let base_dir = if let Ok(dir) = env::var("XDG_CONFIG_HOME") {
-      PathBuf::from(dir)
-    } else if let Ok(home) = env::var("HOME") {
-      PathBuf::from(home).join(".config")
-    } else {
-      env::current_dir()?.join(".config")
-    };
-
-    Ok(base_dir.join("hn").join("bookmarks.json"))
+  pub(crate) fn backup(&self) -> Option<&Path> {
+    self.backup.as_deref()
   }
 
-  fn ensure_parent_dir(path: &Path) -> Result {
-    if let Some(parent) = path.parent() {
-      fs::create_dir_all(parent)?;
-    }
+  fn bookmarks_path() -> Result<PathBuf> {
+    config_path("HN_BOOKMARKS_FILE", "bookmarks.json")
+  }
 
-    Ok(())
+  pub(crate) fn contains(&self, id: &str) -> bool {
+    self.ids.contains(id)
   }
 
   pub(crate) fn entries_vec(&self) -> Vec<ListEntry> {
-    self.entries.clone()
+    self
+      .entries
+      .iter()
+      .map(|bookmark| bookmark.entry.clone())
+      .collect()
   }
 
   pub(crate) fn is_empty(&self) -> bool {
@@ -41,32 +34,34 @@ impl Bookmarks {
   }
 
   pub(crate) fn load() -> Result<Self> {
-    let path = Self::bookmarks_path()?;
+    Self::load_from(Self::bookmarks_path()?)
+  }
 
-    let entries = if path.exists() {
-      let data = fs::read(&path)?;
+  pub(crate) fn load_from(path: PathBuf) -> Result<Self> {
+    let (file, backup) = read_json_file::<BookmarkFile>(&path)?;
 
-      if data.is_empty() {
-        Vec::new()
-      } else {
-        serde_json::from_slice::<Vec<ListEntry>>(&data)?
-      }
-    } else {
-      Vec::new()
-    };
+    let entries = file
+      .into_entries()
+      .with_context(|| format!("could not read `{}`", path.display()))?;
 
     let ids = entries
       .iter()
-      .map(|entry| entry.id.clone())
+      .map(|bookmark| bookmark.entry.id.clone())
       .collect::<HashSet<_>>();
 
-    Ok(Self { entries, ids, path })
+    Ok(Self {
+      backup,
+      entries,
+      ids,
+      path,
+    })
   }
 
   fn persist(&self) -> Result {
-    Self::ensure_parent_dir(&self.path)?;
+    ensure_parent_dir(&self.path)?;
 
-    let serialized = serde_json::to_vec_pretty(&self.entries)?;
+    let serialized =
+      serde_json::to_vec_pretty(&BookmarkFile::new(self.entries.clone()))?;
 
     fs::write(&self.path, serialized)?;
 
@@ -74,7 +69,11 @@ impl Bookmarks {
   }
 
   pub(crate) fn remove(&mut self, id: &str) -> Result<bool> {
-    if let Some(pos) = self.entries.iter().position(|entry| entry.id == id) {
+    if let Some(pos) = self
+      .entries
+      .iter()
+      .position(|bookmark| bookmark.entry.id == id)
+    {
       self.entries.remove(pos);
       self.ids.remove(id);
       self.persist()?;
@@ -84,12 +83,50 @@ impl Bookmarks {
     }
   }
 
+  pub(crate) fn set_tags(
+    &mut self,
+    id: &str,
+    tags: Vec<String>,
+  ) -> Result<bool> {
+    let Some(bookmark) = self
+      .entries
+      .iter_mut()
+      .find(|bookmark| bookmark.entry.id == id)
+    else {
+      return Ok(false);
+    };
+
+    bookmark.entry.tags = tags;
+
+    self.persist()?;
+
+    Ok(true)
+  }
+
+  pub(crate) fn tags(&self) -> Vec<String> {
+    self
+      .entries
+      .iter()
+      .flat_map(|bookmark| bookmark.entry.tags.iter().cloned())
+      .collect::<BTreeSet<String>>()
+      .into_iter()
+      .collect()
+  }
+
+  pub(crate) fn tags_for(&self, id: &str) -> Option<&[String]> {
+    self
+      .entries
+      .iter()
+      .find(|bookmark| bookmark.entry.id == id)
+      .map(|bookmark| bookmark.entry.tags.as_slice())
+  }
+
   pub(crate) fn toggle(&mut self, entry: &ListEntry) -> Result<bool> {
     if self.ids.contains(&entry.id) {
       self.remove(&entry.id)?;
       Ok(false)
     } else {
-      self.entries.insert(0, entry.clone());
+      self.entries.insert(0, BookmarkEntry::new(entry.clone()));
       self.ids.insert(entry.id.clone());
       self.persist()?;
       Ok(true)
@@ -152,8 +189,12 @@ mod tests {
 
   fn sample_entry(id: &str) -> ListEntry {
     ListEntry {
+      comments: None,
       detail: Some("detail".to_string()),
       id: id.to_string(),
+      score: None,
+      tags: Vec::new(),
+      time: None,
       title: format!("Entry {id}"),
       url: Some(format!("https://example.com/{id}")),
     }
@@ -168,8 +209,12 @@ mod tests {
 
       let entry = sample_entry("1");
 
+      assert!(!bookmarks.contains("1"));
+
       assert!(bookmarks.toggle(&entry).unwrap());
       assert!(!bookmarks.is_empty());
+      assert!(bookmarks.contains("1"));
+      assert!(!bookmarks.contains("2"));
 
       assert_eq!(
         bookmarks
@@ -181,6 +226,116 @@ mod tests {
 
       assert!(!bookmarks.toggle(&entry).unwrap());
       assert!(bookmarks.is_empty());
+      assert!(!bookmarks.contains("1"));
+    });
+  }
+
+  #[test]
+  fn load_recovers_from_corrupt_file() {
+    with_temp_env(|path| {
+      fs::write(path, "[{\"id\": ").unwrap();
+
+      let bookmarks = Bookmarks::load().unwrap();
+
+      assert!(bookmarks.is_empty());
+
+      let backup = bookmarks.backup().expect("corrupt file is backed up");
+
+      assert_eq!(backup.extension().unwrap(), "bak");
+      assert_eq!(fs::read_to_string(backup).unwrap(), "[{\"id\": ");
+
+      let _ = fs::remove_file(backup);
+    });
+  }
+
+  #[test]
+  fn load_migrates_legacy_array_to_versioned_file() {
+    with_temp_env(|path| {
+      fs::write(
+        path,
+        serde_json::to_vec(&vec![sample_entry("1"), sample_entry("2")])
+          .unwrap(),
+      )
+      .unwrap();
+
+      let mut bookmarks = Bookmarks::load().unwrap();
+
+      assert!(bookmarks.backup().is_none());
+
+      assert_eq!(
+        bookmarks
+          .entries_vec()
+          .iter()
+          .map(|entry| entry.id.as_str())
+          .collect::<Vec<_>>(),
+        ["1", "2"]
+      );
+
+      bookmarks.toggle(&sample_entry("3")).unwrap();
+
+      let value =
+        serde_json::from_slice::<Value>(&fs::read(path).unwrap()).unwrap();
+
+      assert_eq!(value["version"], 1);
+      assert_eq!(value["entries"][0]["id"], "3");
+      assert!(value["entries"][0]["saved_at"].is_u64());
+      assert_eq!(value["entries"][1]["id"], "1");
+      assert!(value["entries"][1]["saved_at"].is_null());
+
+      let reloaded = Bookmarks::load().unwrap();
+
+      assert_eq!(
+        reloaded
+          .entries_vec()
+          .iter()
+          .map(|entry| entry.id.as_str())
+          .collect::<Vec<_>>(),
+        ["3", "1", "2"]
+      );
+    });
+  }
+
+  #[test]
+  fn load_rejects_newer_file_versions_without_touching_them() {
+    with_temp_env(|path| {
+      let contents = r#"{"entries": [], "version": 2}"#;
+
+      fs::write(path, contents).unwrap();
+
+      let error = Bookmarks::load().unwrap_err();
+
+      assert!(
+        format!("{error:#}").contains("version 2 is newer"),
+        "unexpected error: {error:#}"
+      );
+      assert_eq!(fs::read_to_string(path).unwrap(), contents);
+    });
+  }
+
+  #[test]
+  fn set_tags_persists_and_lists_unique_tags() {
+    with_temp_env(|_| {
+      let mut bookmarks = Bookmarks::load().unwrap();
+
+      bookmarks.toggle(&sample_entry("1")).unwrap();
+      bookmarks.toggle(&sample_entry("2")).unwrap();
+
+      assert!(
+        bookmarks
+          .set_tags("1", vec!["rust".to_string(), "async".to_string()])
+          .unwrap()
+      );
+      assert!(bookmarks.set_tags("2", vec!["rust".to_string()]).unwrap());
+      assert!(!bookmarks.set_tags("3", vec!["rust".to_string()]).unwrap());
+
+      let reloaded = Bookmarks::load().unwrap();
+
+      assert_eq!(reloaded.tags(), ["async", "rust"]);
+      assert_eq!(
+        reloaded.tags_for("1"),
+        Some(["rust".to_string(), "async".to_string()].as_slice())
+      );
+      assert_eq!(reloaded.tags_for("3"), None);
     });
   }
 
@@ -0,0 +1,21 @@
+use super::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct BookmarkEntry {
+  #[serde(flatten)]
+  pub(crate) entry: ListEntry,
+  #[serde(default)]
+  pub(crate) saved_at: Option<u64>,
+}
+
+impl BookmarkEntry {
+  pub(crate) fn new(entry: ListEntry) -> Self {
+    Self {
+      entry,
+      saved_at: SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|elapsed| elapsed.as_secs()),
+    }
+  }
+}
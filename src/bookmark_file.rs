@@ -0,0 +1,47 @@
+use super::*;
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub(crate) enum BookmarkFile {
+  Legacy(Vec<ListEntry>),
+  Versioned {
+    entries: Vec<BookmarkEntry>,
+    version: u32,
+  },
+}
+
+impl Default for BookmarkFile {
+  fn default() -> Self {
+    Self::new(Vec::new())
+  }
+}
+
+impl BookmarkFile {
+  const VERSION: u32 = 1;
+
+  pub(crate) fn into_entries(self) -> Result<Vec<BookmarkEntry>> {
+    match self {
+      Self::Legacy(entries) => Ok(
+        entries
+          .into_iter()
+          .map(|entry| BookmarkEntry {
+            entry,
+            saved_at: None,
+          })
+          .collect(),
+      ),
+      Self::Versioned { version, .. } if version > Self::VERSION => bail!(
+        "bookmarks file version {version} is newer than the supported version {}",
+        Self::VERSION
+      ),
+      Self::Versioned { entries, .. } => Ok(entries),
+    }
+  }
+
+  pub(crate) fn new(entries: Vec<BookmarkEntry>) -> Self {
+    Self::Versioned {
+      entries,
+      version: Self::VERSION,
+    }
+  }
+}
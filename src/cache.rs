@@ -0,0 +1,177 @@
+use super::*;
+
+#[derive(Clone, Debug)]
+pub(crate) struct Cache {
+  category_ttls: BTreeMap<String, Duration>,
+  dir: PathBuf,
+  item_ttl: Duration,
+}
+
+impl Cache {
+  pub(crate) const DEFAULT_ITEM_TTL: Duration = Duration::from_mins(15);
+
+  pub(crate) const STORY_IDS_TTL: Duration = Duration::from_mins(1);
+
+  pub(crate) fn dir() -> Result<PathBuf> {
+    if let Ok(dir) = env::var("HN_CACHE_DIR") {
+      Ok(PathBuf::from(dir))
+    } else if let Ok(dir) = env::var("XDG_CACHE_HOME") {
+      Ok(PathBuf::from(dir).join("hn"))
+    } else if let Ok(home) = env::var("HOME") {
+      Ok(PathBuf::from(home).join(".cache").join("hn"))
+    } else {
+      Ok(env::current_dir()?.join(".cache").join("hn"))
+    }
+  }
+
+  pub(crate) fn item_ttl(&self) -> Duration {
+    self.item_ttl
+  }
+
+  pub(crate) fn new() -> Result<Self> {
+    Ok(Self {
+      category_ttls: BTreeMap::new(),
+      dir: Self::dir()?,
+      item_ttl: Self::DEFAULT_ITEM_TTL,
+    })
+  }
+
+  fn path(&self, kind: &str, key: &str) -> PathBuf {
+    self.dir.join(kind).join(format!("{key}.json"))
+  }
+
+  pub(crate) fn read<T: DeserializeOwned>(
+    &self,
+    kind: &str,
+    key: &str,
+    ttl: Duration,
+  ) -> Option<T> {
+    let path = self.path(kind, key);
+
+    let age = fs::metadata(&path).ok()?.modified().ok()?.elapsed().ok()?;
+
+    if age > ttl {
+      return None;
+    }
+
+    serde_json::from_slice(&fs::read(path).ok()?).ok()
+  }
+
+  pub(crate) fn set_ttls(
+    &mut self,
+    item_ttl: Duration,
+    category_ttls: BTreeMap<String, Duration>,
+  ) {
+    self.item_ttl = item_ttl;
+    self.category_ttls = category_ttls;
+  }
+
+  pub(crate) fn story_ids_ttl(&self, label: &str) -> Duration {
+    self
+      .category_ttls
+      .get(label)
+      .copied()
+      .unwrap_or(Self::STORY_IDS_TTL)
+  }
+
+  pub(crate) fn write<T: Serialize>(
+    &self,
+    kind: &str,
+    key: &str,
+    value: &T,
+  ) -> Result {
+    let path = self.path(kind, key);
+
+    ensure_parent_dir(&path)?;
+
+    fs::write(path, serde_json::to_vec(value)?)?;
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn cache(name: &str) -> Cache {
+    Cache {
+      category_ttls: BTreeMap::new(),
+      dir: env::temp_dir()
+        .join(format!("hn_cache_test_{name}_{}", process::id())),
+      item_ttl: Cache::DEFAULT_ITEM_TTL,
+    }
+  }
+
+  #[test]
+  fn written_values_are_read_back_within_ttl() {
+    let cache = cache("round_trip");
+
+    cache.write("items", "1", &vec![1, 2, 3]).unwrap();
+
+    assert_eq!(
+      cache.read::<Vec<u64>>("items", "1", Cache::STORY_IDS_TTL),
+      Some(vec![1, 2, 3])
+    );
+
+    assert_eq!(
+      cache.read::<Vec<u64>>("items", "2", Cache::STORY_IDS_TTL),
+      None
+    );
+
+    fs::remove_dir_all(&cache.dir).unwrap();
+  }
+
+  #[test]
+  fn story_lists_use_per_category_ttls() {
+    let mut cache = cache("categories");
+
+    cache.set_ttls(
+      Cache::DEFAULT_ITEM_TTL,
+      BTreeMap::from([
+        ("best".to_string(), Duration::from_mins(30)),
+        ("new".to_string(), Duration::ZERO),
+      ]),
+    );
+
+    assert_eq!(cache.story_ids_ttl("best"), Duration::from_mins(30));
+    assert_eq!(cache.story_ids_ttl("top"), Cache::STORY_IDS_TTL);
+
+    cache.write("stories", "newstories", &vec![1]).unwrap();
+    cache.write("stories", "beststories", &vec![2]).unwrap();
+
+    assert_eq!(
+      cache.read::<Vec<u64>>(
+        "stories",
+        "newstories",
+        cache.story_ids_ttl("new")
+      ),
+      None
+    );
+    assert_eq!(
+      cache.read::<Vec<u64>>(
+        "stories",
+        "beststories",
+        cache.story_ids_ttl("best")
+      ),
+      Some(vec![2])
+    );
+
+    fs::remove_dir_all(&cache.dir).unwrap();
+  }
+
+  #[test]
+  fn stale_or_mismatched_entries_are_ignored() {
+    let cache = cache("stale");
+
+    cache.write("items", "1", &"text").unwrap();
+
+    assert_eq!(
+      cache.read::<Vec<u64>>("items", "1", Cache::STORY_IDS_TTL),
+      None
+    );
+    assert_eq!(cache.read::<String>("items", "1", Duration::ZERO), None);
+
+    fs::remove_dir_all(&cache.dir).unwrap();
+  }
+}
@@ -2,10 +2,25 @@
 pub enum CategoryKind {
   Bookmarks,
   Comments,
+  Front { days_ago: u64, now: Option<u64> },
+  Saved,
   Search,
   Stories(&'static str),
 }
 
+impl CategoryKind {
+  pub fn is_dynamic(self) -> bool {
+    matches!(
+      self,
+      CategoryKind::Bookmarks | CategoryKind::Saved | CategoryKind::Search
+    )
+  }
+
+  pub fn is_jobs(self) -> bool {
+    matches!(self, CategoryKind::Stories("jobstories"))
+  }
+}
+
 #[derive(Clone, Copy)]
 pub struct Category {
   pub kind: CategoryKind,
@@ -25,7 +40,10 @@ impl Category {
       },
       Category {
         label: "past",
-        kind: CategoryKind::Stories("topstories"),
+        kind: CategoryKind::Front {
+          days_ago: 1,
+          now: None,
+        },
       },
       Category {
         label: "comments",
@@ -45,4 +63,65 @@ impl Category {
       },
     ]
   }
+
+  pub fn web_url(self) -> Option<&'static str> {
+    match self.kind {
+      CategoryKind::Bookmarks | CategoryKind::Saved | CategoryKind::Search => {
+        None
+      }
+      CategoryKind::Comments => {
+        Some("https://news.ycombinator.com/newcomments")
+      }
+      CategoryKind::Front { .. } => Some("https://news.ycombinator.com/front"),
+      CategoryKind::Stories(endpoint) => match endpoint {
+        "topstories" => Some("https://news.ycombinator.com/news"),
+        "newstories" => Some("https://news.ycombinator.com/newest"),
+        "beststories" => Some("https://news.ycombinator.com/best"),
+        "askstories" => Some("https://news.ycombinator.com/ask"),
+        "showstories" => Some("https://news.ycombinator.com/show"),
+        "jobstories" => Some("https://news.ycombinator.com/jobs"),
+        _ => None,
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn web_url_maps_every_category_to_its_hn_page() {
+    let urls = Category::all()
+      .iter()
+      .map(|category| (category.label, category.web_url()))
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      urls,
+      [
+        ("top", Some("https://news.ycombinator.com/news")),
+        ("new", Some("https://news.ycombinator.com/newest")),
+        ("past", Some("https://news.ycombinator.com/front")),
+        ("comments", Some("https://news.ycombinator.com/newcomments")),
+        ("ask", Some("https://news.ycombinator.com/ask")),
+        ("show", Some("https://news.ycombinator.com/show")),
+        ("jobs", Some("https://news.ycombinator.com/jobs")),
+      ]
+    );
+  }
+
+  #[test]
+  fn web_url_is_none_for_dynamic_tabs() {
+    for kind in [CategoryKind::Bookmarks, CategoryKind::Search] {
+      assert_eq!(
+        Category {
+          kind,
+          label: "dynamic"
+        }
+        .web_url(),
+        None
+      );
+    }
+  }
 }
@@ -0,0 +1,75 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+#[command(name = "hn", about = "Print the stories in a tab and exit")]
+pub(crate) struct CategoryListing {
+  #[arg(help = "Tab to list, e.g. `top` or `ask`")]
+  category: String,
+  #[arg(long, help = "Print the stories as a json array")]
+  json: bool,
+  #[arg(long, help = "Number of stories to fetch, defaults to the batch size")]
+  limit: Option<usize>,
+}
+
+impl CategoryListing {
+  pub(crate) fn parse(arguments: Vec<String>) -> Self {
+    Self::parse_from(iter::once("hn".to_string()).chain(arguments))
+  }
+
+  pub(crate) async fn run(
+    self,
+    client: &Client,
+    keep_tracking: bool,
+  ) -> Result {
+    let category = Arguments::category(&self.category)?;
+
+    let entries = client
+      .fetch_category_items(
+        category,
+        0,
+        self.limit.unwrap_or(client.batch_size()),
+      )
+      .await
+      .with_context(|| format!("failed to load {} stories", category.label))?;
+
+    let format = if self.json {
+      ExportFormat::Json
+    } else {
+      ExportFormat::Markdown
+    };
+
+    print!("{}", Export::render(format, &entries, keep_tracking)?);
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn unknown_subcommands_are_parsed_as_tab_listings() {
+    let Some(Subcommand::Print(PrintSubcommand::Category(arguments))) =
+      Arguments::try_parse_from(["hn", "top", "--json", "--limit", "50"])
+        .unwrap()
+        .subcommand
+    else {
+      panic!("expected a tab listing");
+    };
+
+    let listing = CategoryListing::parse(arguments);
+
+    assert_eq!(listing.category, "top");
+    assert!(listing.json);
+    assert_eq!(listing.limit, Some(50));
+  }
+
+  #[test]
+  fn limit_defaults_to_the_client_batch_size() {
+    let listing = CategoryListing::parse(vec!["ask".to_string()]);
+
+    assert!(!listing.json);
+    assert_eq!(listing.limit, None);
+  }
+}
@@ -2,13 +2,31 @@ use super::*;
 
 #[derive(Clone)]
 pub(crate) struct Client {
+  batch_size: usize,
+  cache: Option<Cache>,
   client: reqwest::Client,
+  concurrency: usize,
+  fresh: bool,
+  offline: bool,
+  requests: Arc<Semaphore>,
 }
 
 impl Default for Client {
   fn default() -> Self {
+    let client = reqwest::Client::builder()
+      .timeout(Self::REQUEST_TIMEOUT)
+      .connect_timeout(Self::CONNECT_TIMEOUT)
+      .build()
+      .unwrap_or_default();
+
     Self {
-      client: reqwest::Client::new(),
+      batch_size: Self::DEFAULT_BATCH_SIZE,
+      cache: None,
+      client,
+      concurrency: Self::DEFAULT_CONCURRENCY,
+      fresh: false,
+      offline: false,
+      requests: Arc::new(Semaphore::new(Self::DEFAULT_MAX_REQUESTS)),
     }
   }
 }
@@ -16,17 +34,202 @@ impl Default for Client {
 impl Client {
   const API_BASE_URL: &str = "https://hacker-news.firebaseio.com/v0";
 
+  pub(crate) const BATCH_SIZE_RANGE: RangeInclusive<usize> = 10..=100;
+
   const COMMENTS_URL: &str =
     "https://hn.algolia.com/api/v1/search_by_date?tags=comment&hitsPerPage=";
 
+  pub(crate) const CONCURRENCY_RANGE: RangeInclusive<usize> = 1..=64;
+
+  const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+  pub(crate) const DEFAULT_BATCH_SIZE: usize = 30;
+
+  const DEFAULT_CONCURRENCY: usize = 16;
+
+  pub(crate) const DEFAULT_MAX_REQUESTS: usize = 24;
+
+  const ERROR_SNIPPET_LENGTH: usize = 80;
+
+  // With no query every hit is equally relevant, so Algolia falls back to
+  // ranking a day's front page by points, like news.ycombinator.com/front.
+  // `search_by_date` would list it newest first instead.
+  const FRONT_PAGE_URL: &str = Self::SEARCH_URL;
+
   const ITEM_URL: &str = "https://hacker-news.firebaseio.com/v0/item";
 
+  const LAZY_THREAD_THRESHOLD: u64 = 500;
+
+  const MAX_RETRIES: u32 = 3;
+
+  const OFFLINE_TTL: Duration = Duration::MAX;
+
+  const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+  const RETRY_DELAY: Duration = Duration::from_millis(200);
+
+  const SEARCH_BY_DATE_URL: &str =
+    "https://hn.algolia.com/api/v1/search_by_date";
+
   const SEARCH_URL: &str = "https://hn.algolia.com/api/v1/search";
 
-  async fn build_comment_from_item(&self, item: Item) -> Result<Comment> {
-    let children = self
-      .fetch_comment_children(item.kids.clone().unwrap_or_default())
-      .await?;
+  fn assemble_comment(
+    id: u64,
+    items: &mut HashMap<u64, Item>,
+    fetched_kids: bool,
+  ) -> Option<Comment> {
+    let item = items.remove(&id)?;
+
+    let children = item
+      .kids
+      .iter()
+      .flatten()
+      .filter_map(|kid| Self::assemble_comment(*kid, items, fetched_kids))
+      .collect();
+
+    Some(Self::comment_from_item(item, children, fetched_kids))
+  }
+
+  pub(crate) fn batch_size(&self) -> usize {
+    self.batch_size
+  }
+
+  async fn collect_thread<F, Fut>(
+    id: u64,
+    timeout: Option<Duration>,
+    concurrency: usize,
+    fetch_item: F,
+  ) -> Result<CommentThread>
+  where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = Result<Option<Item>>>,
+  {
+    let deadline = timeout.map(|timeout| time::Instant::now() + timeout);
+
+    let Some(root) = fetch_item(id).await? else {
+      bail!("item {id} does not exist");
+    };
+
+    if !matches!(
+      root.r#type.as_deref(),
+      Some("comment" | "job" | "poll" | "story")
+    ) {
+      bail!("item {id} is not a story or comment");
+    }
+
+    let lazy = root
+      .descendants
+      .is_some_and(|count| count > Self::LAZY_THREAD_THRESHOLD);
+
+    let mut items = HashMap::new();
+
+    let mut level = root.kids.clone().unwrap_or_default();
+
+    let mut partial = false;
+
+    while !level.is_empty() && !partial {
+      let mut responses = stream::iter(level.into_iter().map(&fetch_item))
+        .buffer_unordered(concurrency);
+
+      let mut next_level = Vec::new();
+
+      loop {
+        let response = match deadline {
+          Some(deadline) => {
+            if let Ok(response) =
+              time::timeout_at(deadline, responses.next()).await
+            {
+              response
+            } else {
+              partial = true;
+              break;
+            }
+          }
+          None => responses.next().await,
+        };
+
+        let Some(item) = response.transpose()? else {
+          break;
+        };
+
+        let Some(item) = item else {
+          continue;
+        };
+
+        if item.r#type.as_deref() == Some("comment") {
+          next_level.extend(item.kids.iter().flatten().copied());
+          items.insert(item.id, item);
+        }
+      }
+
+      if lazy {
+        break;
+      }
+
+      level = next_level;
+    }
+
+    if root.r#type.as_deref() == Some("comment") {
+      let children = root
+        .kids
+        .iter()
+        .flatten()
+        .filter_map(|kid| Self::assemble_comment(*kid, &mut items, true))
+        .collect();
+
+      return Ok(CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: Some(root.id),
+        id,
+        op_author: None,
+        partial,
+        roots: vec![Self::comment_from_item(root, children, true)],
+        score: None,
+        title: None,
+      });
+    }
+
+    let roots = root
+      .kids
+      .iter()
+      .flatten()
+      .filter_map(|kid| Self::assemble_comment(*kid, &mut items, !lazy))
+      .collect();
+
+    let body = root
+      .text
+      .as_deref()
+      .and_then(|html| html2text::from_read(html.as_bytes(), usize::MAX).ok())
+      .map(|text| text.trim_end().to_owned())
+      .filter(|text| !text.is_empty());
+
+    let body_links =
+      root.text.as_deref().map(extract_links).unwrap_or_default();
+
+    Ok(CommentThread {
+      body,
+      body_links,
+      descendants: root.descendants,
+      focus: None,
+      id,
+      op_author: root.by,
+      partial,
+      roots,
+      score: root.score,
+      title: root.title,
+    })
+  }
+
+  fn comment_from_item(
+    item: Item,
+    children: Vec<Comment>,
+    fetched_kids: bool,
+  ) -> Comment {
+    let loaded = fetched_kids || item.kids.as_ref().is_none_or(Vec::is_empty);
+
+    let links = item.text.as_deref().map(extract_links).unwrap_or_default();
 
     let text = item
       .text
@@ -38,14 +241,26 @@ impl Client {
       })
       .filter(|content| !content.is_empty());
 
-    Ok(Comment {
+    Comment {
       author: item.by,
       children,
       dead: item.dead.unwrap_or(false),
       deleted: item.deleted.unwrap_or(false),
+      html: item.text,
       id: item.id,
+      links,
+      loaded,
       text,
-    })
+      time: item.time,
+    }
+  }
+
+  fn ensure_online(&self) -> Result {
+    if self.offline {
+      bail!("offline: not cached");
+    }
+
+    Ok(())
   }
 
   pub(crate) async fn fetch_category_items(
@@ -56,87 +271,128 @@ impl Client {
   ) -> Result<Vec<ListEntry>> {
     Ok(match category.kind {
       CategoryKind::Stories(endpoint) => self
-        .fetch_stories(endpoint, offset, count)
+        .fetch_stories(category.label, endpoint, offset, count)
         .await?
         .into_iter()
         .map(ListEntry::from)
         .collect(),
-      CategoryKind::Bookmarks | CategoryKind::Search => Vec::new(),
+      CategoryKind::Bookmarks | CategoryKind::Saved | CategoryKind::Search => {
+        Vec::new()
+      }
       CategoryKind::Comments => self.fetch_comments(offset, count).await?,
+      CategoryKind::Front { days_ago, now } => {
+        self.fetch_front_page(days_ago, now, offset, count).await?
+      }
     })
   }
 
-  async fn fetch_comment(&self, id: u64) -> Result<Option<Comment>> {
-    let item = self.fetch_item(id).await?;
+  pub(crate) async fn fetch_comment_children(
+    &self,
+    id: u64,
+  ) -> Result<Vec<Comment>> {
+    let thread = self.fetch_thread(id, None).await?;
+
+    Ok(
+      thread
+        .roots
+        .into_iter()
+        .next()
+        .map(|comment| comment.children)
+        .unwrap_or_default(),
+    )
+  }
+
+  pub(crate) async fn fetch_comments(
+    &self,
+    offset: usize,
+    page_size: usize,
+  ) -> Result<Vec<ListEntry>> {
+    self.ensure_online()?;
 
-    if item.r#type.as_deref() != Some("comment") {
-      return Ok(None);
-    }
+    let page = offset / page_size.max(1);
 
-    let comment = self.build_comment_from_item(item).await?;
+    let response = Self::retry(|| async {
+      Self::parse_response::<CommentResponse>(
+        self
+          .client
+          .get(format!("{}{page_size}&page={page}", Self::COMMENTS_URL))
+          .send()
+          .await?,
+      )
+      .await
+    })
+    .await?;
 
-    Ok(Some(comment))
+    Ok(response.hits.into_iter().map(ListEntry::from).collect())
   }
 
-  async fn fetch_comment_children(
+  pub(crate) async fn fetch_front_page(
     &self,
-    ids: Vec<u64>,
-  ) -> Result<Vec<Comment>> {
-    let tasks = ids.into_iter().map(|child_id| {
-      let client = self.clone();
+    days_ago: u64,
+    now: Option<u64>,
+    offset: usize,
+    count: usize,
+  ) -> Result<Vec<ListEntry>> {
+    self.ensure_online()?;
 
-      async move { client.fetch_comment(child_id).await }
-    });
+    let now = match now {
+      Some(now) => now,
+      None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+    };
 
-    let results = stream::iter(tasks).buffered(16).collect::<Vec<_>>().await;
+    let url = Self::front_page_url(now, days_ago, offset, count)?;
 
-    let mut comments = Vec::new();
+    let response = Self::retry(|| async {
+      Self::parse_response::<SearchResponse>(
+        self.client.get(url.clone()).send().await?,
+      )
+      .await
+    })
+    .await?;
 
-    for result in results {
-      if let Some(comment) = result? {
-        comments.push(comment);
-      }
-    }
+    Ok(response.hits.into_iter().map(ListEntry::from).collect())
+  }
 
-    Ok(comments)
+  async fn fetch_item(&self, id: u64) -> Result<Option<Item>> {
+    self
+      .throttled(self.fetch_json(
+        "items",
+        &id.to_string(),
+        format!("{}/{id}.json", Self::ITEM_URL),
+        self.cache.as_ref().map(Cache::item_ttl),
+      ))
+      .await
   }
 
-  pub(crate) async fn fetch_comments(
+  async fn fetch_json<T: DeserializeOwned>(
     &self,
-    offset: usize,
-    page_size: usize,
-  ) -> Result<Vec<ListEntry>> {
-    let page = offset / page_size.max(1);
+    kind: &'static str,
+    key: &str,
+    url: String,
+    ttl: Option<Duration>,
+  ) -> Result<T> {
+    if let Some(ttl) = self.read_ttl(ttl)
+      && let Some(value) = self.read_cache::<Value>(kind, key, ttl).await
+      && let Ok(parsed) = serde_json::from_value(value)
+    {
+      return Ok(parsed);
+    }
 
-    Ok(
-      self
-        .client
-        .get(format!("{}{page_size}&page={page}", Self::COMMENTS_URL))
-        .send()
-        .await?
-        .json::<CommentResponse>()
-        .await?
-        .hits
-        .into_iter()
-        .map(ListEntry::from)
-        .collect(),
-    )
-  }
+    self.ensure_online()?;
 
-  async fn fetch_item(&self, id: u64) -> Result<Item> {
-    Ok(
-      self
-        .client
-        .get(format!("{}/{id}.json", Self::ITEM_URL))
-        .send()
-        .await?
-        .json::<Item>()
-        .await?,
-    )
+    let value = Self::retry(|| async {
+      Self::parse_response::<Value>(self.client.get(&url).send().await?).await
+    })
+    .await?;
+
+    self.write_cache(kind, key, value.clone()).await;
+
+    Ok(serde_json::from_value(value)?)
   }
 
   pub(crate) async fn fetch_stories(
     &self,
+    label: &str,
     endpoint: &str,
     offset: usize,
     count: usize,
@@ -144,11 +400,12 @@ impl Client {
     let ids_url = format!("{}/{endpoint}.json", Self::API_BASE_URL);
 
     let story_ids = self
-      .client
-      .get(ids_url)
-      .send()
-      .await?
-      .json::<Vec<u64>>()
+      .fetch_json::<Vec<u64>>(
+        "stories",
+        endpoint,
+        ids_url,
+        self.cache.as_ref().map(|cache| cache.story_ids_ttl(label)),
+      )
       .await?;
 
     let story_ids = story_ids.into_iter().skip(offset).take(count);
@@ -158,91 +415,227 @@ impl Client {
 
       async move {
         client
-          .client
-          .get(format!("{}/{id}.json", Self::ITEM_URL))
-          .send()
-          .await?
-          .json::<Story>()
+          .fetch_json::<Story>(
+            "items",
+            &id.to_string(),
+            format!("{}/{id}.json", Self::ITEM_URL),
+            client.cache.as_ref().map(Cache::item_ttl),
+          )
           .await
       }
     }))
-    .buffered(16)
+    .buffered(self.concurrency)
     .collect::<Vec<_>>()
     .await;
 
     let mut stories = Vec::with_capacity(responses.len());
 
     for story in responses {
-      stories.push(story?);
+      match story {
+        Ok(story) => stories.push(story),
+        Err(_) if self.offline => {}
+        Err(error) => return Err(error),
+      }
     }
 
     Ok(stories)
   }
 
-  pub(crate) async fn fetch_thread(&self, id: u64) -> Result<CommentThread> {
-    let item = self.fetch_item(id).await?;
-
-    if let Some("comment") = item.r#type.as_deref() {
-      let comment = self.build_comment_from_item(item).await?;
+  pub(crate) async fn fetch_thread(
+    &self,
+    id: u64,
+    timeout: Option<Duration>,
+  ) -> Result<CommentThread> {
+    let key = id.to_string();
 
-      return Ok(CommentThread {
-        focus: Some(comment.id),
-        roots: vec![comment],
-      });
+    if let Some(ttl) = self.read_ttl(self.cache.as_ref().map(Cache::item_ttl))
+      && let Some(thread) = self.read_cache("threads", &key, ttl).await
+    {
+      return Ok(thread);
     }
 
-    let roots = self
-      .fetch_comment_children(item.kids.clone().unwrap_or_default())
-      .await?;
+    let thread = Self::collect_thread(id, timeout, self.concurrency, |id| {
+      let client = self.clone();
 
-    Ok(CommentThread { focus: None, roots })
+      async move { client.fetch_item(id).await }
+    })
+    .await?;
+
+    if !thread.partial {
+      self.write_cache("threads", &key, thread.clone()).await;
+    }
+
+    Ok(thread)
   }
 
-  pub(crate) async fn load_tabs(
+  pub(crate) async fn fetch_top_comments(
     &self,
-    limit: usize,
-  ) -> Result<Vec<(Tab, ListView<ListEntry>)>> {
-    let tasks = Category::all().iter().map(|category| {
+    id: u64,
+    count: usize,
+  ) -> Result<Vec<Comment>> {
+    let Some(item) = self.fetch_item(id).await? else {
+      return Ok(Vec::new());
+    };
+
+    let ids = item.kids.unwrap_or_default().into_iter().take(count);
+
+    let items = stream::iter(ids.map(|id| {
       let client = self.clone();
 
-      let category = *category;
+      async move { client.fetch_item(id).await }
+    }))
+    .buffered(self.concurrency)
+    .collect::<Vec<_>>()
+    .await;
 
-      async move {
-        let entries = client
-          .fetch_category_items(category, 0, limit)
-          .await
-          .with_context(|| {
-            format!("failed to load {} entries", category.label)
-          })?;
-
-        Ok((
-          Tab {
-            category,
-            has_more: entries.len() == limit,
-            label: category.label,
-          },
-          ListView::new(entries),
-        ))
+    let mut comments = Vec::new();
+
+    for item in items {
+      let Some(item) = item? else {
+        continue;
+      };
+
+      if item.r#type.as_deref() == Some("comment") {
+        comments.push(Self::comment_from_item(item, Vec::new(), false));
       }
-    });
+    }
+
+    Ok(comments)
+  }
+
+  pub(crate) fn fresh(&self) -> Self {
+    Self {
+      fresh: true,
+      ..self.clone()
+    }
+  }
+
+  fn front_page_url(
+    now: u64,
+    days_ago: u64,
+    offset: usize,
+    count: usize,
+  ) -> Result<reqwest::Url> {
+    let start = (now / DAY).saturating_sub(days_ago).saturating_mul(DAY);
+    let end = start.saturating_add(DAY);
+
+    let count = count.max(1);
+
+    let mut url = reqwest::Url::parse(Self::FRONT_PAGE_URL)?;
+
+    url
+      .query_pairs_mut()
+      .append_pair("tags", "front_page")
+      .append_pair(
+        "numericFilters",
+        &format!("created_at_i>={start},created_at_i<{end}"),
+      )
+      .append_pair("hitsPerPage", &count.to_string())
+      .append_pair("page", &(offset / count).to_string());
+
+    Ok(url)
+  }
+
+  pub(crate) fn is_offline(&self) -> bool {
+    self.offline
+  }
+
+  async fn parse_response<T: DeserializeOwned>(
+    response: reqwest::Response,
+  ) -> Result<T> {
+    let status = response.status();
+
+    let error = response.error_for_status_ref().err();
+
+    let body = response.text().await?;
+
+    let snippet = truncate(
+      &body.split_whitespace().collect::<Vec<_>>().join(" "),
+      Self::ERROR_SNIPPET_LENGTH,
+    );
+
+    if let Some(error) = error {
+      return Err(
+        anyhow::Error::new(error)
+          .context(snippet)
+          .context(format!("server returned {}", status.as_u16())),
+      );
+    }
+
+    serde_json::from_str(&body)
+      .with_context(|| format!("server returned invalid json: {snippet}"))
+  }
+
+  async fn read_cache<T>(
+    &self,
+    kind: &'static str,
+    key: &str,
+    ttl: Duration,
+  ) -> Option<T>
+  where
+    T: DeserializeOwned + Send + 'static,
+  {
+    let cache = self.cache.clone()?;
 
-    let tabs = join_all(tasks)
+    let key = key.to_string();
+
+    task::spawn_blocking(move || cache.read(kind, &key, ttl))
       .await
-      .into_iter()
-      .collect::<Result<Vec<_>>>()?;
+      .ok()
+      .flatten()
+  }
+
+  fn read_ttl(&self, ttl: Option<Duration>) -> Option<Duration> {
+    if self.offline {
+      Some(Self::OFFLINE_TTL)
+    } else if self.fresh {
+      None
+    } else {
+      ttl
+    }
+  }
+
+  async fn retry<T, F, Fut>(operation: F) -> Result<T>
+  where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T>>,
+  {
+    let mut attempt = 0;
+
+    loop {
+      let error = match operation().await {
+        Ok(value) => return Ok(value),
+        Err(error) => error,
+      };
 
-    Ok(tabs)
+      let Some(reason) = Self::transient_reason(&error) else {
+        return Err(error);
+      };
+
+      if attempt == Self::MAX_RETRIES {
+        return Err(
+          error.context(format!("{reason} after {} attempts", attempt + 1)),
+        );
+      }
+
+      time::sleep(Self::RETRY_DELAY * 2u32.pow(attempt)).await;
+
+      attempt += 1;
+    }
   }
 
   pub(crate) async fn search_stories(
     &self,
     query: &str,
+    sort: SearchSort,
     page: usize,
     hits_per_page: usize,
   ) -> Result<(Vec<ListEntry>, bool)> {
+    self.ensure_online()?;
+
     let hits_per_page = hits_per_page.max(1);
 
-    let mut url = reqwest::Url::parse(Self::SEARCH_URL)?;
+    let mut url = reqwest::Url::parse(Self::search_url(sort))?;
 
     {
       let mut params = url.query_pairs_mut();
@@ -252,18 +645,529 @@ impl Client {
       params.append_pair("page", &page.to_string());
     }
 
-    let response = self
-      .client
-      .get(url)
-      .send()
-      .await?
-      .json::<SearchResponse>()
-      .await?;
+    let response = Self::parse_response::<SearchResponse>(
+      self.client.get(url).send().await?,
+    )
+    .await?;
 
     let has_more = response.page + 1 < response.nb_pages;
 
-    let entries = response.hits.into_iter().map(ListEntry::from).collect();
+    let mut entries = response
+      .hits
+      .into_iter()
+      .map(ListEntry::from)
+      .collect::<Vec<ListEntry>>();
+
+    if sort == SearchSort::Points {
+      entries.sort_by_key(|entry| Reverse(entry.score.unwrap_or(0)));
+    }
 
     Ok((entries, has_more))
   }
+
+  fn search_url(sort: SearchSort) -> &'static str {
+    match sort {
+      SearchSort::Date => Self::SEARCH_BY_DATE_URL,
+      SearchSort::Points | SearchSort::Relevance => Self::SEARCH_URL,
+    }
+  }
+
+  pub(crate) fn set_batch_size(&mut self, batch_size: usize) {
+    self.batch_size = batch_size;
+  }
+
+  pub(crate) fn set_cache_ttls(
+    &mut self,
+    item_ttl: Duration,
+    category_ttls: BTreeMap<String, Duration>,
+  ) {
+    if let Some(cache) = &mut self.cache {
+      cache.set_ttls(item_ttl, category_ttls);
+    }
+  }
+
+  pub(crate) fn set_concurrency(&mut self, concurrency: usize) {
+    self.concurrency = concurrency;
+  }
+
+  pub(crate) fn set_max_requests(&mut self, max_requests: usize) {
+    self.requests = Arc::new(Semaphore::new(max_requests.max(1)));
+  }
+
+  pub(crate) fn set_offline(&mut self, offline: bool) {
+    self.offline = offline;
+  }
+
+  async fn throttled<T>(
+    &self,
+    request: impl Future<Output = Result<T>>,
+  ) -> Result<T> {
+    let _permit = self.requests.acquire().await?;
+
+    request.await
+  }
+
+  fn transient_reason(error: &anyhow::Error) -> Option<String> {
+    let error = error.downcast_ref::<reqwest::Error>()?;
+
+    if error.is_timeout() {
+      return Some("request timed out".into());
+    }
+
+    error
+      .status()
+      .filter(reqwest::StatusCode::is_server_error)
+      .map(|status| format!("server responded with {status}"))
+  }
+
+  pub(crate) fn with_cache(enabled: bool) -> Self {
+    Self {
+      cache: enabled.then(Cache::new).and_then(Result::ok),
+      ..Self::default()
+    }
+  }
+
+  async fn write_cache<T>(&self, kind: &'static str, key: &str, value: T)
+  where
+    T: Serialize + Send + 'static,
+  {
+    let Some(cache) = self.cache.clone() else {
+      return;
+    };
+
+    let key = key.to_string();
+
+    let _ = task::spawn_blocking(move || cache.write(kind, &key, &value)).await;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    anyhow::bail,
+    futures::future::join_all,
+    std::{
+      io::{Read, Write},
+      net::TcpListener,
+      pin::Pin,
+      sync::atomic::{AtomicU32, AtomicUsize, Ordering},
+    },
+  };
+
+  fn comment(id: u64, kids: &[u64]) -> Item {
+    Item {
+      by: Some(format!("user{id}")),
+      dead: None,
+      deleted: None,
+      descendants: None,
+      id,
+      kids: Some(kids.to_vec()),
+      score: None,
+      text: Some(format!("comment {id}")),
+      time: None,
+      title: None,
+      r#type: Some("comment".to_string()),
+      url: None,
+    }
+  }
+
+  fn story(id: u64, kids: &[u64]) -> Item {
+    Item {
+      r#type: Some("story".to_string()),
+      title: Some("story".to_string()),
+      text: None,
+      ..comment(id, kids)
+    }
+  }
+
+  fn fetch_from(
+    slow: u64,
+  ) -> impl Fn(u64) -> Pin<Box<dyn Future<Output = Result<Option<Item>>>>> {
+    move |id| {
+      Box::pin(async move {
+        if id == slow {
+          time::sleep(Duration::from_secs(5)).await;
+        }
+
+        Ok(Some(match id {
+          1 => story(1, &[2, 3]),
+          5 => Item {
+            descendants: Some(Client::LAZY_THREAD_THRESHOLD + 1),
+            ..story(5, &[2, 3])
+          },
+          2 => comment(2, &[4]),
+          3 => comment(3, &[]),
+          4 => comment(4, &[]),
+          6 => Item {
+            r#type: Some("pollopt".to_string()),
+            ..comment(6, &[])
+          },
+          7 => Item {
+            text: Some(
+              "<p>What are you working on?</p><p><a href=\"https://example.com/demo\">demo</a></p>"
+                .to_string(),
+            ),
+            ..story(7, &[3])
+          },
+          8 => story(8, &[2, 9, 3]),
+          9 => return Ok(None),
+          10 => comment(10, &[9]),
+          11 => story(11, &[10]),
+          _ => bail!("unknown item {id}"),
+        }))
+      })
+    }
+  }
+
+  fn ids(comments: &[Comment]) -> Vec<u64> {
+    comments.iter().map(|comment| comment.id).collect()
+  }
+
+  #[test]
+  fn front_page_url_covers_a_whole_utc_day() {
+    let url = Client::front_page_url(1_000_000, 1, 60, 30).unwrap();
+
+    let pairs = url.query_pairs().into_owned().collect::<Vec<_>>();
+
+    assert_eq!(
+      pairs,
+      [
+        ("tags".to_string(), "front_page".to_string()),
+        (
+          "numericFilters".to_string(),
+          "created_at_i>=864000,created_at_i<950400".to_string(),
+        ),
+        ("hitsPerPage".to_string(), "30".to_string()),
+        ("page".to_string(), "2".to_string()),
+      ]
+    );
+  }
+
+  fn serve_once(status: &'static str, body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    std::thread::spawn(move || {
+      let (mut stream, _) = listener.accept().unwrap();
+
+      let _ = stream.read(&mut [0; 1024]);
+
+      let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+      );
+
+      stream.write_all(response.as_bytes()).unwrap();
+    });
+
+    url
+  }
+
+  #[tokio::test]
+  async fn parse_response_reports_status_and_body_of_error_pages() {
+    let url = serve_once(
+      "503 Service Unavailable",
+      "<html>\n  <body>Down for maintenance</body>\n</html>",
+    );
+
+    let response = reqwest::get(&url).await.unwrap();
+
+    let error = Client::parse_response::<Value>(response).await.unwrap_err();
+
+    assert_eq!(error.to_string(), "server returned 503");
+
+    assert!(
+      format!("{error:#}")
+        .contains("<html> <body>Down for maintenance</body> </html>")
+    );
+
+    assert!(Client::transient_reason(&error).is_some());
+  }
+
+  #[tokio::test]
+  async fn parse_response_reports_invalid_json_bodies() {
+    let url = serve_once("200 OK", "<html/>\n");
+
+    let response = reqwest::get(&url).await.unwrap();
+
+    let error = Client::parse_response::<Value>(response).await.unwrap_err();
+
+    assert_eq!(error.to_string(), "server returned invalid json: <html/>");
+  }
+
+  #[tokio::test]
+  async fn retry_returns_permanent_errors_immediately() {
+    let attempts = AtomicU32::new(0);
+
+    let result = Client::retry(|| async {
+      attempts.fetch_add(1, Ordering::SeqCst);
+      Err::<(), _>(anyhow!("invalid response"))
+    })
+    .await;
+
+    assert_eq!(result.unwrap_err().to_string(), "invalid response");
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+  }
+
+  #[tokio::test]
+  async fn retry_retries_timeouts_before_giving_up() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let client = reqwest::Client::builder()
+      .timeout(Duration::from_millis(20))
+      .build()
+      .unwrap();
+
+    let attempts = AtomicU32::new(0);
+
+    let result = Client::retry(|| async {
+      attempts.fetch_add(1, Ordering::SeqCst);
+      Ok(client.get(&url).send().await?.text().await?)
+    })
+    .await;
+
+    assert_eq!(
+      result.unwrap_err().to_string(),
+      "request timed out after 4 attempts"
+    );
+    assert_eq!(attempts.load(Ordering::SeqCst), 4);
+  }
+
+  #[tokio::test]
+  async fn throttled_caps_concurrent_requests_across_clones() {
+    let mut client = Client::default();
+
+    client.set_max_requests(3);
+
+    let in_flight = Arc::new(AtomicUsize::new(0));
+
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    let tasks = (0..20)
+      .map(|_| {
+        let client = client.clone();
+        let in_flight = in_flight.clone();
+        let peak = peak.clone();
+
+        tokio::spawn(async move {
+          client
+            .throttled(async {
+              let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+              peak.fetch_max(current, Ordering::SeqCst);
+              time::sleep(Duration::from_millis(5)).await;
+              in_flight.fetch_sub(1, Ordering::SeqCst);
+              Ok(())
+            })
+            .await
+        })
+      })
+      .collect::<Vec<_>>();
+
+    for task in join_all(tasks).await {
+      task.unwrap().unwrap();
+    }
+
+    assert_eq!(peak.load(Ordering::SeqCst), 3);
+  }
+
+  #[test]
+  fn fresh_clients_skip_cached_copies_unless_offline() {
+    let mut client = Client::default();
+
+    assert_eq!(
+      client.read_ttl(Some(Cache::STORY_IDS_TTL)),
+      Some(Cache::STORY_IDS_TTL)
+    );
+    assert_eq!(client.fresh().read_ttl(Some(Cache::STORY_IDS_TTL)), None);
+
+    client.set_offline(true);
+
+    assert_eq!(
+      client.fresh().read_ttl(Some(Cache::STORY_IDS_TTL)),
+      Some(Client::OFFLINE_TTL)
+    );
+  }
+
+  #[tokio::test]
+  async fn offline_client_never_touches_the_network() {
+    let mut client = Client::default();
+
+    client.set_offline(true);
+
+    for error in [
+      client.fetch_comments(0, 10).await.unwrap_err(),
+      client.fetch_thread(1, None).await.unwrap_err(),
+      client
+        .search_stories("rust", SearchSort::Relevance, 0, 10)
+        .await
+        .unwrap_err(),
+    ] {
+      assert_eq!(error.to_string(), "offline: not cached");
+    }
+
+    assert_eq!(
+      client
+        .fetch_category_items(Category::all()[0], 0, 10)
+        .await
+        .unwrap_err()
+        .to_string(),
+      "offline: not cached"
+    );
+  }
+
+  #[tokio::test]
+  async fn collect_thread_fetches_full_tree_without_timeout() {
+    let thread = Client::collect_thread(
+      1,
+      None,
+      Client::DEFAULT_CONCURRENCY,
+      fetch_from(0),
+    )
+    .await
+    .unwrap();
+
+    assert!(!thread.partial);
+    assert_eq!(ids(&thread.roots), [2, 3]);
+    assert_eq!(ids(&thread.roots[0].children), [4]);
+  }
+
+  #[tokio::test]
+  async fn collect_thread_keeps_the_text_of_ask_posts() {
+    let thread = Client::collect_thread(
+      7,
+      None,
+      Client::DEFAULT_CONCURRENCY,
+      fetch_from(0),
+    )
+    .await
+    .unwrap();
+
+    assert!(
+      thread
+        .body
+        .as_deref()
+        .is_some_and(|body| body.starts_with("What are you working on?"))
+    );
+    assert_eq!(thread.body_links, ["https://example.com/demo"]);
+    assert_eq!(ids(&thread.roots), [3]);
+
+    let thread = Client::collect_thread(
+      1,
+      None,
+      Client::DEFAULT_CONCURRENCY,
+      fetch_from(0),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(thread.body, None);
+  }
+
+  #[tokio::test]
+  async fn collect_thread_returns_partial_results_at_deadline() {
+    let thread = Client::collect_thread(
+      1,
+      Some(Duration::from_millis(50)),
+      Client::DEFAULT_CONCURRENCY,
+      fetch_from(3),
+    )
+    .await
+    .unwrap();
+
+    assert!(thread.partial);
+    assert_eq!(ids(&thread.roots), [2]);
+    assert!(thread.roots[0].children.is_empty());
+    assert!(thread.roots[0].loaded);
+  }
+
+  #[tokio::test]
+  async fn collect_thread_fetches_only_roots_of_huge_threads() {
+    let thread = Client::collect_thread(
+      5,
+      None,
+      Client::DEFAULT_CONCURRENCY,
+      fetch_from(0),
+    )
+    .await
+    .unwrap();
+
+    assert!(!thread.partial);
+    assert_eq!(ids(&thread.roots), [2, 3]);
+    assert!(thread.roots[0].children.is_empty());
+    assert!(!thread.roots[0].loaded);
+    assert!(thread.roots[1].loaded);
+  }
+
+  #[tokio::test]
+  async fn collect_thread_fetches_comment_subtree() {
+    let thread = Client::collect_thread(
+      2,
+      None,
+      Client::DEFAULT_CONCURRENCY,
+      fetch_from(0),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(ids(&thread.roots), [2]);
+    assert_eq!(ids(&thread.roots[0].children), [4]);
+    assert!(thread.roots[0].loaded);
+  }
+
+  #[tokio::test]
+  async fn collect_thread_skips_items_that_no_longer_exist() {
+    let thread = Client::collect_thread(
+      8,
+      None,
+      Client::DEFAULT_CONCURRENCY,
+      fetch_from(0),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(ids(&thread.roots), [2, 3]);
+    assert_eq!(ids(&thread.roots[0].children), [4]);
+
+    let thread = Client::collect_thread(
+      11,
+      None,
+      Client::DEFAULT_CONCURRENCY,
+      fetch_from(0),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(ids(&thread.roots), [10]);
+    assert!(thread.roots[0].children.is_empty());
+    assert!(thread.roots[0].loaded);
+
+    let error = Client::collect_thread(
+      9,
+      None,
+      Client::DEFAULT_CONCURRENCY,
+      fetch_from(0),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(error.to_string(), "item 9 does not exist");
+  }
+
+  #[tokio::test]
+  async fn collect_thread_rejects_items_that_are_not_stories_or_comments() {
+    let error = Client::collect_thread(
+      6,
+      None,
+      Client::DEFAULT_CONCURRENCY,
+      fetch_from(0),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(error.to_string(), "item 6 is not a story or comment");
+  }
 }
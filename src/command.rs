@@ -1,22 +1,85 @@
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+use super::*;
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
 pub(crate) enum Command {
+  AdjustScoreFilter(i64),
+  CancelCommandPalette,
   CancelSearch,
+  CancelTabSwitcher,
+  ClearLocalFilter,
   CloseComments,
+  CloseSearchTab,
+  CollapseAll,
+  CollapseComment,
+  CollapseSubtree,
+  CopyCommentLink,
+  CopyCommentText,
+  CopyLink,
+  CopySummary,
+  CycleContrast,
+  CycleSearchSort,
+  CycleTagFilter,
+  DumpComment,
+  EditTags,
+  ExpandAll,
+  ExpandComment,
   HideHelp,
+  LoadFullThread,
+  LoadNewer,
+  MarkAllRead,
+  NextMatch,
+  NextRoot,
   None,
   OpenCommentLink,
+  OpenCommentLinks,
   OpenComments,
+  OpenCommentsInBrowser,
   OpenCurrentInBrowser,
+  OpenSectionInBrowser,
   PageDown,
   PageUp,
+  PreviousMatch,
+  PreviousRoot,
   Quit,
+  RefreshTab,
+  RepeatSearch,
+  SaveThread,
+  ScrollHelp(i16),
+  ScrollLeft,
+  ScrollRight,
   SelectFirst,
+  SelectIndex(usize),
+  SelectLast,
   SelectNext,
+  SelectNextSibling,
+  SelectNextUnread,
   SelectPrevious,
+  SelectRootNext,
+  ShiftDay(i64),
+  ShowBookmarks,
+  ShowCommandPalette,
   ShowHelp,
+  ShowTabSwitcher,
+  StartLocalFilter,
   StartSearch,
   SubmitSearch,
+  SubmitTabSwitcher,
   SwitchTabLeft,
   SwitchTabRight,
+  SwitchToTab(usize),
   ToggleBookmark,
+  ToggleCenterSelection,
+  ToggleComment,
+  ToggleCommentWrap,
+  ToggleFlat,
+  ToggleFocus,
+  ToggleFollow,
+  ToggleHideDead,
+  ToggleListing,
+  TogglePin,
+  ToggleRead,
+  ToggleScroll,
+  ToggleSplitView,
+  ViewInPager,
 }
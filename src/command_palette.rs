@@ -0,0 +1,171 @@
+use super::*;
+
+#[derive(Debug, Default)]
+pub(crate) struct CommandPalette {
+  filter: String,
+  selected: usize,
+}
+
+impl CommandPalette {
+  fn area(area: Rect, width: usize, height: usize) -> Rect {
+    let width = u16::try_from(width)
+      .unwrap_or(u16::MAX)
+      .min(area.width.saturating_sub(2))
+      .max(1);
+
+    let height = u16::try_from(height)
+      .unwrap_or(u16::MAX)
+      .min(area.height.saturating_sub(2))
+      .max(1);
+
+    Rect {
+      x: area.x + (area.width.saturating_sub(width)) / 2,
+      y: area.y + (area.height.saturating_sub(height)) / 2,
+      width,
+      height,
+    }
+  }
+
+  pub(crate) fn draw(
+    &self,
+    frame: &mut Frame,
+    entries: &[(Command, String, &'static str)],
+    theme: &Theme,
+  ) {
+    let matches = self.matches(entries);
+
+    let title = format!("Commands: {}", self.filter);
+
+    let label_width = matches
+      .iter()
+      .map(|&index| entries[index].2.chars().count())
+      .max()
+      .unwrap_or_default();
+
+    let width = matches
+      .iter()
+      .map(|&index| label_width + entries[index].1.chars().count() + 6)
+      .chain([title.chars().count() + 4, 40])
+      .max()
+      .unwrap_or(40);
+
+    let area = Self::area(frame.area(), width, matches.len().max(1) + 2);
+
+    frame.render_widget(Clear, area);
+
+    let items = if matches.is_empty() {
+      vec![ListItem::new(Line::from(vec![
+        Span::raw(BASE_INDENT),
+        Span::styled("No matching commands", Style::default().fg(theme.detail)),
+      ]))]
+    } else {
+      matches
+        .iter()
+        .map(|&index| {
+          let (_, keys, label) = &entries[index];
+
+          ListItem::new(Line::from(vec![
+            Span::raw(BASE_INDENT),
+            Span::raw(format!("{label:label_width$}  ")),
+            Span::styled(keys.clone(), Style::default().fg(theme.detail)),
+          ]))
+        })
+        .collect()
+    };
+
+    let list = List::new(items)
+      .block(Block::default().title(title).borders(Borders::ALL))
+      .highlight_style(
+        Style::default()
+          .fg(theme.highlight)
+          .add_modifier(Modifier::BOLD),
+      );
+
+    let mut state = ListState::default();
+
+    if !matches.is_empty() {
+      state.select(Some(self.selected.min(matches.len() - 1)));
+    }
+
+    frame.render_stateful_widget(list, area, &mut state);
+  }
+
+  pub(crate) fn matches(
+    &self,
+    entries: &[(Command, String, &'static str)],
+  ) -> Vec<usize> {
+    let mut scored = entries
+      .iter()
+      .enumerate()
+      .filter_map(|(index, (_, _, label))| {
+        fuzzy_score(&self.filter, label).map(|score| (index, score))
+      })
+      .collect::<Vec<(usize, usize)>>();
+
+    scored.sort_by_key(|&(_, score)| Reverse(score));
+
+    scored.into_iter().map(|(index, _)| index).collect()
+  }
+
+  pub(crate) fn pop(&mut self) {
+    self.filter.pop();
+    self.selected = 0;
+  }
+
+  pub(crate) fn push(&mut self, ch: char) {
+    self.filter.push(ch);
+    self.selected = 0;
+  }
+
+  pub(crate) fn select_next(
+    &mut self,
+    entries: &[(Command, String, &'static str)],
+  ) {
+    let count = self.matches(entries).len();
+
+    self.selected = (self.selected + 1).min(count.saturating_sub(1));
+  }
+
+  pub(crate) fn select_previous(&mut self) {
+    self.selected = self.selected.saturating_sub(1);
+  }
+
+  pub(crate) fn selected_command(
+    &self,
+    entries: &[(Command, String, &'static str)],
+  ) -> Option<Command> {
+    self
+      .matches(entries)
+      .get(self.selected)
+      .map(|&index| entries[index].0)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn filter_narrows_commands_by_label() {
+    let entries = Keymap::default().palette_entries(false);
+
+    let mut palette = CommandPalette::default();
+
+    assert_eq!(palette.matches(&entries).len(), entries.len());
+
+    for ch in "bookmarks tab".chars() {
+      palette.push(ch);
+    }
+
+    assert_eq!(
+      palette.selected_command(&entries),
+      Some(Command::ShowBookmarks)
+    );
+
+    for ch in "zzz".chars() {
+      palette.push(ch);
+    }
+
+    assert_eq!(palette.selected_command(&entries), None);
+  }
+}
@@ -1,9 +1,22 @@
-#[derive(Clone, Debug)]
+use super::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub(crate) struct Comment {
   pub(crate) author: Option<String>,
   pub(crate) children: Vec<Comment>,
   pub(crate) dead: bool,
   pub(crate) deleted: bool,
+  pub(crate) html: Option<String>,
   pub(crate) id: u64,
+  pub(crate) links: Vec<String>,
+  pub(crate) loaded: bool,
   pub(crate) text: Option<String>,
+  #[serde(default)]
+  pub(crate) time: Option<u64>,
+}
+
+impl Comment {
+  pub(crate) fn contains(&self, id: u64) -> bool {
+    self.id == id || self.children.iter().any(|child| child.contains(id))
+  }
 }
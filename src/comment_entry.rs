@@ -8,8 +8,15 @@ pub(crate) struct CommentEntry {
   pub(crate) deleted: bool,
   pub(crate) depth: usize,
   pub(crate) expanded: bool,
+  pub(crate) folded: Vec<Comment>,
+  pub(crate) html: Option<String>,
   pub(crate) id: u64,
+  pub(crate) is_op: bool,
+  pub(crate) is_post: bool,
+  pub(crate) links: Vec<String>,
+  pub(crate) loaded: bool,
   pub(crate) parent: Option<usize>,
+  pub(crate) time: Option<u64>,
 }
 
 impl CommentEntry {
@@ -17,18 +24,64 @@ impl CommentEntry {
     self.body.as_str()
   }
 
+  pub(crate) fn diagnostics(&self) -> String {
+    format!(
+      "id: {}\nauthor: {}\npermalink: {}\n\n--- raw ---\n{}\n\n--- rendered ---\n{}\n",
+      self.id,
+      self.author.as_deref().unwrap_or("unknown"),
+      self.permalink(),
+      self.html.as_deref().unwrap_or_default(),
+      self.body,
+    )
+  }
+
   pub(crate) fn has_children(&self) -> bool {
-    !self.children.is_empty()
+    !self.children.is_empty() || !self.loaded
   }
 
-  pub(crate) fn header(&self) -> String {
+  pub(crate) fn header(&self, now: u64) -> String {
     let author = self.author.as_deref().unwrap_or("unknown");
 
-    match (self.deleted, self.dead) {
+    let mut header = match (self.deleted, self.dead) {
       (true, _) => format!("{author} (deleted)"),
       (_, true) => format!("{author} (dead)"),
       _ => author.to_string(),
+    };
+
+    if self.is_op {
+      header = format!("{header} [OP]");
     }
+
+    match self.time {
+      Some(time) => format!(
+        "{header}{DETAIL_SEPARATOR}{}",
+        format_relative_time(now, time)
+      ),
+      None => header,
+    }
+  }
+
+  pub(crate) fn is_fold(&self) -> bool {
+    !self.folded.is_empty()
+  }
+
+  pub(crate) fn listing_detail(&self) -> String {
+    let author = self.author.as_deref().unwrap_or("unknown");
+
+    match self.children.len() {
+      0 => format!("by {author}"),
+      1 => format!("by {author}{DETAIL_SEPARATOR}1 reply"),
+      replies => format!("by {author}{DETAIL_SEPARATOR}{replies} replies"),
+    }
+  }
+
+  pub(crate) fn listing_title(&self) -> &str {
+    self
+      .body
+      .lines()
+      .map(str::trim)
+      .find(|line| !line.is_empty())
+      .unwrap_or_default()
   }
 
   pub(crate) fn permalink(&self) -> String {
@@ -62,13 +115,17 @@ impl CommentEntry {
       if trimmed.is_empty() {
         None
       } else {
-        Some(truncate(trimmed, 120))
+        Some(truncate_words(trimmed, 120))
       }
     };
 
     ListEntry {
+      comments: None,
       detail,
       id: self.id.to_string(),
+      score: None,
+      tags: Vec::new(),
+      time: None,
       title,
       url: Some(self.permalink()),
     }
@@ -4,6 +4,7 @@ use super::*;
 pub(crate) struct CommentHit {
   pub(crate) author: Option<String>,
   pub(crate) comment_text: Option<String>,
+  pub(crate) created_at_i: Option<u64>,
   #[serde(rename = "objectID")]
   pub(crate) object_id: String,
   #[serde(deserialize_with = "deserialize_optional_string")]
@@ -1,7 +1,21 @@
 use super::*;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub(crate) struct CommentThread {
+  #[serde(default)]
+  pub(crate) body: Option<String>,
+  #[serde(default)]
+  pub(crate) body_links: Vec<String>,
+  #[serde(default)]
+  pub(crate) descendants: Option<u64>,
   pub(crate) focus: Option<u64>,
+  pub(crate) id: u64,
+  #[serde(default)]
+  pub(crate) op_author: Option<String>,
+  pub(crate) partial: bool,
   pub(crate) roots: Vec<Comment>,
+  #[serde(default)]
+  pub(crate) score: Option<u64>,
+  #[serde(default)]
+  pub(crate) title: Option<String>,
 }
@@ -1,18 +1,118 @@
 use super::*;
 
 pub(crate) struct CommentView {
+  descendants: Option<u64>,
   pub(crate) entries: Vec<CommentEntry>,
+  filter: String,
+  flat: bool,
+  pub(crate) focus: Option<usize>,
+  pub(crate) focus_only: bool,
+  hide_dead: bool,
+  horizontal_offset: usize,
+  horizontal_selection: Option<usize>,
+  item_id: u64,
+  line_heights: Vec<usize>,
+  line_offset: usize,
   pub(crate) link: String,
+  listing: bool,
+  max_depth: usize,
   pub(crate) offset: usize,
+  op_author: Option<String>,
+  partial: bool,
+  score: Option<u64>,
+  scrolling: bool,
+  search_query: Option<String>,
   pub(crate) selected: Option<usize>,
+  title: Option<String>,
+  visited: HashSet<u64>,
 }
 
 impl CommentView {
+  pub(crate) const DEFAULT_MAX_DEPTH: usize = 8;
+
+  pub(crate) const HORIZONTAL_SCROLL_STEP: isize = 8;
+
+  pub(crate) fn advance_root(&mut self, forward: bool) {
+    let roots = self
+      .entries
+      .iter()
+      .enumerate()
+      .filter(|(_, entry)| entry.parent.is_none())
+      .map(|(idx, _)| idx)
+      .collect::<Vec<_>>();
+
+    let Some(current) = self.selected.map(|idx| self.root_of(idx)) else {
+      return;
+    };
+
+    let Some(position) = roots.iter().position(|&root| root == current) else {
+      return;
+    };
+
+    let target = if forward {
+      roots.get(position + 1).copied()
+    } else {
+      position
+        .checked_sub(1)
+        .and_then(|previous| roots.get(previous).copied())
+    };
+
+    let Some(target) = target else {
+      return;
+    };
+
+    for &root in &roots {
+      if let Some(entry) = self.entries.get_mut(root) {
+        entry.expanded = root == target;
+      }
+    }
+
+    self.selected = Some(target);
+
+    self.ensure_selection_visible();
+  }
+
+  pub(crate) fn clamp_offset(&mut self, rows: usize) {
+    let (visible, selected) = self.visible_with_selection();
+
+    self.offset = self.offset.min(visible.len().saturating_sub(1));
+
+    if let Some(position) = selected {
+      let rows = rows.max(1);
+
+      if position >= self.offset + rows {
+        self.offset = position + 1 - rows;
+      } else if position < self.offset {
+        self.offset = position;
+      }
+    }
+  }
+
+  pub(crate) fn collapse_all(&mut self) {
+    for entry in &mut self.entries {
+      if entry.has_children() {
+        entry.expanded = false;
+      }
+    }
+
+    self.ensure_selection_visible();
+  }
+
+  pub(crate) fn collapse_dead(&mut self) {
+    for entry in &mut self.entries {
+      if entry.dead && entry.has_children() {
+        entry.expanded = false;
+      }
+    }
+
+    self.ensure_selection_visible();
+  }
+
   pub(crate) fn collapse_selected(&mut self) {
     if let Some(selected) = self.selected
       && let Some(entry) = self.entries.get_mut(selected)
     {
-      if entry.expanded && !entry.children.is_empty() {
+      if entry.expanded && entry.has_children() && !self.flat {
         entry.expanded = false;
       } else if let Some(parent) = entry.parent {
         self.selected = Some(parent);
@@ -22,6 +122,63 @@ impl CommentView {
     self.ensure_selection_visible();
   }
 
+  pub(crate) fn collapse_subtree(&mut self, idx: usize) {
+    if idx >= self.entries.len() {
+      return;
+    }
+
+    let mut stack = vec![idx];
+
+    while let Some(current) = stack.pop() {
+      let entry = &mut self.entries[current];
+
+      if entry.has_children() {
+        entry.expanded = false;
+      }
+
+      stack.extend(entry.children.iter().copied());
+    }
+
+    self.selected = Some(idx);
+
+    self.ensure_selection_visible();
+  }
+
+  fn comment_at(&self, idx: usize) -> Comment {
+    let entry = &self.entries[idx];
+
+    let children = entry
+      .children
+      .iter()
+      .flat_map(|&child| match self.entries.get(child) {
+        Some(child) if child.is_fold() => child.folded.clone(),
+        Some(_) => vec![self.comment_at(child)],
+        None => Vec::new(),
+      })
+      .collect();
+
+    Comment {
+      author: entry.author.clone(),
+      children,
+      dead: entry.dead,
+      deleted: entry.deleted,
+      html: entry.html.clone(),
+      id: entry.id,
+      links: entry.links.clone(),
+      loaded: entry.loaded,
+      text: (!entry.dead && !entry.deleted).then(|| entry.body.clone()),
+      time: entry.time,
+    }
+  }
+
+  fn display_order(&self, mut indexes: Vec<usize>) -> Vec<usize> {
+    if self.flat && !self.listing {
+      indexes.sort_by_key(|&idx| Reverse(self.entries[idx].id));
+    }
+
+    indexes
+  }
+
   pub(crate) fn ensure_selection_visible(&mut self) {
     let mut current = self.selected;
 
@@ -37,11 +194,25 @@ impl CommentView {
     self.selected = self.visible_indexes().first().copied();
   }
 
+  pub(crate) fn expand_all(&mut self) {
+    for entry in &mut self.entries {
+      if entry.loaded {
+        entry.expanded = true;
+      }
+    }
+  }
+
   pub(crate) fn expand_selected(&mut self) {
+    if let Some(selected) = self.selected
+      && self.unfold(selected)
+    {
+      return;
+    }
+
     if let Some(selected) = self.selected
       && let Some(entry) = self.entries.get_mut(selected)
     {
-      if entry.children.is_empty() {
+      if !entry.has_children() {
         return;
       }
 
@@ -57,7 +228,167 @@ impl CommentView {
     self.ensure_selection_visible();
   }
 
+  pub(crate) fn filter(&self) -> &str {
+    &self.filter
+  }
+
+  fn fold(comments: Vec<Comment>, parent: usize, depth: usize) -> CommentEntry {
+    fn count(comments: &[Comment]) -> usize {
+      comments
+        .iter()
+        .map(|comment| 1 + count(&comment.children))
+        .sum()
+    }
+
+    let replies = count(&comments);
+
+    let id = comments.first().map_or(0, |comment| comment.id);
+
+    CommentEntry {
+      author: None,
+      body: match replies {
+        1 => "1 more reply".to_string(),
+        _ => format!("{replies} more replies"),
+      },
+      children: Vec::new(),
+      dead: false,
+      deleted: false,
+      depth,
+      expanded: false,
+      folded: comments,
+      html: None,
+      id,
+      is_op: false,
+      is_post: false,
+      links: Vec::new(),
+      loaded: true,
+      parent: Some(parent),
+      time: None,
+    }
+  }
+
+  pub(crate) fn guides(&self, idx: usize) -> Vec<Guide> {
+    let mut guides = Vec::new();
+
+    let mut current = idx;
+
+    while let Some(parent) =
+      self.entries.get(current).and_then(|entry| entry.parent)
+    {
+      let has_next = self.entries[parent]
+        .children
+        .iter()
+        .skip_while(|&&child| child != current)
+        .skip(1)
+        .any(|&sibling| self.is_visible(sibling));
+
+      guides.push(match (current == idx, has_next) {
+        (true, true) => Guide::Branch,
+        (true, false) => Guide::Last,
+        (false, true) => Guide::Pipe,
+        (false, false) => Guide::Blank,
+      });
+
+      current = parent;
+    }
+
+    guides.reverse();
+
+    guides
+  }
+
+  fn has_live_descendant(&self, idx: usize) -> bool {
+    self.entries.get(idx).is_some_and(|entry| {
+      !entry.loaded
+        || entry.children.iter().any(|&child| {
+          self
+            .entries
+            .get(child)
+            .is_some_and(|child| !child.dead && !child.deleted)
+            || self.has_live_descendant(child)
+        })
+    })
+  }
+
+  pub(crate) fn horizontal_offset(&self) -> usize {
+    if self.horizontal_selection == self.selected {
+      self.horizontal_offset
+    } else {
+      0
+    }
+  }
+
+  pub(crate) fn insert_children(&mut self, id: u64, children: Vec<Comment>) {
+    let Some(parent) = self
+      .entries
+      .iter()
+      .position(|entry| entry.id == id && !entry.is_fold())
+    else {
+      return;
+    };
+
+    self.splice_children(parent, children, self.max_depth);
+  }
+
+  pub(crate) fn is_flat(&self) -> bool {
+    self.flat && !self.listing
+  }
+
+  fn is_in_focus(&self, idx: usize) -> bool {
+    let Some(focus) = self.focus.filter(|_| self.focus_only) else {
+      return true;
+    };
+
+    let mut current = Some(idx);
+
+    while let Some(i) = current {
+      if i == focus {
+        return true;
+      }
+
+      current = self.entries.get(i).and_then(|entry| entry.parent);
+    }
+
+    false
+  }
+
+  pub(crate) fn is_listing(&self) -> bool {
+    self.listing
+  }
+
+  pub(crate) fn is_partial(&self) -> bool {
+    self.partial
+  }
+
+  pub(crate) fn is_redacted(&self, idx: usize) -> bool {
+    self.hide_dead
+      && self
+        .entries
+        .get(idx)
+        .is_some_and(|entry| entry.dead || entry.deleted)
+  }
+
+  pub(crate) fn is_scrolling(&self) -> bool {
+    self.scrolling && !self.listing
+  }
+
   pub(crate) fn is_visible(&self, idx: usize) -> bool {
+    if self.is_redacted(idx) && !self.has_live_descendant(idx) {
+      return false;
+    }
+
+    if self.listing {
+      return self.matches_filter(idx);
+    }
+
+    if !self.is_in_focus(idx) {
+      return false;
+    }
+
+    if self.flat {
+      return true;
+    }
+
     let mut current = Some(idx);
 
     while let Some(i) = current {
@@ -77,10 +408,72 @@ impl CommentView {
     true
   }
 
+  pub(crate) fn item_id(&self) -> u64 {
+    self.item_id
+  }
+
+  pub(crate) fn line_offset(&self) -> usize {
+    self.line_offset
+  }
+
   pub(crate) fn link(&self) -> &str {
     &self.link
   }
 
+  fn mark_op(entries: &mut [CommentEntry], op_author: Option<&str>) {
+    let Some(op_author) = op_author else {
+      return;
+    };
+
+    for entry in entries {
+      entry.is_op =
+        !entry.is_post && entry.author.as_deref() == Some(op_author);
+    }
+  }
+
+  pub(crate) fn mark_visited(&mut self) {
+    if let Some(entry) = self.selected_entry().filter(|entry| !entry.is_fold())
+    {
+      self.visited.insert(entry.id);
+    }
+  }
+
+  fn matches_filter(&self, idx: usize) -> bool {
+    let Some(entry) = self.entries.get(idx) else {
+      return false;
+    };
+
+    if entry.parent.is_some() || entry.dead || entry.deleted {
+      return false;
+    }
+
+    let filter = self.filter.to_lowercase();
+
+    filter.split_whitespace().all(|term| {
+      entry.body.to_lowercase().contains(term)
+        || entry
+          .author
+          .as_deref()
+          .is_some_and(|author| author.to_lowercase().contains(term))
+    })
+  }
+
+  fn matches_query(&self, idx: usize) -> bool {
+    let (Some(query), Some(entry)) =
+      (self.search_query.as_deref(), self.entries.get(idx))
+    else {
+      return false;
+    };
+
+    let query = query.to_lowercase();
+
+    entry.body.to_lowercase().contains(&query)
+      || entry
+        .author
+        .as_deref()
+        .is_some_and(|author| author.to_lowercase().contains(&query))
+  }
+
   pub(crate) fn move_by(&mut self, delta: isize) {
     let (visible, selected_pos) = self.visible_with_selection();
 
@@ -108,25 +501,11 @@ impl CommentView {
   }
 
   pub(crate) fn new(thread: CommentThread, comment_link: String) -> Self {
-    let CommentThread { focus, roots } = thread;
-
-    let mut entries = Vec::new();
-    let mut selected = None;
-
-    for comment in roots {
-      Self::push_comment(&mut entries, comment, None, 0, focus, &mut selected);
-    }
-
-    if selected.is_none() && !entries.is_empty() {
-      selected = Some(0);
-    }
+    Self::with_max_depth(thread, comment_link, Self::DEFAULT_MAX_DEPTH)
+  }
 
-    Self {
-      entries,
-      link: comment_link,
-      offset: 0,
-      selected,
-    }
+  pub(crate) fn next_match(&mut self) -> bool {
+    self.step_match(true)
   }
 
   pub(crate) fn page_down(&mut self, amount: usize) {
@@ -141,11 +520,42 @@ impl CommentView {
     self.move_by(-delta);
   }
 
+  pub(crate) fn parent_reference(&self, idx: usize) -> Option<String> {
+    let parent = self.entries.get(idx)?.parent?;
+
+    let author = self
+      .entries
+      .get(parent)
+      .and_then(|entry| entry.author.as_deref())
+      .unwrap_or("unknown");
+
+    Some(format!("↳ {author}"))
+  }
+
+  fn position_at_line(heights: &[usize], line: usize) -> Option<usize> {
+    let mut top = 0usize;
+
+    for (position, height) in heights.iter().enumerate() {
+      top = top.saturating_add(*height);
+
+      if line < top {
+        return Some(position);
+      }
+    }
+
+    heights.len().checked_sub(1)
+  }
+
+  pub(crate) fn prev_match(&mut self) -> bool {
+    self.step_match(false)
+  }
+
   fn push_comment(
     entries: &mut Vec<CommentEntry>,
     comment: Comment,
     parent: Option<usize>,
     depth: usize,
+    max_depth: usize,
     focus: Option<u64>,
     selected: &mut Option<usize>,
   ) -> usize {
@@ -154,8 +564,12 @@ impl CommentView {
       children,
       dead,
       deleted,
+      html,
       id,
+      links,
+      loaded,
       text,
+      time,
     } = comment;
 
     let body = if deleted {
@@ -175,9 +589,16 @@ impl CommentView {
       dead,
       deleted,
       depth,
-      expanded: true,
+      expanded: loaded,
+      folded: Vec::new(),
+      html,
       id,
+      is_op: false,
+      is_post: false,
+      links,
+      loaded,
       parent,
+      time,
     });
 
     if selected.is_none() && focus == Some(id) {
@@ -186,17 +607,30 @@ impl CommentView {
 
     let mut child_indices = Vec::new();
 
-    for child in children {
-      let child_idx = Self::push_comment(
-        entries,
-        child,
-        Some(idx),
-        depth.saturating_add(1),
-        focus,
-        selected,
-      );
+    let leads_to_focus = focus
+      .is_some_and(|focus| children.iter().any(|child| child.contains(focus)));
+
+    if depth.saturating_add(1) >= max_depth
+      && !children.is_empty()
+      && !leads_to_focus
+    {
+      child_indices.push(entries.len());
 
-      child_indices.push(child_idx);
+      entries.push(Self::fold(children, idx, depth.saturating_add(1)));
+    } else {
+      for child in children {
+        let child_idx = Self::push_comment(
+          entries,
+          child,
+          Some(idx),
+          depth.saturating_add(1),
+          max_depth,
+          focus,
+          selected,
+        );
+
+        child_indices.push(child_idx);
+      }
     }
 
     if let Some(entry) = entries.get_mut(idx) {
@@ -206,6 +640,86 @@ impl CommentView {
     idx
   }
 
+  fn root_of(&self, idx: usize) -> usize {
+    let mut current = idx;
+
+    while let Some(parent) =
+      self.entries.get(current).and_then(|entry| entry.parent)
+    {
+      current = parent;
+    }
+
+    current
+  }
+
+  pub(crate) fn scroll_by(&mut self, delta: isize) {
+    let magnitude = delta.unsigned_abs();
+
+    self.line_offset = if delta >= 0 {
+      self.line_offset.saturating_add(magnitude)
+    } else {
+      self.line_offset.saturating_sub(magnitude)
+    };
+
+    self.select_at_line();
+  }
+
+  pub(crate) fn scroll_horizontally(&mut self, delta: isize) {
+    let longest = self
+      .selected_entry()
+      .filter(|entry| !entry.is_fold())
+      .and_then(|entry| {
+        entry.body().lines().map(|line| line.chars().count()).max()
+      })
+      .unwrap_or_default();
+
+    let magnitude = delta.unsigned_abs();
+
+    let offset = if delta >= 0 {
+      self.horizontal_offset().saturating_add(magnitude)
+    } else {
+      self.horizontal_offset().saturating_sub(magnitude)
+    };
+
+    self.horizontal_offset = offset.min(longest.saturating_sub(1));
+    self.horizontal_selection = self.selected;
+  }
+
+  pub(crate) fn scroll_to_end(&mut self) {
+    let last = self.line_heights.last().copied().unwrap_or_default();
+
+    self.line_offset =
+      self.line_heights.iter().sum::<usize>().saturating_sub(last);
+
+    self.select_at_line();
+  }
+
+  pub(crate) fn search_query(&self) -> Option<&str> {
+    self.search_query.as_deref()
+  }
+
+  fn select_at_line(&mut self) {
+    let total = self.line_heights.iter().sum::<usize>();
+
+    self.line_offset = self.line_offset.min(total.saturating_sub(1));
+
+    let (visible, _) = self.visible_with_selection();
+
+    if let Some(position) =
+      Self::position_at_line(&self.line_heights, self.line_offset)
+      && let Some(&idx) = visible.get(position)
+    {
+      self.selected = Some(idx);
+    }
+  }
+
+  pub(crate) fn select_comment(&mut self, id: u64) {
+    if let Some(idx) = self.entries.iter().position(|entry| entry.id == id) {
+      self.selected = Some(idx);
+      self.ensure_selection_visible();
+    }
+  }
+
   pub(crate) fn select_index_at(&mut self, pos: usize) {
     let (visible, _) = self.visible_with_selection();
 
@@ -233,23 +747,82 @@ impl CommentView {
     self.selected = Some(visible[next]);
   }
 
-  pub(crate) fn select_previous(&mut self) {
+  pub(crate) fn select_next_sibling(&mut self) {
     let (visible, selected_pos) = self.visible_with_selection();
 
-    if visible.is_empty() {
-      self.selected = None;
+    let Some(position) = selected_pos else {
+      self.selected = visible.first().copied();
       return;
-    }
+    };
 
-    let current = selected_pos.unwrap_or(0);
-    let previous = current.saturating_sub(1);
+    let parent = self.entries[visible[position]].parent;
 
-    self.selected = Some(visible[previous]);
+    if let Some(&next) = visible[position + 1..]
+      .iter()
+      .find(|&&idx| self.entries[idx].parent == parent)
+    {
+      self.selected = Some(next);
+    }
+
+    self.ensure_selection_visible();
   }
 
-  pub(crate) fn selected_comment_link(&self) -> Option<String> {
-    self
-      .selected
+  pub(crate) fn select_next_unread(&mut self) -> bool {
+    self.mark_visited();
+
+    let (visible, selected_pos) = self.visible_with_selection();
+
+    let start = selected_pos.map_or(0, |pos| pos + 1);
+
+    let Some(found) = visible.into_iter().skip(start).find(|&idx| {
+      let entry = &self.entries[idx];
+      !entry.is_fold() && !self.visited.contains(&entry.id)
+    }) else {
+      return false;
+    };
+
+    self.selected = Some(found);
+
+    self.mark_visited();
+
+    true
+  }
+
+  pub(crate) fn select_previous(&mut self) {
+    let (visible, selected_pos) = self.visible_with_selection();
+
+    if visible.is_empty() {
+      self.selected = None;
+      return;
+    }
+
+    let current = selected_pos.unwrap_or(0);
+    let previous = current.saturating_sub(1);
+
+    self.selected = Some(visible[previous]);
+  }
+
+  pub(crate) fn select_root_next(&mut self) {
+    let (visible, selected_pos) = self.visible_with_selection();
+
+    let Some(position) = selected_pos else {
+      self.selected = visible.first().copied();
+      return;
+    };
+
+    if let Some(&next) = visible[position + 1..]
+      .iter()
+      .find(|&&idx| self.entries[idx].depth == 0)
+    {
+      self.selected = Some(next);
+    }
+
+    self.ensure_selection_visible();
+  }
+
+  pub(crate) fn selected_comment_link(&self) -> Option<String> {
+    self
+      .selected
       .and_then(|idx| self.entries.get(idx))
       .map(CommentEntry::permalink)
   }
@@ -258,11 +831,263 @@ impl CommentView {
     self.selected.and_then(|idx| self.entries.get(idx))
   }
 
+  fn selected_lines(&self) -> Option<Range<usize>> {
+    let (_, selected_pos) = self.visible_with_selection();
+
+    let position = selected_pos?;
+
+    let top = self.line_heights.iter().take(position).sum::<usize>();
+
+    let height = self.line_heights.get(position).copied()?;
+
+    Some(top..top.saturating_add(height))
+  }
+
+  pub(crate) fn set_filter(&mut self, filter: String) {
+    self.filter = filter;
+    self.ensure_selection_visible();
+  }
+
+  pub(crate) fn set_line_heights(&mut self, heights: Vec<usize>) {
+    self.line_heights = heights;
+
+    match self.selected_lines() {
+      Some(lines) if !lines.contains(&self.line_offset) => {
+        self.line_offset = lines.start;
+      }
+      Some(_) => {}
+      None => self.select_at_line(),
+    }
+  }
+
+  pub(crate) fn set_search_query(&mut self, query: String) {
+    self.search_query = (!query.is_empty()).then_some(query);
+  }
+
+  fn splice_children(
+    &mut self,
+    parent: usize,
+    children: Vec<Comment>,
+    max_depth: usize,
+  ) {
+    let (visible_before, _) = self.visible_with_selection();
+
+    let parent_position = visible_before.iter().position(|&idx| idx == parent);
+
+    let depth = self.entries[parent].depth.saturating_add(1);
+
+    let mut inserted = Vec::new();
+
+    for child in children {
+      Self::push_comment(
+        &mut inserted,
+        child,
+        None,
+        depth,
+        max_depth,
+        None,
+        &mut None,
+      );
+    }
+
+    Self::mark_op(&mut inserted, self.op_author.as_deref());
+
+    let start = parent + 1;
+    let count = inserted.len();
+
+    let shift = |idx: usize| if idx >= start { idx + count } else { idx };
+
+    for entry in &mut self.entries {
+      entry.parent = entry.parent.map(shift);
+
+      for child in &mut entry.children {
+        *child = shift(*child);
+      }
+    }
+
+    self.focus = self.focus.map(shift);
+    self.selected = self.selected.map(shift);
+
+    let roots = inserted
+      .iter()
+      .enumerate()
+      .filter(|(_, entry)| entry.parent.is_none())
+      .map(|(idx, _)| idx + start)
+      .collect();
+
+    for entry in &mut inserted {
+      entry.parent = Some(entry.parent.map_or(parent, |idx| idx + start));
+
+      for child in &mut entry.children {
+        *child += start;
+      }
+    }
+
+    self.entries.splice(start..start, inserted);
+
+    if let Some(entry) = self.entries.get_mut(parent) {
+      entry.children = roots;
+      entry.loaded = true;
+    }
+
+    let visible_after = self.visible_indexes().len();
+
+    if parent_position.is_some_and(|position| position < self.offset) {
+      self.offset = self
+        .offset
+        .saturating_add(visible_after.saturating_sub(visible_before.len()));
+    }
+
+    self.ensure_selection_visible();
+  }
+
+  fn step_match(&mut self, forward: bool) -> bool {
+    let order = self.display_order((0..self.entries.len()).collect());
+
+    let count = order.len();
+
+    if count == 0 {
+      return false;
+    }
+
+    let start = self
+      .selected
+      .and_then(|selected| order.iter().position(|&idx| idx == selected))
+      .unwrap_or(if forward { count - 1 } else { 0 });
+
+    let Some(found) = (1..=count)
+      .map(|step| {
+        if forward {
+          order[(start + step) % count]
+        } else {
+          order[(start + count - step) % count]
+        }
+      })
+      .find(|&idx| self.matches_query(idx))
+    else {
+      return false;
+    };
+
+    let mut ancestor = self.entries[found].parent;
+
+    while let Some(idx) = ancestor {
+      self.entries[idx].expanded = true;
+      ancestor = self.entries[idx].parent;
+    }
+
+    self.selected = Some(found);
+
+    self.ensure_selection_visible();
+
+    true
+  }
+
+  pub(crate) fn sync_horizontal_offset(&mut self) {
+    if self.horizontal_selection != self.selected {
+      self.horizontal_offset = 0;
+      self.horizontal_selection = None;
+    }
+  }
+
+  pub(crate) fn thread(&self) -> CommentThread {
+    let post = self.entries.iter().find(|entry| entry.is_post);
+
+    CommentThread {
+      body: post.map(|entry| entry.body.clone()),
+      body_links: post.map(|entry| entry.links.clone()).unwrap_or_default(),
+      descendants: self.descendants,
+      focus: None,
+      id: self.item_id,
+      op_author: self.op_author.clone(),
+      partial: self.partial,
+      roots: self
+        .entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| entry.parent.is_none() && !entry.is_post)
+        .map(|(idx, _)| self.comment_at(idx))
+        .collect(),
+      score: self.score,
+      title: self.title.clone(),
+    }
+  }
+
+  pub(crate) fn title(&self) -> Option<String> {
+    let title = self.title.clone()?;
+
+    Some(
+      [
+        Some(title),
+        self.score.map(format_points),
+        self.descendants.map(format_comments),
+      ]
+      .into_iter()
+      .flatten()
+      .collect::<Vec<String>>()
+      .join(DETAIL_SEPARATOR),
+    )
+  }
+
+  pub(crate) fn toggle_flat(&mut self) {
+    self.flat = !self.flat;
+    self.ensure_selection_visible();
+  }
+
+  pub(crate) fn toggle_focus_only(&mut self) {
+    if self.focus.is_none() {
+      return;
+    }
+
+    self.focus_only = !self.focus_only;
+
+    self.ensure_selection_visible();
+  }
+
+  pub(crate) fn toggle_hide_dead(&mut self) {
+    self.hide_dead = !self.hide_dead;
+
+    let Some(selected) = self.selected else {
+      return;
+    };
+
+    if self.is_visible(selected) {
+      return;
+    }
+
+    let visible = self.visible_indexes();
+
+    self.selected = visible
+      .iter()
+      .rev()
+      .find(|&&idx| idx < selected)
+      .or_else(|| visible.first())
+      .copied();
+  }
+
+  pub(crate) fn toggle_listing(&mut self) {
+    self.listing = !self.listing;
+    self.ensure_selection_visible();
+  }
+
+  pub(crate) fn toggle_scrolling(&mut self) {
+    self.scrolling = !self.scrolling;
+
+    if let Some(lines) = self.selected_lines() {
+      self.line_offset = lines.start;
+    }
+  }
+
   pub(crate) fn toggle_selected(&mut self) {
+    if let Some(selected) = self.selected
+      && self.unfold(selected)
+    {
+      return;
+    }
+
     if let Some(selected) = self.selected
       && let Some(entry) = self.entries.get_mut(selected)
     {
-      if entry.children.is_empty() {
+      if !entry.has_children() {
         return;
       }
 
@@ -272,16 +1097,54 @@ impl CommentView {
     self.ensure_selection_visible();
   }
 
-  pub(crate) fn visible_indexes(&self) -> Vec<usize> {
-    let mut visible = Vec::new();
+  fn unfold(&mut self, idx: usize) -> bool {
+    let Some(entry) = self.entries.get(idx).filter(|entry| entry.is_fold())
+    else {
+      return false;
+    };
 
-    for idx in 0..self.entries.len() {
-      if self.is_visible(idx) {
-        visible.push(idx);
+    let Some(parent) = entry.parent else {
+      return false;
+    };
+
+    let comments = self.entries.remove(idx).folded;
+
+    let shift = |i: usize| if i > idx { i - 1 } else { i };
+
+    for entry in &mut self.entries {
+      entry.parent = entry.parent.map(shift);
+
+      entry.children.retain(|&child| child != idx);
+
+      for child in &mut entry.children {
+        *child = shift(*child);
       }
     }
 
-    visible
+    self.focus = self.focus.filter(|&focus| focus != idx).map(shift);
+
+    self.splice_children(parent, comments, usize::MAX);
+
+    self.selected = self.entries[parent].children.first().copied();
+
+    true
+  }
+
+  pub(crate) fn unloaded_expanded(&self) -> Vec<u64> {
+    self
+      .entries
+      .iter()
+      .filter(|entry| entry.expanded && !entry.loaded)
+      .map(|entry| entry.id)
+      .collect()
+  }
+
+  pub(crate) fn visible_indexes(&self) -> Vec<usize> {
+    self.display_order(
+      (0..self.entries.len())
+        .filter(|&idx| self.is_visible(idx))
+        .collect(),
+    )
   }
 
   pub(crate) fn visible_with_selection(&self) -> (Vec<usize>, Option<usize>) {
@@ -293,6 +1156,96 @@ impl CommentView {
 
     (visible, selected_pos)
   }
+
+  pub(crate) fn with_max_depth(
+    thread: CommentThread,
+    comment_link: String,
+    max_depth: usize,
+  ) -> Self {
+    let CommentThread {
+      body,
+      body_links,
+      descendants,
+      focus,
+      id,
+      op_author,
+      partial,
+      roots,
+      score,
+      title,
+    } = thread;
+
+    let mut entries = Vec::new();
+    let mut selected = None;
+
+    if let Some(body) = body {
+      entries.push(CommentEntry {
+        author: op_author.clone(),
+        body,
+        children: Vec::new(),
+        dead: false,
+        deleted: false,
+        depth: 0,
+        expanded: true,
+        folded: Vec::new(),
+        html: None,
+        id,
+        is_op: false,
+        is_post: true,
+        links: body_links,
+        loaded: true,
+        parent: None,
+        time: None,
+      });
+    }
+
+    for comment in roots {
+      Self::push_comment(
+        &mut entries,
+        comment,
+        None,
+        0,
+        max_depth,
+        focus,
+        &mut selected,
+      );
+    }
+
+    Self::mark_op(&mut entries, op_author.as_deref());
+
+    let focus_index = selected;
+
+    if selected.is_none() && !entries.is_empty() {
+      selected = Some(0);
+    }
+
+    Self {
+      descendants,
+      entries,
+      filter: String::new(),
+      flat: false,
+      focus: focus_index,
+      focus_only: false,
+      hide_dead: false,
+      horizontal_offset: 0,
+      horizontal_selection: None,
+      item_id: id,
+      line_heights: Vec::new(),
+      line_offset: 0,
+      link: comment_link,
+      listing: false,
+      max_depth,
+      offset: 0,
+      op_author,
+      partial,
+      score,
+      scrolling: false,
+      search_query: None,
+      selected,
+      title,
+      visited: HashSet::new(),
+    }
+  }
 }
 
 #[cfg(test)]
@@ -308,8 +1261,12 @@ mod tests {
       children,
       dead: false,
       deleted: false,
+      html: None,
       id,
+      links: Vec::new(),
+      loaded: true,
       text: Some(format!("comment {id}")),
+      time: None,
     }
   }
 
@@ -320,13 +1277,89 @@ mod tests {
 
     CommentView::new(
       CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
         focus,
+        id: 1,
+        op_author: None,
+        partial: false,
         roots: vec![parent],
+        score: None,
+        title: None,
       },
       ROOT_COMMENT_LINK.to_string(),
     )
   }
 
+  #[test]
+  fn comments_by_the_story_author_get_an_op_badge() {
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: Some("user3".to_string()),
+        partial: false,
+        roots: vec![make_comment(2, vec![make_comment(3, Vec::new())])],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    assert_eq!(view.entries[0].header(0), "user2");
+    assert_eq!(view.entries[1].header(0), "user3 [OP]");
+
+    let mut reply = make_comment(4, Vec::new());
+
+    reply.author = Some("user3".to_string());
+
+    view.insert_children(3, vec![reply]);
+
+    assert!(view.entries[2].is_op);
+  }
+
+  #[test]
+  fn post_body_is_shown_before_the_replies() {
+    let view = CommentView::new(
+      CommentThread {
+        body: Some("What are you working on?".to_string()),
+        body_links: vec!["https://example.com/demo".to_string()],
+        descendants: None,
+        focus: None,
+        id: 9,
+        op_author: Some("user1".to_string()),
+        partial: false,
+        roots: vec![make_comment(1, vec![make_comment(2, Vec::new())])],
+        score: None,
+        title: Some("Ask HN: Projects".to_string()),
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    assert_eq!(view.selected, Some(0));
+    assert_eq!(view.visible_indexes(), vec![0, 1, 2]);
+
+    let post = &view.entries[0];
+
+    assert!(post.is_post && !post.is_op);
+    assert_eq!(post.body(), "What are you working on?");
+    assert_eq!(post.author.as_deref(), Some("user1"));
+    assert_eq!(post.links, ["https://example.com/demo"]);
+
+    assert!(view.entries[1].is_op);
+
+    let thread = view.thread();
+
+    assert_eq!(thread.body.as_deref(), Some("What are you working on?"));
+    assert_eq!(thread.body_links, post.links);
+    assert_eq!(thread.roots.len(), 1);
+    assert_eq!(thread.roots[0].id, 1);
+  }
+
   #[test]
   fn new_selects_focused_comment_when_present() {
     let view = make_view(Some(2));
@@ -363,28 +1396,952 @@ mod tests {
   }
 
   #[test]
-  fn collapse_selected_moves_to_parent_when_child_selected() {
-    let mut view = make_view(None);
-    view.select_index_at(1);
-    assert_eq!(view.selected, Some(1));
-    view.collapse_selected();
+  fn collapse_all_and_expand_all_toggle_every_level() {
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![
+          make_comment(1, vec![make_comment(2, vec![make_comment(3, vec![])])]),
+          make_comment(4, vec![make_comment(5, Vec::new())]),
+        ],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    assert_eq!(view.visible_indexes(), vec![0, 1, 2, 3, 4]);
+
+    view.select_index_at(2);
+    view.collapse_all();
+
+    assert_eq!(view.visible_indexes(), vec![0, 3]);
     assert_eq!(view.selected, Some(0));
+
+    view.select_index_at(1);
+    view.expand_all();
+
+    assert_eq!(view.visible_indexes(), vec![0, 1, 2, 3, 4]);
+    assert_eq!(view.selected, Some(3));
   }
 
   #[test]
-  fn expand_selected_moves_into_first_child() {
-    let mut view = make_view(None);
-    view.expand_selected();
-    assert_eq!(view.selected, Some(1));
+  fn collapse_dead_folds_dead_subtrees_until_expanded() {
+    let dead = |id, children| Comment {
+      dead: true,
+      ..make_comment(id, children)
+    };
+
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![
+          dead(1, vec![make_comment(2, vec![])]),
+          dead(3, Vec::new()),
+          make_comment(4, vec![make_comment(5, vec![])]),
+        ],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    view.collapse_dead();
+
+    assert_eq!(view.visible_indexes(), vec![0, 2, 3, 4]);
+    assert!(!view.entries[0].expanded);
+    assert!(view.entries[3].expanded);
+
+    view.select_index_at(0);
+    view.toggle_selected();
+
+    assert_eq!(view.visible_indexes(), vec![0, 1, 2, 3, 4]);
   }
 
   #[test]
-  fn ensure_selection_visible_promotes_hidden_selection() {
-    let mut view = make_view(None);
-    view.select_index_at(1);
-    view.entries[0].expanded = false;
-    view.ensure_selection_visible();
-    assert_eq!(view.selected, Some(0));
+  fn collapse_subtree_collapses_every_descendant() {
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![make_comment(
+          1,
+          vec![
+            make_comment(
+              2,
+              vec![make_comment(3, vec![make_comment(4, vec![])])],
+            ),
+            make_comment(5, Vec::new()),
+          ],
+        )],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    assert_eq!(view.visible_indexes(), vec![0, 1, 2, 3, 4]);
+
+    view.select_index_at(3);
+    view.collapse_subtree(1);
+
+    assert_eq!(view.visible_indexes(), vec![0, 1, 4]);
+    assert_eq!(view.selected, Some(1));
+    assert!(!view.entries[2].expanded);
+
+    view.expand_selected();
+
+    assert_eq!(view.visible_indexes(), vec![0, 1, 2, 4]);
+  }
+
+  #[test]
+  fn hide_dead_skips_dead_leaves_but_keeps_dead_parents_of_live_replies() {
+    let dead = |id, children| Comment {
+      dead: true,
+      ..make_comment(id, children)
+    };
+
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![
+          make_comment(
+            1,
+            vec![dead(2, Vec::new()), dead(3, vec![make_comment(4, vec![])])],
+          ),
+          dead(5, Vec::new()),
+        ],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    view.select_index_at(1);
+    view.toggle_hide_dead();
+
+    assert_eq!(view.visible_indexes(), vec![0, 2, 3]);
+    assert_eq!(view.selected, Some(0));
+    assert!(view.is_redacted(2));
+    assert!(!view.is_redacted(3));
+
+    view.toggle_hide_dead();
+
+    assert_eq!(view.visible_indexes(), vec![0, 1, 2, 3, 4]);
+    assert!(!view.is_redacted(2));
+  }
+
+  #[test]
+  fn collapse_selected_moves_to_parent_when_child_selected() {
+    let mut view = make_view(None);
+    view.select_index_at(1);
+    assert_eq!(view.selected, Some(1));
+    view.collapse_selected();
+    assert_eq!(view.selected, Some(0));
+  }
+
+  #[test]
+  fn expand_selected_moves_into_first_child() {
+    let mut view = make_view(None);
+    view.expand_selected();
+    assert_eq!(view.selected, Some(1));
+  }
+
+  #[test]
+  fn clamp_offset_keeps_selection_within_shrunken_viewport() {
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: (2..8).map(|id| make_comment(id, Vec::new())).collect(),
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    view.select_index_at(5);
+    view.offset = 2;
+
+    view.clamp_offset(2);
+
+    assert_eq!(view.offset, 4);
+
+    view.offset = 9;
+    view.select_index_at(1);
+
+    view.clamp_offset(3);
+
+    assert_eq!(view.offset, 1);
+  }
+
+  #[test]
+  fn new_preserves_thread_title_with_counts() {
+    assert_eq!(make_view(None).title(), None);
+
+    let view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: Some(12),
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: Vec::new(),
+        score: Some(1),
+        title: Some("Show HN: A thing".to_string()),
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    assert_eq!(
+      view.title().as_deref(),
+      Some("Show HN: A thing • 1 point • 12 comments")
+    );
+  }
+
+  #[test]
+  fn thread_rebuilds_comments_including_folded_replies() {
+    let chain = (1..10)
+      .rev()
+      .fold(make_comment(10, Vec::new()), |child, id| {
+        make_comment(id, vec![child])
+      });
+
+    let view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: Some(10),
+        focus: None,
+        id: 0,
+        op_author: Some("user".to_string()),
+        partial: false,
+        roots: vec![chain, make_comment(11, Vec::new())],
+        score: Some(5),
+        title: Some("A thread".to_string()),
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    let thread = view.thread();
+
+    let mut ids = Vec::new();
+    let mut stack = thread.roots.iter().rev().collect::<Vec<&Comment>>();
+
+    while let Some(comment) = stack.pop() {
+      ids.push(comment.id);
+      stack.extend(comment.children.iter().rev());
+    }
+
+    assert_eq!(ids, (1..=11).collect::<Vec<u64>>());
+    assert_eq!(thread.roots[0].text.as_deref(), Some("comment 1"));
+
+    assert_eq!(
+      CommentView::new(thread, ROOT_COMMENT_LINK.to_string())
+        .title()
+        .as_deref(),
+      Some("A thread • 5 points • 10 comments")
+    );
+  }
+
+  #[test]
+  fn replies_beyond_max_depth_are_folded_until_expanded() {
+    let chain = (1..10)
+      .rev()
+      .fold(make_comment(10, Vec::new()), |child, id| {
+        make_comment(id, vec![child])
+      });
+
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 0,
+        op_author: None,
+        partial: false,
+        roots: vec![chain],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    assert_eq!(view.entries.len(), 9);
+
+    let fold = &view.entries[8];
+
+    assert!(fold.is_fold());
+    assert_eq!(fold.depth, 8);
+    assert_eq!(fold.body(), "2 more replies");
+    assert_eq!(fold.parent, Some(7));
+    assert_eq!(view.entries[7].children, [8]);
+
+    view.select_comment(9);
+
+    assert_eq!(view.selected, Some(8));
+
+    view.toggle_selected();
+
+    assert_eq!(
+      view
+        .entries
+        .iter()
+        .map(|entry| entry.id)
+        .collect::<Vec<u64>>(),
+      (1..=10).collect::<Vec<u64>>()
+    );
+
+    assert!(view.entries.iter().all(|entry| !entry.is_fold()));
+    assert_eq!(view.entries[9].depth, 9);
+    assert_eq!(view.entries[8].parent, Some(7));
+    assert_eq!(view.entries[8].children, [9]);
+    assert_eq!(view.selected, Some(8));
+  }
+
+  #[test]
+  fn replies_leading_to_the_focus_stay_unfolded_beyond_max_depth() {
+    let chain = (1..12)
+      .rev()
+      .fold(make_comment(12, Vec::new()), |child, id| {
+        make_comment(id, vec![child])
+      });
+
+    let view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: Some(10),
+        id: 0,
+        op_author: None,
+        partial: false,
+        roots: vec![chain],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    assert_eq!(view.entries.len(), 11);
+    assert_eq!(view.selected, Some(9));
+    assert_eq!(view.focus, Some(9));
+    assert_eq!(view.entries[9].id, 10);
+    assert_eq!(view.entries[9].depth, 9);
+
+    let fold = &view.entries[10];
+
+    assert!(fold.is_fold());
+    assert_eq!(fold.body(), "2 more replies");
+    assert_eq!(fold.parent, Some(9));
+  }
+
+  #[test]
+  fn ensure_selection_visible_promotes_hidden_selection() {
+    let mut view = make_view(None);
+    view.select_index_at(1);
+    view.entries[0].expanded = false;
+    view.ensure_selection_visible();
+    assert_eq!(view.selected, Some(0));
+  }
+
+  #[test]
+  fn focus_only_restricts_visible_entries_to_focused_subtree() {
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: Some(2),
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![
+          make_comment(1, vec![make_comment(2, vec![make_comment(3, vec![])])]),
+          make_comment(4, Vec::new()),
+        ],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    assert_eq!(view.visible_indexes(), vec![0, 1, 2, 3]);
+
+    view.toggle_focus_only();
+
+    assert_eq!(view.visible_indexes(), vec![1, 2]);
+    assert_eq!(view.selected, Some(1));
+
+    view.toggle_focus_only();
+
+    assert_eq!(view.visible_indexes(), vec![0, 1, 2, 3]);
+    assert_eq!(view.selected, Some(1));
+  }
+
+  #[test]
+  fn toggle_focus_only_is_noop_without_focus() {
+    let mut view = make_view(None);
+
+    view.toggle_focus_only();
+
+    assert!(!view.focus_only);
+    assert_eq!(view.visible_indexes(), vec![0, 1]);
+  }
+
+  #[test]
+  fn listing_shows_root_comments_matching_filter() {
+    let mut root = make_comment(3, Vec::new());
+    root.text = Some("Acme | Rust engineer | REMOTE".to_string());
+
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![make_comment(1, vec![make_comment(2, Vec::new())]), root],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    view.toggle_listing();
+
+    assert_eq!(view.visible_indexes(), vec![0, 2]);
+
+    view.set_filter("rust remote".to_string());
+
+    assert_eq!(view.visible_indexes(), vec![2]);
+    assert_eq!(view.selected, Some(2));
+
+    view.set_filter(String::new());
+    view.toggle_listing();
+
+    assert_eq!(view.visible_indexes(), vec![0, 1, 2]);
+  }
+
+  #[test]
+  fn advance_root_collapses_previous_root_and_expands_next() {
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![
+          make_comment(1, vec![make_comment(2, Vec::new())]),
+          make_comment(3, vec![make_comment(4, Vec::new())]),
+        ],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    view.select_index_at(1);
+    view.advance_root(true);
+
+    assert_eq!(view.selected, Some(2));
+    assert!(!view.entries[0].expanded);
+    assert!(view.entries[2].expanded);
+    assert_eq!(view.visible_indexes(), vec![0, 2, 3]);
+
+    view.advance_root(true);
+
+    assert_eq!(view.selected, Some(2));
+
+    view.advance_root(false);
+
+    assert_eq!(view.selected, Some(0));
+    assert!(view.entries[0].expanded);
+    assert!(!view.entries[2].expanded);
+  }
+
+  #[test]
+  fn insert_children_fills_unloaded_comment_and_keeps_selection() {
+    let mut unloaded = make_comment(1, Vec::new());
+    unloaded.loaded = false;
+
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![
+          make_comment(5, Vec::new()),
+          unloaded,
+          make_comment(6, Vec::new()),
+        ],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    assert!(view.entries[1].has_children());
+    assert!(!view.entries[1].expanded);
+
+    view.select_index_at(1);
+    view.expand_selected();
+
+    assert_eq!(view.unloaded_expanded(), [1]);
+
+    view.select_index_at(2);
+    view.offset = 2;
+
+    view.insert_children(
+      1,
+      vec![make_comment(2, vec![make_comment(3, Vec::new())])],
+    );
+
+    let ids = view
+      .visible_indexes()
+      .into_iter()
+      .map(|idx| view.entries[idx].id)
+      .collect::<Vec<_>>();
+
+    assert_eq!(ids, [5, 1, 2, 3, 6]);
+    assert_eq!(view.selected.map(|idx| view.entries[idx].id), Some(6));
+    assert_eq!(view.offset, 4);
+    assert_eq!(view.entries[2].parent, Some(1));
+    assert_eq!(view.entries[3].parent, Some(2));
+    assert_eq!(view.entries[1].children, [2]);
+    assert!(view.unloaded_expanded().is_empty());
+  }
+
+  #[test]
+  fn position_at_line_maps_lines_to_entries() {
+    let heights = [3, 1, 4];
+
+    assert_eq!(CommentView::position_at_line(&heights, 0), Some(0));
+    assert_eq!(CommentView::position_at_line(&heights, 2), Some(0));
+    assert_eq!(CommentView::position_at_line(&heights, 3), Some(1));
+    assert_eq!(CommentView::position_at_line(&heights, 4), Some(2));
+    assert_eq!(CommentView::position_at_line(&heights, 7), Some(2));
+    assert_eq!(CommentView::position_at_line(&heights, 99), Some(2));
+    assert_eq!(CommentView::position_at_line(&[], 0), None);
+  }
+
+  #[test]
+  fn scrolling_selects_comment_at_top_of_viewport() {
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![
+          make_comment(1, vec![make_comment(2, Vec::new())]),
+          make_comment(3, Vec::new()),
+        ],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    view.toggle_scrolling();
+    view.set_line_heights(vec![3, 2, 4]);
+
+    view.scroll_by(2);
+    assert_eq!((view.line_offset(), view.selected), (2, Some(0)));
+
+    view.scroll_by(1);
+    assert_eq!((view.line_offset(), view.selected), (3, Some(1)));
+
+    view.scroll_by(100);
+    assert_eq!((view.line_offset(), view.selected), (8, Some(2)));
+
+    view.scroll_to_end();
+    assert_eq!((view.line_offset(), view.selected), (5, Some(2)));
+
+    view.scroll_by(-100);
+    assert_eq!((view.line_offset(), view.selected), (0, Some(0)));
+  }
+
+  #[test]
+  fn line_offset_follows_selection_moved_by_other_commands() {
+    let mut view = make_view(None);
+
+    view.toggle_scrolling();
+    view.set_line_heights(vec![3, 2]);
+
+    view.scroll_by(4);
+    assert_eq!(view.selected, Some(1));
+
+    view.collapse_selected();
+    view.set_line_heights(vec![3, 2]);
+
+    assert_eq!((view.line_offset(), view.selected), (0, Some(0)));
+  }
+
+  #[test]
+  fn flat_mode_lists_comments_newest_first() {
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![
+          make_comment(
+            1,
+            vec![make_comment(5, vec![make_comment(7, Vec::new())])],
+          ),
+          make_comment(3, vec![make_comment(4, Vec::new())]),
+        ],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    view.entries[3].expanded = false;
+
+    view.toggle_flat();
+
+    assert!(view.is_flat());
+
+    let ids = view
+      .visible_indexes()
+      .into_iter()
+      .map(|idx| view.entries[idx].id)
+      .collect::<Vec<u64>>();
+
+    assert_eq!(ids, vec![7, 5, 4, 3, 1]);
+
+    view.select_index_at(0);
+    view.collapse_selected();
+    assert_eq!(view.selected_entry().map(|entry| entry.id), Some(5));
+
+    view.toggle_flat();
+
+    assert_eq!(view.visible_indexes(), vec![0, 1, 2, 3]);
+  }
+
+  #[test]
+  fn flat_mode_navigation_follows_the_displayed_order() {
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![
+          make_comment(
+            1,
+            vec![make_comment(5, vec![make_comment(7, Vec::new())])],
+          ),
+          make_comment(3, vec![make_comment(4, Vec::new())]),
+        ],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    view.toggle_flat();
+
+    let selected_id =
+      |view: &CommentView| view.selected_entry().map(|entry| entry.id);
+
+    view.select_index_at(3);
+    view.select_root_next();
+    assert_eq!(selected_id(&view), Some(1));
+
+    view.select_index_at(3);
+    view.select_next_sibling();
+    assert_eq!(selected_id(&view), Some(1));
+
+    view.set_search_query("comment".into());
+    view.select_index_at(1);
+
+    assert!(view.next_match());
+    assert_eq!(selected_id(&view), Some(4));
+
+    assert!(view.prev_match());
+    assert_eq!(selected_id(&view), Some(5));
+  }
+
+  #[test]
+  fn parent_reference_names_parent_author() {
+    let view = make_view(None);
+
+    assert_eq!(view.parent_reference(0), None);
+    assert_eq!(view.parent_reference(1).as_deref(), Some("↳ user1"));
+  }
+
+  #[test]
+  fn select_next_sibling_skips_replies_and_stays_at_last_sibling() {
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![
+          make_comment(
+            1,
+            vec![
+              make_comment(2, vec![make_comment(3, Vec::new())]),
+              make_comment(4, Vec::new()),
+            ],
+          ),
+          make_comment(5, Vec::new()),
+        ],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    view.selected = None;
+    view.select_next_sibling();
+    assert_eq!(view.selected, Some(0));
+
+    view.select_index_at(1);
+    view.select_next_sibling();
+    assert_eq!(view.selected, Some(3));
+
+    view.select_next_sibling();
+    assert_eq!(view.selected, Some(3));
+
+    view.select_index_at(0);
+    view.select_next_sibling();
+    assert_eq!(view.selected, Some(4));
+  }
+
+  #[test]
+  fn select_root_next_jumps_out_of_deep_replies() {
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![
+          make_comment(1, vec![make_comment(2, vec![make_comment(3, vec![])])]),
+          make_comment(4, Vec::new()),
+        ],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    view.selected = None;
+    view.select_root_next();
+    assert_eq!(view.selected, Some(0));
+
+    view.select_index_at(2);
+    view.select_root_next();
+    assert_eq!(view.selected, Some(3));
+
+    view.select_root_next();
+    assert_eq!(view.selected, Some(3));
+  }
+
+  #[test]
+  fn matches_expand_ancestors_and_wrap_around() {
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![
+          make_comment(1, vec![make_comment(2, vec![make_comment(3, vec![])])]),
+          make_comment(4, Vec::new()),
+        ],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    assert!(!view.next_match());
+
+    view.entries[2].body = "Rust is Great".to_string();
+    view.entries[3].author = Some("RUSTACEAN".to_string());
+    view.collapse_all();
+
+    view.set_search_query("rust".to_string());
+
+    assert!(view.next_match());
+    assert_eq!(view.selected, Some(2));
+    assert_eq!(view.visible_indexes(), vec![0, 1, 2, 3]);
+
+    assert!(view.next_match());
+    assert_eq!(view.selected, Some(3));
+
+    assert!(view.next_match());
+    assert_eq!(view.selected, Some(2));
+
+    assert!(view.prev_match());
+    assert_eq!(view.selected, Some(3));
+
+    view.set_search_query("missing".to_string());
+
+    assert!(!view.next_match());
+    assert_eq!(view.selected, Some(3));
+  }
+
+  #[test]
+  fn horizontal_offset_is_clamped_and_reset_by_moving() {
+    let mut view = make_view(None);
+
+    view.entries[0].body = "short\na much longer line".to_string();
+
+    view.scroll_horizontally(8);
+    assert_eq!(view.horizontal_offset(), 8);
+
+    view.scroll_horizontally(100);
+    assert_eq!(view.horizontal_offset(), 17);
+
+    view.scroll_horizontally(-20);
+    assert_eq!(view.horizontal_offset(), 0);
+
+    view.scroll_horizontally(8);
+    view.select_next();
+    assert_eq!(view.horizontal_offset(), 0);
+
+    view.sync_horizontal_offset();
+    view.select_previous();
+    assert_eq!(view.horizontal_offset(), 0);
+  }
+
+  #[test]
+  fn select_next_unread_skips_visited_and_hidden_comments() {
+    let mut view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![
+          make_comment(1, vec![make_comment(2, vec![make_comment(3, vec![])])]),
+          make_comment(4, Vec::new()),
+        ],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    view.select_next();
+    view.mark_visited();
+    view.select_previous();
+    view.entries[1].expanded = false;
+
+    assert!(view.select_next_unread());
+    assert_eq!(view.selected, Some(3));
+    assert_eq!(view.visible_indexes(), vec![0, 1, 3]);
+
+    assert!(!view.select_next_unread());
+    assert_eq!(view.selected, Some(3));
+
+    view.toggle_flat();
+
+    assert_eq!(view.visible_indexes(), vec![3, 2, 1, 0]);
+
+    view.selected = Some(3);
+
+    assert!(view.select_next_unread());
+    assert_eq!(view.selected, Some(2));
+  }
+
+  #[test]
+  fn guides_trace_ancestors_with_later_siblings() {
+    let view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![make_comment(
+          1,
+          vec![
+            make_comment(2, vec![make_comment(3, Vec::new())]),
+            make_comment(4, Vec::new()),
+          ],
+        )],
+        score: None,
+        title: None,
+      },
+      ROOT_COMMENT_LINK.to_string(),
+    );
+
+    assert!(view.guides(0).is_empty());
+    assert_eq!(view.guides(1), [Guide::Branch]);
+    assert_eq!(view.guides(2), [Guide::Pipe, Guide::Last]);
+    assert_eq!(view.guides(3), [Guide::Last]);
   }
 
   #[test]
@@ -0,0 +1,281 @@
+use super::*;
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub(crate) struct Config {
+  #[serde(deserialize_with = "deserialize_colors")]
+  accents: BTreeMap<String, Color>,
+  pub(crate) auto_collapse_dead: bool,
+  #[serde(skip)]
+  backup: Option<PathBuf>,
+  cache_ttl: u64,
+  cache_ttls: BTreeMap<String, u64>,
+  comment_indent: usize,
+  comment_timeout: u64,
+  pub(crate) defer_bookmarks: bool,
+  #[serde(skip)]
+  keep_tracking: bool,
+  #[serde(skip)]
+  keymap: Keymap,
+  max_depth: usize,
+  max_requests: usize,
+  openers: Vec<Opener>,
+  pub(crate) scroll_comments: bool,
+  separator: Separator,
+  summary_format: String,
+  #[serde(skip)]
+  theme: Theme,
+  #[serde(rename = "theme")]
+  theme_name: Option<String>,
+  tree_guides: TreeGuides,
+  pub(crate) wrap_comments: bool,
+}
+
+impl Default for Config {
+  fn default() -> Self {
+    Self {
+      accents: BTreeMap::new(),
+      auto_collapse_dead: false,
+      backup: None,
+      cache_ttl: Cache::DEFAULT_ITEM_TTL.as_secs(),
+      cache_ttls: BTreeMap::new(),
+      comment_indent: 2,
+      comment_timeout: 10,
+      defer_bookmarks: false,
+      keep_tracking: false,
+      keymap: Keymap::default(),
+      max_depth: CommentView::DEFAULT_MAX_DEPTH,
+      max_requests: Client::DEFAULT_MAX_REQUESTS,
+      openers: Opener::DEFAULT_ORDER.to_vec(),
+      scroll_comments: false,
+      separator: Separator::default(),
+      summary_format: Self::DEFAULT_SUMMARY_FORMAT.to_string(),
+      theme: Theme::default(),
+      theme_name: None,
+      tree_guides: TreeGuides::default(),
+      wrap_comments: true,
+    }
+  }
+}
+
+impl Config {
+  pub(crate) const DEFAULT_SUMMARY_FORMAT: &str = "{title} — {points} — {url}";
+
+  pub(crate) fn accent(&self, label: &str) -> Color {
+    self
+      .accents
+      .get(label)
+      .copied()
+      .unwrap_or(self.theme.tab_active)
+  }
+
+  fn apply_theme(&mut self) -> Result {
+    if let Some(name) = &self.theme_name {
+      self.theme = name.parse().context("invalid `theme` in config file")?;
+    }
+
+    if let Ok(name) = env::var("HN_THEME") {
+      self.theme = name.parse().context("invalid `HN_THEME`")?;
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn backup(&self) -> Option<&Path> {
+    self.backup.as_deref()
+  }
+
+  pub(crate) fn cache_ttl(&self) -> Option<Duration> {
+    (self.cache_ttl > 0).then(|| Duration::from_secs(self.cache_ttl))
+  }
+
+  pub(crate) fn category_cache_ttls(&self) -> BTreeMap<String, Duration> {
+    self
+      .cache_ttls
+      .iter()
+      .map(|(label, seconds)| (label.clone(), Duration::from_secs(*seconds)))
+      .collect()
+  }
+
+  pub(crate) fn comment_indent(&self) -> usize {
+    self.comment_indent.max(1)
+  }
+
+  pub(crate) fn comment_timeout(&self) -> Option<Duration> {
+    (self.comment_timeout > 0)
+      .then(|| Duration::from_secs(self.comment_timeout))
+  }
+
+  pub(crate) fn keep_tracking(&self) -> bool {
+    self.keep_tracking
+  }
+
+  pub(crate) fn keymap(&self) -> &Keymap {
+    &self.keymap
+  }
+
+  pub(crate) fn load() -> Result<Self> {
+    let (mut config, backup) =
+      read_toml_file::<Self>(&config_path("HN_CONFIG_FILE", "config.toml")?)?;
+
+    config.backup = backup;
+
+    config.apply_theme()?;
+
+    config.keep_tracking =
+      env::var("HN_KEEP_TRACKING").is_ok_and(|value| value == "1");
+
+    config.keymap = Keymap::load()?;
+
+    Ok(config)
+  }
+
+  pub(crate) fn max_depth(&self) -> usize {
+    self.max_depth.max(1)
+  }
+
+  pub(crate) fn max_requests(&self) -> usize {
+    self.max_requests.max(1)
+  }
+
+  pub(crate) fn openers(&self) -> &[Opener] {
+    &self.openers
+  }
+
+  pub(crate) fn separator(&self) -> &Separator {
+    &self.separator
+  }
+
+  pub(crate) fn summary_format(&self) -> &str {
+    &self.summary_format
+  }
+
+  pub(crate) fn theme(&self) -> &Theme {
+    &self.theme
+  }
+
+  pub(crate) fn tree_guides(&self) -> TreeGuides {
+    self.tree_guides
+  }
+}
+
+fn deserialize_colors<'de, D>(
+  deserializer: D,
+) -> Result<BTreeMap<String, Color>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  BTreeMap::<String, String>::deserialize(deserializer)?
+    .into_iter()
+    .map(|(label, name)| {
+      name
+        .parse::<Color>()
+        .map(|color| (label, color))
+        .map_err(|_| de::Error::custom(format!("invalid color `{name}`")))
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn accents_default_to_cyan() {
+    assert_eq!(Config::default().accent("top"), Color::Cyan);
+  }
+
+  #[test]
+  fn accents_are_parsed_per_category() {
+    let config = toml::from_str::<Config>(
+      "[accents]\nask = \"yellow\"\nshow = \"#00ff00\"",
+    )
+    .unwrap();
+
+    assert_eq!(config.accent("ask"), Color::Yellow);
+    assert_eq!(config.accent("show"), Color::Rgb(0, 255, 0));
+    assert_eq!(config.accent("top"), Color::Cyan);
+  }
+
+  #[test]
+  fn theme_sets_the_default_accent() {
+    let mut config = toml::from_str::<Config>("theme = \"light\"").unwrap();
+
+    config.apply_theme().unwrap();
+
+    assert_eq!(config.theme(), &Theme::LIGHT);
+    assert_eq!(config.accent("top"), Color::Blue);
+
+    let mut config = toml::from_str::<Config>("theme = \"sepia\"").unwrap();
+
+    assert_eq!(
+      format!("{:#}", config.apply_theme().unwrap_err()),
+      "invalid `theme` in config file: unknown theme `sepia`, expected `dark` or `light`"
+    );
+  }
+
+  #[test]
+  fn invalid_accent_colors_are_rejected() {
+    assert!(toml::from_str::<Config>("[accents]\nask = \"nope\"").is_err());
+  }
+
+  #[test]
+  fn missing_fields_fall_back_to_defaults() {
+    let config = toml::from_str::<Config>("wrap_comments = false").unwrap();
+
+    assert_eq!(config.comment_indent(), 2);
+    assert!(!config.wrap_comments);
+  }
+
+  #[test]
+  fn openers_default_to_browser_command_then_clipboard() {
+    assert_eq!(
+      Config::default().openers(),
+      [Opener::Browser, Opener::Command, Opener::Clipboard]
+    );
+  }
+
+  #[test]
+  fn openers_can_be_reordered() {
+    let config =
+      toml::from_str::<Config>("openers = [\"command\", \"clipboard\"]")
+        .unwrap();
+
+    assert_eq!(config.openers(), [Opener::Command, Opener::Clipboard]);
+  }
+
+  #[test]
+  fn unknown_openers_are_rejected() {
+    assert!(
+      toml::from_str::<Config>("openers = [\"carrier-pigeon\"]").is_err()
+    );
+  }
+
+  #[test]
+  fn zero_comment_timeout_disables_deadline() {
+    assert_eq!(
+      Config::default().comment_timeout(),
+      Some(Duration::from_secs(10))
+    );
+
+    let config = toml::from_str::<Config>("comment_timeout = 0").unwrap();
+
+    assert_eq!(config.comment_timeout(), None);
+  }
+
+  #[test]
+  fn tree_guides_default_to_unicode_and_accept_ascii() {
+    assert_eq!(Config::default().tree_guides(), TreeGuides::Unicode);
+
+    let config = toml::from_str::<Config>("tree_guides = \"ascii\"").unwrap();
+
+    assert_eq!(config.tree_guides(), TreeGuides::Ascii);
+  }
+
+  #[test]
+  fn comment_indent_is_at_least_one() {
+    let config = toml::from_str::<Config>("comment_indent = 0").unwrap();
+
+    assert_eq!(config.comment_indent(), 1);
+  }
+}
@@ -0,0 +1,68 @@
+use super::*;
+
+#[derive(
+  Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Contrast {
+  Bright,
+  #[default]
+  Dim,
+  Normal,
+}
+
+impl Display for Contrast {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      Self::Bright => "bright",
+      Self::Dim => "dim",
+      Self::Normal => "normal",
+    })
+  }
+}
+
+impl Contrast {
+  pub(crate) fn body_style(self, theme: &Theme) -> Style {
+    Style::default().fg(match self {
+      Self::Bright => theme.title,
+      Self::Dim => theme.detail,
+      Self::Normal => theme.body,
+    })
+  }
+
+  pub(crate) fn next(self) -> Self {
+    match self {
+      Self::Bright => Self::Dim,
+      Self::Dim => Self::Normal,
+      Self::Normal => Self::Bright,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn levels_cycle_from_dim_to_bright() {
+    let mut contrast = Contrast::default();
+
+    let mut styles = Vec::new();
+
+    for _ in 0..3 {
+      styles.push((contrast, contrast.body_style(&Theme::default()).fg));
+      contrast = contrast.next();
+    }
+
+    assert_eq!(
+      styles,
+      [
+        (Contrast::Dim, Some(Color::DarkGray)),
+        (Contrast::Normal, Some(Color::Gray)),
+        (Contrast::Bright, Some(Color::White)),
+      ]
+    );
+
+    assert_eq!(contrast, Contrast::Dim);
+  }
+}
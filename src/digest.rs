@@ -0,0 +1,180 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Digest {
+  #[arg(
+    long,
+    default_value_t = 2,
+    help = "Number of top comments to include per story"
+  )]
+  comments_per_story: usize,
+  #[arg(
+    long,
+    default_value_t = 10,
+    help = "Number of front page stories to include"
+  )]
+  stories: usize,
+}
+
+impl Digest {
+  const COMMENT_LENGTH: usize = 280;
+
+  fn render(
+    stories: &[(ListEntry, Result<Vec<Comment>>)],
+    keep_tracking: bool,
+  ) -> String {
+    let mut blocks = vec!["# Hacker News digest".to_string()];
+
+    for (index, (entry, comments)) in stories.iter().enumerate() {
+      blocks.push(format!(
+        "## {}. {}",
+        index + 1,
+        markdown_link(&entry.title, &entry.resolved_url(keep_tracking))
+      ));
+
+      let mut details = entry.detail.clone().into_iter().collect::<Vec<_>>();
+
+      details.push(markdown_link(
+        "discussion",
+        &format!("https://news.ycombinator.com/item?id={}", entry.id),
+      ));
+
+      blocks.push(details.join(DETAIL_SEPARATOR));
+
+      match comments {
+        Ok(comments) => {
+          for comment in comments {
+            if comment.dead || comment.deleted {
+              continue;
+            }
+
+            let Some(text) = comment.text.as_deref() else {
+              continue;
+            };
+
+            let author = comment.author.as_deref().unwrap_or("unknown");
+
+            blocks.push(markdown_quote(&format!(
+              "**{author}**: {}",
+              truncate_words(text, Self::COMMENT_LENGTH)
+            )));
+          }
+        }
+        Err(error) => {
+          blocks.push(format!("_Could not load comments: {error}_"));
+        }
+      }
+    }
+
+    format!("{}\n", blocks.join("\n\n"))
+  }
+
+  pub(crate) async fn run(
+    self,
+    client: &Client,
+    keep_tracking: bool,
+  ) -> Result {
+    let top = Category::all()[0];
+
+    let entries = client
+      .fetch_category_items(top, 0, self.stories)
+      .await
+      .context("failed to load front page stories")?;
+
+    let stories = stream::iter(entries.into_iter().map(|entry| {
+      let client = client.clone();
+
+      let count = self.comments_per_story;
+
+      async move {
+        let comments = if count == 0 {
+          Ok(Vec::new())
+        } else {
+          match entry.id.parse::<u64>() {
+            Ok(id) => client.fetch_top_comments(id, count).await,
+            Err(error) => Err(error.into()),
+          }
+        };
+
+        (entry, comments)
+      }
+    }))
+    .buffered(4)
+    .collect::<Vec<_>>()
+    .await;
+
+    print!("{}", Self::render(&stories, keep_tracking));
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn entry(id: &str, title: &str) -> ListEntry {
+    ListEntry {
+      comments: None,
+      detail: Some("10 points by alice".to_string()),
+      id: id.to_string(),
+      score: None,
+      tags: Vec::new(),
+      time: None,
+      title: title.to_string(),
+      url: Some(format!("https://example.com/{id}")),
+    }
+  }
+
+  fn comment(author: &str, text: &str) -> Comment {
+    Comment {
+      author: Some(author.to_string()),
+      children: Vec::new(),
+      dead: false,
+      deleted: false,
+      html: None,
+      id: 1,
+      links: Vec::new(),
+      loaded: true,
+      text: Some(text.to_string()),
+      time: None,
+    }
+  }
+
+  #[test]
+  fn render_includes_stories_and_quoted_comments() {
+    let output = Digest::render(
+      &[(
+        entry("1", "First"),
+        Ok(vec![comment("bob", "Great post\nSecond line")]),
+      )],
+      false,
+    );
+
+    assert_eq!(
+      output,
+      "# Hacker News digest\n\
+       \n\
+       ## 1. [First](https://example.com/1)\n\
+       \n\
+       10 points by alice • [discussion](https://news.ycombinator.com/item?id=1)\n\
+       \n\
+       > **bob**: Great post\n\
+       > Second line\n"
+    );
+  }
+
+  #[test]
+  fn render_notes_stories_whose_comments_failed_to_load() {
+    let output = Digest::render(
+      &[
+        (entry("1", "First"), Err(anyhow!("timed out"))),
+        (entry("2", "Second"), Ok(Vec::new())),
+      ],
+      false,
+    );
+
+    assert!(output.contains("_Could not load comments: timed out_"));
+    assert!(output.contains("## 2. [Second](https://example.com/2)"));
+  }
+}
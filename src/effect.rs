@@ -1,21 +1,47 @@
-use super::category::Category;
+use {
+  super::{category::Category, search_sort::SearchSort},
+  std::time::Duration,
+};
 
 #[derive(Clone)]
 pub(crate) enum Effect {
+  CopyToClipboard {
+    label: &'static str,
+    text: String,
+  },
+  FetchCommentChildren {
+    item_id: u64,
+    request_id: u64,
+  },
   FetchComments {
+    fresh: bool,
+    item_id: u64,
+    request_id: u64,
+    timeout: Option<Duration>,
+  },
+  FetchNewerTabItems {
+    category: Category,
+    request_id: u64,
+  },
+  FetchPreview {
     item_id: u64,
     request_id: u64,
   },
   FetchSearchResults {
     query: String,
     request_id: u64,
+    sort: SearchSort,
   },
   FetchTabItems {
-    tab_index: usize,
     category: Category,
+    fresh: bool,
     offset: usize,
+    request_id: u64,
   },
   OpenUrl {
     url: String,
   },
+  RunPager {
+    text: String,
+  },
 }
@@ -1,16 +1,28 @@
 use super::*;
 
 pub(crate) enum Event {
+  CommentChildren {
+    request_id: u64,
+    result: Result<Vec<Comment>>,
+  },
   Comments {
     request_id: u64,
     result: Result<CommentThread>,
   },
+  NewerTabItems {
+    request_id: u64,
+    result: Result<Vec<ListEntry>>,
+  },
+  Preview {
+    request_id: u64,
+    result: Result<Vec<Comment>>,
+  },
   SearchResults {
     request_id: u64,
     result: Result<(Vec<ListEntry>, bool)>,
   },
   TabItems {
-    tab_index: usize,
+    request_id: u64,
     result: Result<Vec<ListEntry>>,
   },
 }
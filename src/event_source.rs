@@ -0,0 +1,7 @@
+use super::*;
+
+pub(crate) trait EventSource {
+  fn poll(&mut self, timeout: Duration) -> Result<bool>;
+
+  fn read(&mut self) -> Result<CrosstermEvent>;
+}
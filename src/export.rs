@@ -0,0 +1,129 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Export {
+  #[arg(
+    long,
+    value_enum,
+    default_value_t = ExportFormat::Markdown,
+    help = "Output format for the exported bookmarks"
+  )]
+  format: ExportFormat,
+}
+
+impl Export {
+  pub(crate) fn render(
+    format: ExportFormat,
+    entries: &[ListEntry],
+    keep_tracking: bool,
+  ) -> Result<String> {
+    match format {
+      ExportFormat::Json => {
+        Ok(format!("{}\n", serde_json::to_string_pretty(entries)?))
+      }
+      ExportFormat::Markdown => Ok(
+        entries
+          .iter()
+          .map(|entry| {
+            let link =
+              markdown_link(&entry.title, &entry.resolved_url(keep_tracking));
+
+            match &entry.detail {
+              Some(detail) => format!("- {link} — {detail}\n"),
+              None => format!("- {link}\n"),
+            }
+          })
+          .collect(),
+      ),
+    }
+  }
+
+  pub(crate) fn run(self, keep_tracking: bool) -> Result {
+    let bookmarks = Bookmarks::load().context("could not load bookmarks")?;
+
+    print!(
+      "{}",
+      Self::render(self.format, &bookmarks.entries_vec(), keep_tracking)?
+    );
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn entries() -> Vec<ListEntry> {
+    vec![
+      ListEntry {
+        comments: None,
+        detail: Some("10 points by alice".to_string()),
+        id: "1".to_string(),
+        score: Some(10),
+        tags: Vec::new(),
+        time: None,
+        title: "First".to_string(),
+        url: Some("https://example.com/1".to_string()),
+      },
+      ListEntry {
+        comments: None,
+        detail: None,
+        id: "2".to_string(),
+        score: None,
+        tags: vec!["rust".to_string()],
+        time: None,
+        title: "Ask HN: Second".to_string(),
+        url: None,
+      },
+    ]
+  }
+
+  #[test]
+  fn markdown_lists_links_with_details() {
+    assert_eq!(
+      Export::render(ExportFormat::Markdown, &entries(), false).unwrap(),
+      "- [First](https://example.com/1) — 10 points by alice\n\
+       - [Ask HN: Second](https://news.ycombinator.com/item?id=2)\n"
+    );
+  }
+
+  #[test]
+  fn json_round_trips_entries() {
+    let output = Export::render(ExportFormat::Json, &entries(), false).unwrap();
+
+    let parsed = serde_json::from_str::<Vec<ListEntry>>(&output).unwrap();
+
+    assert_eq!(
+      parsed
+        .iter()
+        .map(|entry| entry.id.as_str())
+        .collect::<Vec<_>>(),
+      ["1", "2"]
+    );
+
+    assert_eq!(parsed[1].tags, ["rust"]);
+  }
+
+  #[test]
+  fn format_defaults_to_markdown() {
+    let arguments = Arguments::try_parse_from(["hn", "export"]).unwrap();
+
+    assert!(matches!(
+      arguments.subcommand,
+      Some(Subcommand::Print(PrintSubcommand::Export(Export {
+        format: ExportFormat::Markdown
+      })))
+    ));
+
+    let arguments =
+      Arguments::try_parse_from(["hn", "export", "--format", "json"]).unwrap();
+
+    assert!(matches!(
+      arguments.subcommand,
+      Some(Subcommand::Print(PrintSubcommand::Export(Export {
+        format: ExportFormat::Json
+      })))
+    ));
+  }
+}
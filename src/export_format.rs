@@ -0,0 +1,6 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, clap::ValueEnum)]
+pub(crate) enum ExportFormat {
+  Json,
+  #[default]
+  Markdown,
+}
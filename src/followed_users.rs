@@ -0,0 +1,124 @@
+use super::*;
+
+#[derive(Debug, Default)]
+pub(crate) struct FollowedUsers {
+  backup: Option<PathBuf>,
+  path: Option<PathBuf>,
+  users: HashSet<String>,
+}
+
+impl FollowedUsers {
+  pub(crate) fn backup(&self) -> Option<&Path> {
+    self.backup.as_deref()
+  }
+
+  pub(crate) fn contains(&self, user: &str) -> bool {
+    self.users.contains(user)
+  }
+
+  pub(crate) fn follows(&self, entry: &CommentEntry) -> bool {
+    entry
+      .author
+      .as_deref()
+      .is_some_and(|author| self.contains(author))
+  }
+
+  pub(crate) fn load() -> Self {
+    let Ok(path) = config_path("HN_FOLLOWED_FILE", "followed.json") else {
+      return Self::default();
+    };
+
+    Self::load_from(path)
+  }
+
+  fn load_from(path: PathBuf) -> Self {
+    let (users, backup) = read_json_file::<HashSet<String>>(&path)
+      .unwrap_or_else(|_| (HashSet::new(), None));
+
+    Self {
+      backup,
+      path: Some(path),
+      users,
+    }
+  }
+
+  fn persist(&self) -> Result {
+    let Some(path) = &self.path else {
+      return Ok(());
+    };
+
+    ensure_parent_dir(path)?;
+
+    let mut users = self.users.iter().collect::<Vec<&String>>();
+
+    users.sort();
+
+    fs::write(path, serde_json::to_vec_pretty(&users)?)?;
+
+    Ok(())
+  }
+
+  pub(crate) fn toggle(&mut self, user: &str) -> Result<bool> {
+    let followed = if self.users.remove(user) {
+      false
+    } else {
+      self.users.insert(user.to_string())
+    };
+
+    self.persist()?;
+
+    Ok(followed)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn temp_followed_file() -> PathBuf {
+    let unique = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .unwrap()
+      .as_nanos();
+
+    env::temp_dir()
+      .join(format!("hn_followed_test_{unique}"))
+      .join("followed.json")
+  }
+
+  #[test]
+  fn followed_users_round_trip_through_file() {
+    let path = temp_followed_file();
+
+    let mut followed = FollowedUsers::load_from(path.clone());
+
+    assert!(!followed.contains("pg"));
+
+    assert!(followed.toggle("pg").unwrap());
+    assert!(followed.toggle("dang").unwrap());
+    assert!(!followed.toggle("dang").unwrap());
+
+    let reloaded = FollowedUsers::load_from(path.clone());
+
+    assert!(reloaded.contains("pg"));
+    assert!(!reloaded.contains("dang"));
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+  }
+
+  #[test]
+  fn corrupt_file_is_backed_up_and_ignored() {
+    let path = temp_followed_file();
+
+    ensure_parent_dir(&path).unwrap();
+
+    fs::write(&path, "not json").unwrap();
+
+    let followed = FollowedUsers::load_from(path.clone());
+
+    assert!(!followed.contains("pg"));
+    assert!(followed.backup().is_some());
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+  }
+}
@@ -0,0 +1,16 @@
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Guide {
+  Blank,
+  Branch,
+  Last,
+  Pipe,
+}
+
+impl Guide {
+  pub(crate) fn continuation(self) -> Self {
+    match self {
+      Self::Blank | Self::Last => Self::Blank,
+      Self::Branch | Self::Pipe => Self::Pipe,
+    }
+  }
+}
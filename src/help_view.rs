@@ -1,49 +1,79 @@
 use super::*;
 
 pub(crate) struct HelpView {
+  area: Rect,
   message_backup: Option<String>,
+  scroll: u16,
+  text: String,
   visible: bool,
 }
 
 impl HelpView {
-  pub(crate) fn draw(&self, frame: &mut Frame) {
+  fn contextual_text(mode: &Mode, keymap: &Keymap) -> String {
+    let headings = mode.help_headings();
+
+    let text = keymap.help_text();
+
+    let (mut sections, rest): (Vec<&str>, Vec<&str>) =
+      text.split("\n\n").partition(|section| {
+        headings.iter().any(|heading| section.starts_with(heading))
+      });
+
+    sections.extend(rest);
+
+    sections.join("\n\n")
+  }
+
+  pub(crate) fn draw(&self, frame: &mut Frame, theme: &Theme) {
     if !self.visible {
       return;
     }
 
-    let area = Self::help_area(frame.area());
+    let area = Self::help_area(frame.area(), &self.text);
 
     frame.render_widget(Clear, area);
 
-    let help = Paragraph::new(HELP_TEXT)
-      .block(Block::default().title(HELP_TITLE).borders(Borders::ALL))
-      .wrap(Wrap { trim: true });
+    let help = self
+      .paragraph()
+      .block(
+        Block::default()
+          .title(HELP_TITLE)
+          .borders(Borders::ALL)
+          .border_style(Style::default().fg(theme.detail)),
+      )
+      .style(Style::default().fg(theme.title))
+      .scroll((self.scroll.min(self.max_scroll(area)), 0));
 
     frame.render_widget(help, area);
   }
 
-  pub(crate) fn handle_key(key: KeyEvent) -> Command {
-    match key.code {
-      KeyCode::Char('?') | KeyCode::Esc => Command::HideHelp,
-      KeyCode::Char('q' | 'Q') => Command::Quit,
+  pub(crate) fn handle_key(key: KeyEvent, keymap: &Keymap) -> Command {
+    if key.code == KeyCode::Esc {
+      return Command::HideHelp;
+    }
+
+    match keymap.list_command(key) {
+      Command::ShowHelp => Command::HideHelp,
+      Command::Quit => Command::Quit,
+      Command::SelectNext => Command::ScrollHelp(1),
+      Command::SelectPrevious => Command::ScrollHelp(-1),
+      Command::ScrollHelp(delta) => Command::ScrollHelp(delta),
       _ => Command::None,
     }
   }
 
-  fn help_area(area: Rect) -> Rect {
+  fn help_area(area: Rect, text: &str) -> Rect {
     fn saturating_usize_to_u16(value: usize) -> u16 {
       u16::try_from(value).unwrap_or(u16::MAX)
     }
 
     let (line_count, max_line_width) =
-      HELP_TEXT
-        .lines()
-        .fold((0usize, 0usize), |(count, width), line| {
-          let updated_count = count.saturating_add(1);
-          let line_width = line.chars().count();
+      text.lines().fold((0usize, 0usize), |(count, width), line| {
+        let updated_count = count.saturating_add(1);
+        let line_width = line.chars().count();
 
-          (updated_count, width.max(line_width))
-        });
+        (updated_count, width.max(line_width))
+      });
 
     let desired_width =
       saturating_usize_to_u16(max_line_width.saturating_add(2)).max(1);
@@ -63,15 +93,16 @@ impl HelpView {
     Rect::new(x, y, width, height)
   }
 
+  fn help_rect(&self) -> Rect {
+    Self::help_area(self.area, &self.text)
+  }
+
   pub(crate) fn hide(&mut self, message: &mut String) {
     if !self.visible {
       return;
     }
 
-    *message = self
-      .message_backup
-      .take()
-      .unwrap_or_else(|| LIST_STATUS.into());
+    *message = self.message_backup.take().unwrap_or_default();
 
     self.visible = false;
   }
@@ -80,22 +111,176 @@ impl HelpView {
     self.visible
   }
 
+  fn max_scroll(&self, area: Rect) -> u16 {
+    let line_count = self.paragraph().line_count(area.width.saturating_sub(2));
+
+    u16::try_from(line_count)
+      .unwrap_or(u16::MAX)
+      .saturating_sub(area.height.saturating_sub(2))
+  }
+
   pub(crate) fn new() -> Self {
     Self {
+      area: Rect::default(),
       message_backup: None,
+      scroll: 0,
+      text: String::new(),
       visible: false,
     }
   }
 
-  pub(crate) fn show(&mut self, message: &mut String) {
+  fn paragraph(&self) -> Paragraph<'_> {
+    Paragraph::new(self.text.as_str()).wrap(Wrap { trim: true })
+  }
+
+  pub(crate) fn resize(&mut self, area: Rect) {
+    self.area = area;
+    self.scroll = self.scroll.min(self.max_scroll(self.help_rect()));
+  }
+
+  pub(crate) fn scroll_by(&mut self, delta: i16) {
+    self.scroll = self
+      .scroll
+      .saturating_add_signed(delta)
+      .min(self.max_scroll(self.help_rect()));
+  }
+
+  pub(crate) fn show(
+    &mut self,
+    message: &mut String,
+    mode: &Mode,
+    keymap: &Keymap,
+  ) {
     if self.visible {
       return;
     }
 
+    self.text = Self::contextual_text(mode, keymap);
+
+    self.scroll = 0;
+
     self.message_backup = Some(message.clone());
 
-    *message = HELP_STATUS.into();
+    *message = help_status(keymap);
 
     self.visible = true;
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use {super::*, ratatui::backend::TestBackend};
+
+  fn headings(text: &str) -> Vec<&str> {
+    text
+      .split("\n\n")
+      .filter_map(|section| section.lines().next())
+      .collect()
+  }
+
+  #[test]
+  fn list_mode_help_lists_navigation_and_actions_first() {
+    let text = HelpView::contextual_text(
+      &Mode::List(ListView::new(Vec::new())),
+      &Keymap::default(),
+    );
+
+    assert_eq!(headings(&text), ["Navigation:", "Actions:", "Comments:"]);
+  }
+
+  #[test]
+  fn comments_mode_help_lists_comment_bindings_first() {
+    let text = HelpView::contextual_text(
+      &Mode::Comments(Box::new(CommentView::new(
+        CommentThread {
+          body: None,
+          body_links: Vec::new(),
+          descendants: None,
+          focus: None,
+          id: 1,
+          op_author: None,
+          partial: false,
+          roots: Vec::new(),
+          score: None,
+          title: None,
+        },
+        "https://news.ycombinator.com/item?id=1".to_string(),
+      ))),
+      &Keymap::default(),
+    );
+
+    assert_eq!(headings(&text), ["Comments:", "Navigation:", "Actions:"]);
+    assert!(text.lines().any(|line| {
+      line.starts_with("  esc ") && line.ends_with("return to the story list")
+    }));
+  }
+
+  #[test]
+  fn scrolling_is_clamped_to_the_last_line_and_reset_on_show() {
+    let mode = Mode::List(ListView::new(Vec::new()));
+
+    let keymap = Keymap::default();
+
+    let mut help = HelpView::new();
+
+    let mut message = String::new();
+
+    help.show(&mut message, &mode, &keymap);
+    help.resize(Rect::new(0, 0, 100, 12));
+
+    let line_count = u16::try_from(help.text.lines().count()).unwrap();
+
+    help.scroll_by(i16::MAX);
+
+    assert_eq!(help.scroll, line_count - 8);
+
+    help.scroll_by(-1);
+
+    assert_eq!(help.scroll, line_count - 9);
+
+    help.hide(&mut message);
+    help.show(&mut message, &mode, &keymap);
+
+    assert_eq!(help.scroll, 0);
+  }
+
+  #[test]
+  fn scrolling_reaches_the_last_wrapped_line_on_narrow_terminals() {
+    let mut help = HelpView::new();
+
+    help.show(
+      &mut String::new(),
+      &Mode::List(ListView::new(Vec::new())),
+      &Keymap::default(),
+    );
+    help.resize(Rect::new(0, 0, 30, 12));
+    help.scroll_by(i16::MAX);
+
+    assert!(
+      usize::from(help.scroll) + 8 > help.text.lines().count(),
+      "wrapped lines should extend the scroll range"
+    );
+
+    let mut terminal = Terminal::new(TestBackend::new(30, 12)).unwrap();
+
+    terminal
+      .draw(|frame| help.draw(frame, &Theme::default()))
+      .unwrap();
+
+    let rendered = terminal
+      .backend()
+      .buffer()
+      .content()
+      .iter()
+      .map(ratatui::buffer::Cell::symbol)
+      .collect::<String>();
+
+    let last_word = help.text.split_whitespace().last().unwrap();
+
+    assert!(rendered.contains(last_word));
+
+    help.resize(Rect::new(0, 0, 100, 12));
+
+    assert!(usize::from(help.scroll) + 8 <= help.text.lines().count());
+  }
+}
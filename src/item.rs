@@ -5,10 +5,12 @@ pub(crate) struct Item {
   pub(crate) by: Option<String>,
   pub(crate) dead: Option<bool>,
   pub(crate) deleted: Option<bool>,
+  pub(crate) descendants: Option<u64>,
   pub(crate) id: u64,
   pub(crate) kids: Option<Vec<u64>>,
+  pub(crate) score: Option<u64>,
   pub(crate) text: Option<String>,
-  #[allow(dead_code)]
+  pub(crate) time: Option<u64>,
   pub(crate) title: Option<String>,
   pub(crate) r#type: Option<String>,
   #[allow(dead_code)]
@@ -0,0 +1,13 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct ItemLink {
+  #[arg(
+    long,
+    value_name = "ID",
+    help = "Comment to select once the thread has loaded"
+  )]
+  pub(crate) comment: Option<u64>,
+  #[arg(help = "Story or comment to open")]
+  pub(crate) id: u64,
+}
@@ -0,0 +1,202 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct KeyBinding {
+  code: KeyCode,
+  modifiers: KeyModifiers,
+}
+
+impl Display for KeyBinding {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    if self.modifiers.contains(KeyModifiers::CONTROL) {
+      write!(f, "ctrl+")?;
+    }
+
+    if self.modifiers.contains(KeyModifiers::ALT) {
+      write!(f, "alt+")?;
+    }
+
+    if self.modifiers.contains(KeyModifiers::SHIFT) {
+      write!(f, "shift+")?;
+    }
+
+    match self.code {
+      KeyCode::BackTab => write!(f, "shift+tab"),
+      KeyCode::Backspace => write!(f, "backspace"),
+      KeyCode::Char(' ') => write!(f, "space"),
+      KeyCode::Char(ch) => write!(f, "{ch}"),
+      KeyCode::Delete => write!(f, "delete"),
+      KeyCode::Down => write!(f, "↓"),
+      KeyCode::End => write!(f, "end"),
+      KeyCode::Enter => write!(f, "enter"),
+      KeyCode::Esc => write!(f, "esc"),
+      KeyCode::F(number) => write!(f, "f{number}"),
+      KeyCode::Home => write!(f, "home"),
+      KeyCode::Left => write!(f, "←"),
+      KeyCode::PageDown => write!(f, "pg↓"),
+      KeyCode::PageUp => write!(f, "pg↑"),
+      KeyCode::Right => write!(f, "→"),
+      KeyCode::Tab => write!(f, "tab"),
+      KeyCode::Up => write!(f, "↑"),
+      code => write!(f, "{code:?}"),
+    }
+  }
+}
+
+impl FromStr for KeyBinding {
+  type Err = anyhow::Error;
+
+  fn from_str(key: &str) -> Result<Self> {
+    let mut modifiers = KeyModifiers::NONE;
+
+    let mut rest = key;
+
+    while let Some((prefix, remainder)) = rest.split_once('+')
+      && !remainder.is_empty()
+    {
+      modifiers |= match prefix.to_lowercase().as_str() {
+        "alt" => KeyModifiers::ALT,
+        "ctrl" | "control" => KeyModifiers::CONTROL,
+        "shift" => KeyModifiers::SHIFT,
+        _ => bail!("unknown modifier `{prefix}` in key `{key}`"),
+      };
+
+      rest = remainder;
+    }
+
+    let name = rest.to_lowercase();
+
+    let code = match name.as_str() {
+      "backtab" => KeyCode::BackTab,
+      "backspace" => KeyCode::Backspace,
+      "delete" | "del" => KeyCode::Delete,
+      "down" | "↓" => KeyCode::Down,
+      "end" => KeyCode::End,
+      "enter" | "return" => KeyCode::Enter,
+      "esc" | "escape" => KeyCode::Esc,
+      "home" => KeyCode::Home,
+      "left" | "←" => KeyCode::Left,
+      "pagedown" | "pgdn" | "pg↓" => KeyCode::PageDown,
+      "pageup" | "pgup" | "pg↑" => KeyCode::PageUp,
+      "right" | "→" => KeyCode::Right,
+      "space" => KeyCode::Char(' '),
+      "tab" => KeyCode::Tab,
+      "up" | "↑" => KeyCode::Up,
+      _ => {
+        let mut chars = rest.chars();
+
+        match (chars.next(), chars.next()) {
+          (Some(ch), None) => KeyCode::Char(ch),
+          _ => name
+            .strip_prefix('f')
+            .and_then(|number| number.parse::<u8>().ok())
+            .filter(|number| (1..=12).contains(number))
+            .map(KeyCode::F)
+            .ok_or_else(|| anyhow!("unknown key `{key}`"))?,
+        }
+      }
+    };
+
+    let code = match code {
+      KeyCode::Char(ch) if modifiers.contains(KeyModifiers::SHIFT) => {
+        KeyCode::Char(ch.to_ascii_uppercase())
+      }
+      KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+        KeyCode::BackTab
+      }
+      code => code,
+    };
+
+    Ok(Self {
+      code,
+      modifiers: modifiers.intersection(Self::significant_modifiers(code)),
+    })
+  }
+}
+
+impl KeyBinding {
+  const MODIFIERS: KeyModifiers =
+    KeyModifiers::CONTROL.union(KeyModifiers::ALT);
+
+  pub(crate) fn matches(self, key: KeyEvent) -> bool {
+    self.code == key.code
+      && self.modifiers
+        == key
+          .modifiers
+          .intersection(Self::significant_modifiers(key.code))
+  }
+
+  fn significant_modifiers(code: KeyCode) -> KeyModifiers {
+    match code {
+      KeyCode::BackTab | KeyCode::Char(_) => Self::MODIFIERS,
+      _ => Self::MODIFIERS.union(KeyModifiers::SHIFT),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(key: &str) -> KeyBinding {
+    key.parse().unwrap()
+  }
+
+  #[test]
+  fn parses_characters_and_modifiers() {
+    assert!(
+      parse("ctrl+n")
+        .matches(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL))
+    );
+
+    assert!(
+      !parse("n")
+        .matches(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL))
+    );
+
+    assert!(
+      parse("shift+g")
+        .matches(KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT))
+    );
+
+    assert!(
+      parse("shift+left")
+        .matches(KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT))
+    );
+
+    assert!(
+      !parse("left").matches(KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT))
+    );
+  }
+
+  #[test]
+  fn parses_named_keys() {
+    assert_eq!(parse("PageDown"), parse("pg↓"));
+    assert_eq!(parse("space"), parse(" "));
+    assert_eq!(parse("f5").to_string(), "f5");
+  }
+
+  #[test]
+  fn display_round_trips() {
+    for key in [
+      "ctrl+d",
+      "←",
+      "pg↑",
+      "enter",
+      "space",
+      "Q",
+      "alt+x",
+      "shift+tab",
+      "shift+→",
+    ] {
+      assert_eq!(parse(key).to_string(), key);
+    }
+  }
+
+  #[test]
+  fn rejects_unknown_keys_and_modifiers() {
+    assert!("hyper+x".parse::<KeyBinding>().is_err());
+    assert!("banana".parse::<KeyBinding>().is_err());
+    assert!("f13".parse::<KeyBinding>().is_err());
+  }
+}
@@ -0,0 +1,796 @@
+use super::*;
+
+type Binding = (KeyBinding, Command);
+
+type Entry = (&'static [&'static str], Command, &'static str);
+
+#[derive(Debug)]
+pub(crate) struct Keymap {
+  backup: Option<PathBuf>,
+  comments: Vec<Binding>,
+  errors: Vec<String>,
+  list: Vec<Binding>,
+}
+
+impl Default for Keymap {
+  fn default() -> Self {
+    Self {
+      backup: None,
+      comments: Self::defaults(&[Self::COMMENTS]),
+      errors: Vec::new(),
+      list: Self::defaults(&[Self::LIST_NAVIGATION, Self::LIST_ACTIONS]),
+    }
+  }
+}
+
+impl Keymap {
+  const COMMENTS: &[Entry] = &[
+    (&["up", "k"], Command::SelectPrevious, "move selection up"),
+    (&["down", "j"], Command::SelectNext, "move selection down"),
+    (&["pagedown", "ctrl+d"], Command::PageDown, "page down"),
+    (&["pageup", "ctrl+u"], Command::PageUp, "page up"),
+    (
+      &["home"],
+      Command::SelectFirst,
+      "jump to first comment (or press g twice)",
+    ),
+    (&["end", "G"], Command::SelectLast, "jump to last comment"),
+    (
+      &["left", "h"],
+      Command::CollapseComment,
+      "collapse or go to parent",
+    ),
+    (
+      &["right", "l"],
+      Command::ExpandComment,
+      "expand or go to first child",
+    ),
+    (
+      &["enter", "space"],
+      Command::ToggleComment,
+      "toggle collapse or expand",
+    ),
+    (
+      &["H"],
+      Command::CollapseSubtree,
+      "collapse the selected comment and all of its replies",
+    ),
+    (
+      &["shift+left"],
+      Command::ScrollLeft,
+      "scroll the selected comment's unwrapped lines left",
+    ),
+    (
+      &["shift+right"],
+      Command::ScrollRight,
+      "scroll the selected comment's unwrapped lines right",
+    ),
+    (&["z"], Command::CollapseAll, "collapse every comment"),
+    (&["Z"], Command::ExpandAll, "expand every comment"),
+    (
+      &["tab"],
+      Command::NextRoot,
+      "expand the next top-level comment, collapsing the others",
+    ),
+    (
+      &["shift+tab"],
+      Command::PreviousRoot,
+      "expand the previous top-level comment, collapsing the others",
+    ),
+    (
+      &["U"],
+      Command::SelectNextUnread,
+      "jump to the next shown comment you haven't visited yet",
+    ),
+    (
+      &["c"],
+      Command::CycleContrast,
+      "cycle comment body contrast between dim, normal and bright",
+    ),
+    (
+      &["}"],
+      Command::SelectNextSibling,
+      "jump to the next sibling comment",
+    ),
+    (
+      &["J"],
+      Command::SelectRootNext,
+      "jump to the next top-level comment",
+    ),
+    (
+      &["o"],
+      Command::OpenCommentLink,
+      "open the selected comment in your browser",
+    ),
+    (
+      &["O"],
+      Command::OpenCommentLinks,
+      "open every link in the selected comment",
+    ),
+    (
+      &["y"],
+      Command::CopyCommentLink,
+      "copy the selected comment's link",
+    ),
+    (
+      &["Y"],
+      Command::CopyCommentText,
+      "copy the selected comment's text, attributed to its author",
+    ),
+    (
+      &["p"],
+      Command::ViewInPager,
+      "read the selected comment in $PAGER",
+    ),
+    (
+      &["w"],
+      Command::ToggleCommentWrap,
+      "toggle wrapping of comment text",
+    ),
+    (
+      &["f"],
+      Command::ToggleFocus,
+      "toggle between the focused comment and the full thread",
+    ),
+    (
+      &["x"],
+      Command::ToggleHideDead,
+      "toggle hiding dead and deleted comments",
+    ),
+    (
+      &["F"],
+      Command::ToggleFlat,
+      "toggle flat mode, showing every comment newest first",
+    ),
+    (
+      &["u"],
+      Command::ToggleFollow,
+      "follow or unfollow the selected comment's author",
+    ),
+    (
+      &["L"],
+      Command::ToggleListing,
+      "toggle list mode, showing each top-level comment as a listing",
+    ),
+    (
+      &["v"],
+      Command::ToggleScroll,
+      "toggle scrolling by line instead of stepping between comments",
+    ),
+    (
+      &["n"],
+      Command::NextMatch,
+      "jump to the next comment matching the search",
+    ),
+    (
+      &["N"],
+      Command::PreviousMatch,
+      "jump to the previous comment matching the search",
+    ),
+    (
+      &["/"],
+      Command::StartSearch,
+      "find text in this thread, or filter listings in list mode",
+    ),
+    (
+      &["b", "B"],
+      Command::ToggleBookmark,
+      "toggle a bookmark for the selected comment",
+    ),
+    (
+      &["r"],
+      Command::LoadFullThread,
+      "load the rest of a partially fetched thread",
+    ),
+    (
+      &["S"],
+      Command::SaveThread,
+      "save this thread to the saved tab for offline reading",
+    ),
+    (
+      &[":", "ctrl+p"],
+      Command::ShowCommandPalette,
+      "search for a command by name and run it",
+    ),
+    (&["esc"], Command::CloseComments, "return to the story list"),
+    (&["q", "Q"], Command::Quit, "quit hn"),
+    (&["?"], Command::ShowHelp, "toggle this help"),
+  ];
+
+  const DEBUG_COMMENTS: &[Entry] = &[(
+    &["D"],
+    Command::DumpComment,
+    "write the selected comment's raw and rendered text to a file",
+  )];
+
+  const LIST_ACTIONS: &[Entry] = &[
+    (
+      &["enter"],
+      Command::OpenComments,
+      "view comments for the selected item",
+    ),
+    (
+      &["o", "O"],
+      Command::OpenCurrentInBrowser,
+      "open the selected item in your browser",
+    ),
+    (
+      &["c"],
+      Command::OpenCommentsInBrowser,
+      "open the selected item's discussion in your browser",
+    ),
+    (&["y"], Command::CopyLink, "copy the selected item's link"),
+    (
+      &["s"],
+      Command::CopySummary,
+      "copy a one-line summary of the selected item",
+    ),
+    (
+      &["w"],
+      Command::OpenSectionInBrowser,
+      "open the current tab's page on hacker news",
+    ),
+    (
+      &["b", "B"],
+      Command::ToggleBookmark,
+      "toggle a bookmark for the selected item",
+    ),
+    (&["m"], Command::ShowBookmarks, "jump to the bookmarks tab"),
+    (
+      &["X"],
+      Command::CloseSearchTab,
+      "close the active search tab",
+    ),
+    (
+      &["t"],
+      Command::CycleTagFilter,
+      "cycle the bookmarks tab through its tags, then back to all",
+    ),
+    (
+      &["T"],
+      Command::EditTags,
+      "edit the tags of the selected bookmark",
+    ),
+    (
+      &["S"],
+      Command::CycleSearchSort,
+      "sort search results by relevance, date, or points",
+    ),
+    (
+      &["ctrl+t"],
+      Command::ShowTabSwitcher,
+      "pick a tab by name (type to filter, enter to jump)",
+    ),
+    (
+      &["+"],
+      Command::AdjustScoreFilter(10),
+      "raise the minimum score of shown stories by 10",
+    ),
+    (
+      &["-"],
+      Command::AdjustScoreFilter(-10),
+      "lower the minimum score of shown stories by 10",
+    ),
+    (
+      &["r"],
+      Command::RefreshTab,
+      "reload the current tab, or rerun the last search",
+    ),
+    (
+      &["R"],
+      Command::LoadNewer,
+      "pull in comments posted since the comments tab was loaded",
+    ),
+    (
+      &["["],
+      Command::ShiftDay(-1),
+      "show the next newer day on the past tab",
+    ),
+    (
+      &["]"],
+      Command::ShiftDay(1),
+      "show the next older day on the past tab",
+    ),
+    (
+      &["p"],
+      Command::TogglePin,
+      "pin the selected item to the top of its tab for this session",
+    ),
+    (
+      &["v"],
+      Command::ToggleSplitView,
+      "show the selected story's top comments beside the list",
+    ),
+    (
+      &["u"],
+      Command::ToggleRead,
+      "mark the selected item as read or unread",
+    ),
+    (
+      &["U"],
+      Command::MarkAllRead,
+      "mark every item in the current tab as read",
+    ),
+    (
+      &["/"],
+      Command::StartSearch,
+      "start a search (type to edit, enter to submit)",
+    ),
+    (
+      &["*"],
+      Command::RepeatSearch,
+      "edit and resubmit the previous search",
+    ),
+    (
+      &["f"],
+      Command::StartLocalFilter,
+      "filter loaded stories by title (esc to clear)",
+    ),
+    (
+      &[":", "ctrl+p"],
+      Command::ShowCommandPalette,
+      "search for a command by name and run it",
+    ),
+    (&["q", "Q", "esc"], Command::Quit, "quit hn"),
+    (&["?"], Command::ShowHelp, "toggle this help"),
+  ];
+
+  const LIST_NAVIGATION: &[Entry] = &[
+    (&["left", "h"], Command::SwitchTabLeft, "previous tab"),
+    (&["right", "l"], Command::SwitchTabRight, "next tab"),
+    (&["alt+1"], Command::SwitchToTab(0), "jump to tab 1"),
+    (&["alt+2"], Command::SwitchToTab(1), "jump to tab 2"),
+    (&["alt+3"], Command::SwitchToTab(2), "jump to tab 3"),
+    (&["alt+4"], Command::SwitchToTab(3), "jump to tab 4"),
+    (&["alt+5"], Command::SwitchToTab(4), "jump to tab 5"),
+    (&["alt+6"], Command::SwitchToTab(5), "jump to tab 6"),
+    (&["alt+7"], Command::SwitchToTab(6), "jump to tab 7"),
+    (&["alt+8"], Command::SwitchToTab(7), "jump to tab 8"),
+    (&["alt+9"], Command::SwitchToTab(8), "jump to tab 9"),
+    (&["up", "k"], Command::SelectPrevious, "move selection up"),
+    (&["down", "j"], Command::SelectNext, "move selection down"),
+    (&["pagedown", "ctrl+d"], Command::PageDown, "page down"),
+    (&["pageup", "ctrl+u"], Command::PageUp, "page up"),
+    (
+      &["home"],
+      Command::SelectFirst,
+      "jump to first item (or press g twice)",
+    ),
+    (&["end", "G"], Command::SelectLast, "jump to last item"),
+  ];
+
+  fn apply(
+    bindings: &mut Vec<Binding>,
+    entries: &[&[Entry]],
+    extra: &[Command],
+    mode: &str,
+    overrides: BTreeMap<String, String>,
+    errors: &mut Vec<String>,
+  ) {
+    for (key, name) in overrides {
+      let binding = match key.parse::<KeyBinding>() {
+        Ok(binding) => binding,
+        Err(error) => {
+          errors.push(format!("[{mode}] {error}"));
+          continue;
+        }
+      };
+
+      let command = match Self::parse_command(&name) {
+        Ok(command) => command,
+        Err(error) => {
+          errors.push(format!("[{mode}] {error}"));
+          continue;
+        }
+      };
+
+      let available = command == Command::None
+        || entries
+          .iter()
+          .flat_map(|entries| entries.iter())
+          .map(|(_, candidate, _)| candidate)
+          .chain(extra)
+          .any(|candidate| {
+            mem::discriminant(candidate) == mem::discriminant(&command)
+          });
+
+      if !available {
+        errors.push(format!("[{mode}] `{name}` is not available here"));
+        continue;
+      }
+
+      bindings.retain(|(existing, _)| *existing != binding);
+
+      if command != Command::None {
+        bindings.push((binding, command));
+      }
+    }
+  }
+
+  pub(crate) fn backup(&self) -> Option<&Path> {
+    self.backup.as_deref()
+  }
+
+  fn bindings(&self, comments: bool) -> &[Binding] {
+    if comments { &self.comments } else { &self.list }
+  }
+
+  pub(crate) fn comments_command(&self, key: KeyEvent) -> Command {
+    Self::lookup(&self.comments, key)
+  }
+
+  fn defaults(entries: &[&[Entry]]) -> Vec<Binding> {
+    entries
+      .iter()
+      .flat_map(|entries| entries.iter())
+      .flat_map(|(keys, command, _)| {
+        keys
+          .iter()
+          .filter_map(|key| key.parse().ok())
+          .map(|binding| (binding, *command))
+      })
+      .collect()
+  }
+
+  pub(crate) fn errors(&self) -> &[String] {
+    &self.errors
+  }
+
+  pub(crate) fn from_file(file: KeymapFile) -> Self {
+    let mut keymap = Self::default();
+
+    Self::apply(
+      &mut keymap.list,
+      &[Self::LIST_NAVIGATION, Self::LIST_ACTIONS],
+      &[Command::ScrollHelp(0), Command::SelectIndex(0)],
+      "list",
+      file.list,
+      &mut keymap.errors,
+    );
+
+    Self::apply(
+      &mut keymap.comments,
+      &[Self::COMMENTS],
+      &[Command::SelectIndex(0)],
+      "comments",
+      file.comments,
+      &mut keymap.errors,
+    );
+
+    keymap
+  }
+
+  pub(crate) fn help_text(&self) -> String {
+    let sections = [
+      ("Navigation:", &self.list, Self::LIST_NAVIGATION),
+      ("Actions:", &self.list, Self::LIST_ACTIONS),
+      ("Comments:", &self.comments, Self::COMMENTS),
+    ];
+
+    sections
+      .into_iter()
+      .map(|(heading, bindings, entries)| {
+        let mut rows = entries
+          .iter()
+          .filter_map(|(_, command, description)| {
+            let keys = Self::keys(bindings, *command);
+
+            (!keys.is_empty()).then_some((keys, *description))
+          })
+          .collect::<Vec<_>>();
+
+        if heading == "Actions:" {
+          rows.push((
+            "scroll".into(),
+            "keep going past the end to load more stories",
+          ));
+        }
+
+        let width = rows
+          .iter()
+          .map(|(keys, _)| keys.chars().count() + 2)
+          .max()
+          .unwrap_or_default()
+          .max(8);
+
+        let mut lines = vec![heading.to_string()];
+
+        lines.extend(rows.into_iter().map(|(keys, description)| {
+          format!(
+            "  {keys}{}{description}",
+            " ".repeat(width - keys.chars().count())
+          )
+        }));
+
+        lines.join("\n")
+      })
+      .collect::<Vec<_>>()
+      .join("\n\n")
+  }
+
+  pub(crate) fn hint(
+    &self,
+    comments: bool,
+    items: &[(&[Command], &str)],
+  ) -> String {
+    let bindings = self.bindings(comments);
+
+    items
+      .iter()
+      .filter_map(|(commands, label)| {
+        let keys = commands
+          .iter()
+          .map(|command| {
+            bindings
+              .iter()
+              .filter(|(_, bound)| bound == command)
+              .map(|(binding, _)| binding.to_string())
+              .collect::<Vec<_>>()
+              .join("/")
+          })
+          .filter(|keys| !keys.is_empty())
+          .collect::<Vec<_>>();
+
+        (!keys.is_empty()).then(|| format!("{} {label}", keys.join(" ")))
+      })
+      .collect::<Vec<_>>()
+      .join(" • ")
+  }
+
+  pub(crate) fn key(&self, comments: bool, command: Command) -> Option<String> {
+    self
+      .bindings(comments)
+      .iter()
+      .find(|(_, bound)| *bound == command)
+      .map(|(binding, _)| binding.to_string())
+  }
+
+  fn keys(bindings: &[Binding], command: Command) -> String {
+    bindings
+      .iter()
+      .filter(|(_, bound)| *bound == command)
+      .map(|(binding, _)| binding.to_string())
+      .collect::<Vec<_>>()
+      .join(" / ")
+  }
+
+  pub(crate) fn list_command(&self, key: KeyEvent) -> Command {
+    Self::lookup(&self.list, key)
+  }
+
+  pub(crate) fn load() -> Result<Self> {
+    let (file, backup) =
+      read_toml_file::<KeymapFile>(&config_path("HN_KEYS_FILE", "keys.toml")?)?;
+
+    let mut keymap = Self::from_file(file);
+
+    keymap.backup = backup;
+
+    if env::var_os("HN_DEBUG").is_some() {
+      keymap
+        .comments
+        .extend(Self::defaults(&[Self::DEBUG_COMMENTS]));
+    }
+
+    Ok(keymap)
+  }
+
+  fn lookup(bindings: &[Binding], key: KeyEvent) -> Command {
+    bindings
+      .iter()
+      .find(|(binding, _)| binding.matches(key))
+      .map_or(Command::None, |(_, command)| *command)
+  }
+
+  pub(crate) fn palette_entries(
+    &self,
+    comments: bool,
+  ) -> Vec<(Command, String, &'static str)> {
+    let (bindings, sections) = if comments {
+      (&self.comments, [Self::COMMENTS, &[]])
+    } else {
+      (&self.list, [Self::LIST_NAVIGATION, Self::LIST_ACTIONS])
+    };
+
+    sections
+      .iter()
+      .flat_map(|entries| entries.iter())
+      .filter(|(_, command, _)| *command != Command::ShowCommandPalette)
+      .map(|(_, command, description)| {
+        (*command, Self::keys(bindings, *command), *description)
+      })
+      .collect()
+  }
+
+  fn parse_command(name: &str) -> Result<Command, String> {
+    let Some((name, argument)) = name.split_once(':') else {
+      return Command::deserialize(de::value::StrDeserializer::<
+        de::value::Error,
+      >::new(name))
+      .map_err(|_| format!("unknown command `{name}`"));
+    };
+
+    let position = || {
+      argument
+        .parse::<usize>()
+        .ok()
+        .and_then(|position| position.checked_sub(1))
+    };
+
+    let command = match name {
+      "adjust-score-filter" => {
+        argument.parse().ok().map(Command::AdjustScoreFilter)
+      }
+      "scroll-help" => argument.parse().ok().map(Command::ScrollHelp),
+      "select-index" => position().map(Command::SelectIndex),
+      "shift-day" => argument.parse().ok().map(Command::ShiftDay),
+      "switch-to-tab" => position().map(Command::SwitchToTab),
+      _ => return Err(format!("unknown command `{name}`")),
+    };
+
+    command.ok_or_else(|| format!("invalid argument `{argument}` for `{name}`"))
+  }
+
+  pub(crate) fn press(
+    &self,
+    comments: bool,
+    command: Command,
+    rest: &str,
+  ) -> Option<String> {
+    self
+      .key(comments, command)
+      .map(|key| format!("Press {key} {rest}"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn keymap(toml: &str) -> Keymap {
+    Keymap::from_file(toml::from_str(toml).unwrap())
+  }
+
+  fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent::new(code, modifiers)
+  }
+
+  #[test]
+  fn debug_bindings_are_not_bound_or_listed_by_default() {
+    let keymap = Keymap::default();
+
+    assert_eq!(
+      keymap.comments_command(press(KeyCode::Char('D'), KeyModifiers::SHIFT)),
+      Command::None
+    );
+
+    assert!(!keymap.help_text().contains("raw and rendered"));
+  }
+
+  #[test]
+  fn defaults_match_builtin_bindings() {
+    let keymap = Keymap::default();
+
+    assert_eq!(
+      keymap.list_command(press(KeyCode::Char('j'), KeyModifiers::NONE)),
+      Command::SelectNext
+    );
+
+    assert_eq!(
+      keymap.list_command(press(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+      Command::PageDown
+    );
+
+    assert_eq!(
+      keymap.comments_command(press(KeyCode::Esc, KeyModifiers::NONE)),
+      Command::CloseComments
+    );
+
+    assert!(keymap.errors().is_empty());
+  }
+
+  #[test]
+  fn overrides_replace_and_extend_defaults() {
+    let keymap = keymap(
+      "[list]\n\"ctrl+n\" = \"select-next\"\n\"j\" = \"none\"\n\n[comments]\n\"x\" = \"toggle-comment\"",
+    );
+
+    assert_eq!(
+      keymap.list_command(press(KeyCode::Char('n'), KeyModifiers::CONTROL)),
+      Command::SelectNext
+    );
+
+    assert_eq!(
+      keymap.list_command(press(KeyCode::Char('j'), KeyModifiers::NONE)),
+      Command::None
+    );
+
+    assert_eq!(
+      keymap.list_command(press(KeyCode::Down, KeyModifiers::NONE)),
+      Command::SelectNext
+    );
+
+    assert_eq!(
+      keymap.comments_command(press(KeyCode::Char('x'), KeyModifiers::NONE)),
+      Command::ToggleComment
+    );
+  }
+
+  #[test]
+  fn invalid_bindings_are_reported_and_skipped() {
+    let keymap = keymap(
+      "[list]\n\"hyper+x\" = \"quit\"\n\"x\" = \"explode\"\n\"z\" = \"toggle-comment\"",
+    );
+
+    assert_eq!(keymap.errors().len(), 3);
+
+    assert_eq!(
+      keymap.list_command(press(KeyCode::Char('x'), KeyModifiers::NONE)),
+      Command::None
+    );
+  }
+
+  #[test]
+  fn commands_with_arguments_can_be_rebound() {
+    let keymap = keymap(
+      "[list]\n\"x\" = \"switch-to-tab:3\"\n\"{\" = \"shift-day:-7\"\n\"v\" = \"scroll-help:5\"\n\"w\" = \"shift-day:soon\"\n\"y\" = \"switch-to-tab:0\"",
+    );
+
+    assert_eq!(
+      keymap.list_command(press(KeyCode::Char('x'), KeyModifiers::NONE)),
+      Command::SwitchToTab(2)
+    );
+
+    assert_eq!(
+      keymap.list_command(press(KeyCode::Char('{'), KeyModifiers::NONE)),
+      Command::ShiftDay(-7)
+    );
+
+    assert_eq!(
+      keymap.list_command(press(KeyCode::Char('v'), KeyModifiers::NONE)),
+      Command::ScrollHelp(5)
+    );
+
+    assert_eq!(
+      keymap.errors(),
+      [
+        "[list] invalid argument `soon` for `shift-day`",
+        "[list] invalid argument `0` for `switch-to-tab`",
+      ]
+    );
+  }
+
+  #[test]
+  fn hints_name_the_active_bindings() {
+    let keymap = keymap("[list]\n\"j\" = \"none\"\n\"x\" = \"select-next\"");
+
+    assert_eq!(
+      keymap.hint(
+        false,
+        &[
+          (&[Command::SelectNext], "down"),
+          (
+            &[Command::SwitchTabLeft, Command::SwitchTabRight],
+            "switch tabs"
+          ),
+          (&[Command::None], "nothing"),
+        ]
+      ),
+      "↓/x down • ←/h →/l switch tabs"
+    );
+
+    assert_eq!(
+      keymap.key(true, Command::OpenCommentLinks).as_deref(),
+      Some("O")
+    );
+    assert_eq!(keymap.key(false, Command::OpenCommentLinks), None);
+  }
+
+  #[test]
+  fn help_text_reflects_active_bindings() {
+    let help = keymap("[list]\n\"ctrl+n\" = \"select-next\"").help_text();
+
+    assert!(help.contains("↓ / j / ctrl+n  move selection down"));
+    assert!(help.starts_with("Navigation:\n  ← / h"));
+  }
+}
@@ -0,0 +1,8 @@
+use super::*;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct KeymapFile {
+  pub(crate) comments: BTreeMap<String, String>,
+  pub(crate) list: BTreeMap<String, String>,
+}
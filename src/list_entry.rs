@@ -4,8 +4,16 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub(crate) struct ListEntry {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) comments: Option<u64>,
   pub(crate) detail: Option<String>,
   pub(crate) id: String,
+  #[serde(default)]
+  pub(crate) score: Option<u64>,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub(crate) tags: Vec<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) time: Option<u64>,
   pub(crate) title: String,
   pub(crate) url: Option<String>,
 }
@@ -23,7 +31,7 @@ impl From<CommentHit> for ListEntry {
           .map(|text| text.trim_end().to_owned())
       })
       .filter(|text| !text.is_empty())
-      .map(|text| truncate(&text, 120));
+      .map(|text| truncate_words(&text, 120));
 
     let detail = snippet.map(|text| format!("{author}: {text}"));
 
@@ -39,14 +47,53 @@ impl From<CommentHit> for ListEntry {
     });
 
     Self {
+      comments: None,
       detail,
       id: hit.object_id,
+      score: None,
+      tags: Vec::new(),
+      time: hit.created_at_i,
       title,
       url,
     }
   }
 }
 
+impl From<&CommentThread> for ListEntry {
+  fn from(thread: &CommentThread) -> Self {
+    let detail = match (thread.score, thread.op_author.as_deref()) {
+      (Some(score), Some(by)) => {
+        Some(format!("{} by {}", format_points(score), by))
+      }
+      (Some(score), None) => Some(format_points(score)),
+      (None, Some(by)) => Some(format!("by {by}")),
+      _ => None,
+    };
+
+    let detail = match (detail, thread.descendants) {
+      (Some(detail), Some(count)) => {
+        Some(format!("{detail} • {}", format_comments(count)))
+      }
+      (None, Some(count)) => Some(format_comments(count)),
+      (detail, None) => detail,
+    };
+
+    Self {
+      comments: thread.descendants,
+      detail,
+      id: thread.id.to_string(),
+      score: thread.score,
+      tags: Vec::new(),
+      time: None,
+      title: thread
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Thread {}", thread.id)),
+      url: None,
+    }
+  }
+}
+
 impl From<Story> for ListEntry {
   fn from(story: Story) -> Self {
     let detail = match (story.score, story.by.as_deref()) {
@@ -58,9 +105,21 @@ impl From<Story> for ListEntry {
       _ => None,
     };
 
+    let detail = match (detail, story.descendants) {
+      (Some(detail), Some(count)) => {
+        Some(format!("{detail} • {}", format_comments(count)))
+      }
+      (None, Some(count)) => Some(format_comments(count)),
+      (detail, None) => detail,
+    };
+
     Self {
+      comments: story.descendants,
       detail,
       id: story.id.to_string(),
+      score: story.score,
+      tags: Vec::new(),
+      time: story.time,
       title: story.title,
       url: story.url,
     }
@@ -81,8 +140,12 @@ impl From<SearchHit> for ListEntry {
     let title = hit.title.unwrap_or_else(|| "Untitled".to_string());
 
     Self {
+      comments: None,
       detail,
       id: hit.object_id,
+      score: hit.points,
+      tags: Vec::new(),
+      time: hit.created_at_i,
       title,
       url: hit.url,
     }
@@ -90,14 +153,62 @@ impl From<SearchHit> for ListEntry {
 }
 
 impl ListEntry {
-  pub(crate) fn resolved_url(&self) -> String {
-    self
-      .url
-      .clone()
-      .filter(|url| !url.is_empty())
-      .unwrap_or_else(|| {
-        format!("https://news.ycombinator.com/item?id={}", self.id)
-      })
+  const HIRING_THREAD_PREFIXES: [&str; 3] = [
+    "ask hn: who is hiring",
+    "ask hn: who wants to be hired",
+    "ask hn: freelancer? seeking freelancer",
+  ];
+
+  pub(crate) fn comments_url(&self) -> String {
+    format!("https://news.ycombinator.com/item?id={}", self.id)
+  }
+
+  pub(crate) fn detail_at(&self, now: u64) -> Option<String> {
+    let age = self.time.map(|time| format_relative_time(now, time));
+
+    match (self.detail.as_deref(), age) {
+      (Some(detail), Some(age)) => {
+        Some(format!("{detail}{DETAIL_SEPARATOR}{age}"))
+      }
+      (Some(detail), None) => Some(detail.to_string()),
+      (None, age) => age,
+    }
+  }
+
+  pub(crate) fn is_hiring_thread(&self) -> bool {
+    let title = self.title.to_lowercase();
+
+    Self::HIRING_THREAD_PREFIXES
+      .iter()
+      .any(|prefix| title.starts_with(prefix))
+  }
+
+  pub(crate) fn matches_filter(&self, filter: Option<&str>) -> bool {
+    filter.is_none_or(|filter| fuzzy_score(filter, &self.title).is_some())
+  }
+
+  pub(crate) fn meets_score(&self, min_score: u64) -> bool {
+    self.score.is_none_or(|score| score >= min_score)
+  }
+
+  pub(crate) fn resolved_url(&self, keep_tracking: bool) -> String {
+    self.url.clone().filter(|url| !url.is_empty()).map_or_else(
+      || self.comments_url(),
+      |url| strip_tracking(url, keep_tracking),
+    )
+  }
+
+  pub(crate) fn summary(&self, template: &str, keep_tracking: bool) -> String {
+    let points = self
+      .score
+      .map_or_else(|| "? points".to_string(), format_points);
+
+    template
+      .replace("{comments}", &self.comments_url())
+      .replace("{id}", &self.id)
+      .replace("{points}", &points)
+      .replace("{url}", &self.resolved_url(keep_tracking))
+      .replace("{title}", &self.title)
   }
 }
 
@@ -105,12 +216,113 @@ impl ListEntry {
 mod tests {
   use super::*;
 
+  #[test]
+  fn summary_fills_template_placeholders() {
+    let entry = ListEntry {
+      comments: None,
+      detail: None,
+      id: "7".to_string(),
+      score: Some(123),
+      tags: Vec::new(),
+      time: None,
+      title: "Example".to_string(),
+      url: Some("https://example.com".to_string()),
+    };
+
+    assert_eq!(
+      entry.summary(Config::DEFAULT_SUMMARY_FORMAT, false),
+      "Example — 123 points — https://example.com"
+    );
+
+    assert_eq!(
+      entry.summary("{id}: {comments}", false),
+      "7: https://news.ycombinator.com/item?id=7"
+    );
+  }
+
+  #[test]
+  fn summary_marks_unknown_points() {
+    let entry = ListEntry {
+      comments: None,
+      detail: None,
+      id: "7".to_string(),
+      score: None,
+      tags: Vec::new(),
+      time: None,
+      title: "Example".to_string(),
+      url: None,
+    };
+
+    assert_eq!(
+      entry.summary(Config::DEFAULT_SUMMARY_FORMAT, false),
+      "Example — ? points — https://news.ycombinator.com/item?id=7"
+    );
+  }
+
+  #[test]
+  fn detail_at_appends_relative_age() {
+    let story = |score, time| Story {
+      by: None,
+      descendants: None,
+      id: 1,
+      score,
+      time,
+      title: "Story".to_string(),
+      url: None,
+    };
+
+    assert_eq!(
+      ListEntry::from(story(Some(5), Some(1_000))).detail_at(1_000 + 7200),
+      Some("5 points • 2h ago".to_string())
+    );
+
+    assert_eq!(
+      ListEntry::from(story(None, Some(1_000))).detail_at(1_000 + 90),
+      Some("1m ago".to_string())
+    );
+
+    assert_eq!(
+      ListEntry::from(story(Some(5), None)).detail_at(1_000),
+      Some("5 points".to_string())
+    );
+  }
+
+  #[test]
+  fn from_story_appends_comment_count_to_detail() {
+    let story = |descendants| Story {
+      by: Some("alice".to_string()),
+      descendants,
+      id: 123,
+      score: Some(120),
+      time: None,
+      title: "Interesting story".to_string(),
+      url: None,
+    };
+
+    assert_eq!(
+      ListEntry::from(story(Some(45))).detail.as_deref(),
+      Some("120 points by alice • 45 comments")
+    );
+
+    assert_eq!(
+      ListEntry::from(story(Some(1))).detail.as_deref(),
+      Some("120 points by alice • 1 comment")
+    );
+
+    assert_eq!(
+      ListEntry::from(story(None)).detail.as_deref(),
+      Some("120 points by alice")
+    );
+  }
+
   #[test]
   fn from_story_uses_score_and_author_for_detail() {
     let entry = ListEntry::from(Story {
       by: Some("alice".to_string()),
+      descendants: None,
       id: 123,
       score: Some(10),
+      time: None,
       title: "Interesting story".to_string(),
       url: Some("https://example.com/story".to_string()),
     });
@@ -122,17 +334,62 @@ mod tests {
     assert_eq!(entry.url.as_deref(), Some("https://example.com/story"));
   }
 
+  #[test]
+  fn is_hiring_thread_matches_monthly_job_threads() {
+    let entry = |title: &str| ListEntry {
+      comments: None,
+      detail: None,
+      id: "1".to_string(),
+      score: None,
+      tags: Vec::new(),
+      time: None,
+      title: title.to_string(),
+      url: None,
+    };
+
+    assert!(entry("Ask HN: Who is hiring? (March 2025)").is_hiring_thread());
+    assert!(
+      entry("Ask HN: Who wants to be hired? (May 2025)").is_hiring_thread()
+    );
+    assert!(!entry("Show HN: Who is hiring, visualized").is_hiring_thread());
+  }
+
+  #[test]
+  fn resolved_url_keeps_tracking_parameters_when_asked() {
+    let entry = ListEntry {
+      comments: None,
+      detail: None,
+      id: "456".to_string(),
+      score: None,
+      tags: Vec::new(),
+      time: None,
+      title: "Tracked".to_string(),
+      url: Some("https://example.com/?utm_source=hn&id=1".to_string()),
+    };
+
+    assert_eq!(entry.resolved_url(false), "https://example.com/?id=1");
+
+    assert_eq!(
+      entry.resolved_url(true),
+      "https://example.com/?utm_source=hn&id=1"
+    );
+  }
+
   #[test]
   fn resolved_url_falls_back_to_hn_item_page() {
     let entry = ListEntry {
+      comments: None,
       detail: None,
       id: "456".to_string(),
+      score: None,
+      tags: Vec::new(),
+      time: None,
       title: "Fallback".to_string(),
       url: None,
     };
 
     assert_eq!(
-      entry.resolved_url(),
+      entry.resolved_url(false),
       "https://news.ycombinator.com/item?id=456"
     );
   }
@@ -142,6 +399,7 @@ mod tests {
     let entry = ListEntry::from(CommentHit {
       author: Some("bob".to_string()),
       comment_text: Some("Test detail".to_string()),
+      created_at_i: Some(1_700_000_000),
       object_id: "789".to_string(),
       story_id: Some("42".to_string()),
       story_title: Some("Comment thread".to_string()),
@@ -156,12 +414,14 @@ mod tests {
     );
 
     assert_eq!(entry.title, "Comment thread");
+    assert_eq!(entry.time, Some(1_700_000_000));
   }
 
   #[test]
   fn from_search_hit_handles_missing_title_and_author() {
     let entry = ListEntry::from(SearchHit {
       author: None,
+      created_at_i: Some(1_700_000_000),
       object_id: "s1".to_string(),
       points: Some(5),
       title: None,
@@ -173,5 +433,6 @@ mod tests {
     assert_eq!(entry.detail.as_deref(), Some("5 points"));
 
     assert_eq!(entry.url.as_deref(), Some("https://example.com/search"));
+    assert_eq!(entry.time, Some(1_700_000_000));
   }
 }
@@ -1,7 +1,9 @@
 pub(crate) struct ListView<T> {
   items: Vec<T>,
   offset: usize,
+  ranks: Vec<usize>,
   selected: usize,
+  selection_generation: u64,
 }
 
 impl<T> Default for ListView<T> {
@@ -9,17 +11,44 @@ impl<T> Default for ListView<T> {
     Self {
       items: Vec::new(),
       offset: 0,
+      ranks: Vec::new(),
       selected: 0,
+      selection_generation: 0,
     }
   }
 }
 
 impl<T> ListView<T> {
+  pub(crate) fn centered_offset<F>(&self, height: usize, shown: F) -> usize
+  where
+    F: Fn(&T) -> bool,
+  {
+    let Some(selected) = self.selected_index() else {
+      return 0;
+    };
+
+    let position = self.items[..selected]
+      .iter()
+      .filter(|item| shown(item))
+      .count();
+
+    let len = self.items.iter().filter(|item| shown(item)).count();
+
+    let height = height.max(1);
+
+    position
+      .saturating_sub(height / 2)
+      .min(len.saturating_sub(height))
+  }
+
   pub(crate) fn extend<I>(&mut self, items: I)
   where
     I: IntoIterator<Item = T>,
   {
+    let start = self.items.len();
+
     self.items.extend(items);
+    self.ranks.extend(start..self.items.len());
   }
 
   pub(crate) fn is_empty(&self) -> bool {
@@ -36,9 +65,11 @@ impl<T> ListView<T> {
 
   pub(crate) fn new(items: Vec<T>) -> Self {
     Self {
+      ranks: (0..items.len()).collect(),
       items,
       offset: 0,
       selected: 0,
+      selection_generation: 0,
     }
   }
 
@@ -52,6 +83,22 @@ impl<T> ListView<T> {
     }
   }
 
+  pub(crate) fn prepend(&mut self, items: Vec<T>) {
+    let count = items.len();
+
+    if !self.items.is_empty() {
+      self.selected = self.selected.saturating_add(count);
+      self.offset = self.offset.saturating_add(count);
+    }
+
+    for rank in &mut self.ranks {
+      *rank = rank.saturating_add(count);
+    }
+
+    self.items.splice(0..0, items);
+    self.ranks.splice(0..0, 0..count);
+  }
+
   pub(crate) fn selected_index(&self) -> Option<usize> {
     if self.items.is_empty() {
       None
@@ -70,6 +117,10 @@ impl<T> ListView<T> {
     self.selected
   }
 
+  pub(crate) fn selection_generation(&self) -> u64 {
+    self.selection_generation
+  }
+
   pub(crate) fn set_offset(&mut self, offset: usize) {
     if self.items.is_empty() {
       self.offset = 0;
@@ -80,18 +131,96 @@ impl<T> ListView<T> {
   }
 
   pub(crate) fn set_selected(&mut self, index: usize) {
+    self.selection_generation = self.selection_generation.wrapping_add(1);
+
     if self.items.is_empty() {
       self.selected = 0;
     } else {
       self.selected = index.min(self.items.len().saturating_sub(1));
     }
   }
+
+  pub(crate) fn sort_by_key<K, F>(&mut self, mut key: F)
+  where
+    F: FnMut(&T) -> K,
+    K: Ord,
+  {
+    let selected = self.selected_index();
+
+    let mut order = (0..self.items.len()).collect::<Vec<_>>();
+
+    order.sort_by_key(|&index| (key(&self.items[index]), self.ranks[index]));
+
+    let mut items = std::mem::take(&mut self.items)
+      .into_iter()
+      .map(Some)
+      .collect::<Vec<_>>();
+
+    self.items = order
+      .iter()
+      .filter_map(|&index| items[index].take())
+      .collect();
+
+    self.ranks = order.iter().map(|&index| self.ranks[index]).collect();
+
+    if let Some(selected) = selected {
+      self.selected = order
+        .iter()
+        .position(|&index| index == selected)
+        .unwrap_or(0);
+    }
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  #[test]
+  fn centered_offset_keeps_middle_selections_centered() {
+    let mut view = ListView::new((0..20).collect());
+
+    view.set_selected(10);
+
+    assert_eq!(view.centered_offset(5, |_| true), 8);
+    assert_eq!(view.centered_offset(6, |_| true), 7);
+  }
+
+  #[test]
+  fn centered_offset_anchors_to_the_top() {
+    let mut view = ListView::new((0..20).collect());
+
+    view.set_selected(1);
+
+    assert_eq!(view.centered_offset(5, |_| true), 0);
+    assert_eq!(ListView::<i32>::default().centered_offset(5, |_| true), 0);
+  }
+
+  #[test]
+  fn centered_offset_anchors_to_the_bottom() {
+    let mut view = ListView::new((0..20).collect());
+
+    view.set_selected(18);
+
+    assert_eq!(view.centered_offset(5, |_| true), 15);
+
+    let mut short = ListView::new(vec![1, 2, 3]);
+
+    short.set_selected(2);
+
+    assert_eq!(short.centered_offset(10, |_| true), 0);
+  }
+
+  #[test]
+  fn centered_offset_counts_only_shown_items() {
+    let mut view = ListView::new((0..40).collect());
+
+    view.set_selected(20);
+
+    assert_eq!(view.centered_offset(5, |item| item % 2 == 0), 8);
+    assert_eq!(view.centered_offset(5, |item| item % 4 == 0), 3);
+  }
+
   #[test]
   fn selected_index_is_none_when_empty() {
     let view = ListView::<i32>::default();
@@ -122,6 +251,37 @@ mod tests {
     assert_eq!(view.selected_item(), Some(&"b"));
   }
 
+  #[test]
+  fn set_selected_advances_selection_generation() {
+    let mut view = ListView::new(vec![1, 2]);
+
+    let generation = view.selection_generation();
+
+    view.set_selected(1);
+
+    assert_ne!(view.selection_generation(), generation);
+  }
+
+  #[test]
+  fn sort_by_key_breaks_ties_by_arrival_and_keeps_selected_item() {
+    let mut view = ListView::new(vec![1, 2, 3, 4]);
+
+    view.set_selected(1);
+
+    view.sort_by_key(|item| item % 2 == 0);
+
+    assert_eq!(view.items(), &[1, 3, 2, 4]);
+    assert_eq!(view.selected_item(), Some(&2));
+
+    view.prepend(vec![0]);
+    view.extend(vec![5]);
+
+    view.sort_by_key(|_| false);
+
+    assert_eq!(view.items(), &[0, 1, 2, 3, 4, 5]);
+    assert_eq!(view.selected_item(), Some(&2));
+  }
+
   #[test]
   fn selecting_index_uses_visible_order() {
     let mut view = ListView::new(vec![10, 20, 30]);
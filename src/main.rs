@@ -1,21 +1,33 @@
 use {
-  anyhow::Context,
+  anyhow::{Context, anyhow, bail},
   app::App,
+  arboard::Clipboard,
+  arguments::Arguments,
   bookmark::Bookmarks,
+  bookmark_entry::BookmarkEntry,
+  bookmark_file::BookmarkFile,
+  cache::Cache,
   category::{Category, CategoryKind},
+  category_listing::CategoryListing,
+  clap::Parser,
   client::Client,
   command::Command,
   command_dispatch::CommandDispatch,
+  command_palette::CommandPalette,
   comment::Comment,
   comment_entry::CommentEntry,
   comment_hit::CommentHit,
   comment_response::CommentResponse,
   comment_thread::CommentThread,
   comment_view::CommentView,
+  config::Config,
+  contrast::Contrast,
   crossterm::{
     event as crossterm_event,
     event::{
-      Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
+      DisableMouseCapture, EnableMouseCapture, Event as CrosstermEvent,
+      KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+      MouseEventKind,
     },
     execute,
     style::Stylize,
@@ -24,138 +36,198 @@ use {
       enable_raw_mode,
     },
   },
+  digest::Digest,
   effect::Effect,
   event::Event,
-  futures::{
-    future::join_all,
-    stream::{self, StreamExt},
-  },
+  event_source::EventSource,
+  export::Export,
+  export_format::ExportFormat,
+  followed_users::FollowedUsers,
+  futures::stream::{self, StreamExt},
+  guide::Guide,
   help_view::HelpView,
   item::Item,
+  item_link::ItemLink,
+  key_binding::KeyBinding,
+  keymap::Keymap,
+  keymap_file::KeymapFile,
   list_entry::ListEntry,
   list_view::ListView,
   mode::Mode,
+  opener::Opener,
   pending_comment::PendingComment,
   pending_search::PendingSearch,
+  pending_selection::PendingSelection,
+  preview::Preview,
+  print_subcommand::PrintSubcommand,
   ratatui::{
     Frame, Terminal,
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{
       Block, Borders, Clear, List, ListItem, ListState, Paragraph, Tabs, Wrap,
     },
   },
+  read_items::ReadItems,
+  render_context::RenderContext,
+  saved_threads::SavedThreads,
   search_hit::SearchHit,
   search_input::SearchInput,
   search_response::SearchResponse,
+  search_sort::SearchSort,
+  search_tab::SearchTab,
+  separator::Separator,
   serde::{
-    Deserialize, Deserializer,
-    de::{self, Unexpected},
+    Deserialize, Deserializer, Serialize,
+    de::{self, DeserializeOwned, Unexpected},
   },
   serde_json::Value,
+  session::Session,
+  spinner::Spinner,
   state::State,
+  status::{help_status, list_status, status_for},
   std::{
     backtrace::BacktraceStatus,
-    collections::HashSet,
-    env, fs,
-    io::{self, IsTerminal, Stdout},
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    env,
+    fmt::{self, Display, Formatter},
+    fs,
+    io::{self, IsTerminal, Stdout, Write},
+    iter, mem,
+    ops::{Range, RangeInclusive},
     path::{Path, PathBuf},
-    process,
+    process::{self, Stdio},
+    str::{self, FromStr},
     string::String,
-    time::{Duration, Instant},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
   },
   story::Story,
+  subcommand::Subcommand,
   tab::Tab,
+  tab_position::TabPosition,
+  tab_switcher::TabSwitcher,
+  terminal_events::TerminalEvents,
+  theme::Theme,
   tokio::{
     runtime::Handle,
-    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    sync::{
+      Semaphore,
+      mpsc::{self, UnboundedReceiver, UnboundedSender},
+    },
+    task, time,
   },
   transient_message::TransientMessage,
-  utils::{deserialize_optional_string, format_points, truncate, wrap_text},
+  tree_guides::TreeGuides,
+  utils::{
+    config_path, deserialize_optional_string, ensure_parent_dir, extract_links,
+    find_matches, format_comments, format_date, format_points,
+    format_relative_time, fuzzy_score, hn_item_id, markdown_link,
+    markdown_quote, parse_clamped, read_json_file, read_toml_file,
+    strip_tracking, truncate, truncate_words, wrap_text,
+  },
 };
 
 mod app;
+mod arguments;
 mod bookmark;
+mod bookmark_entry;
+mod bookmark_file;
+mod cache;
 mod category;
+mod category_listing;
 mod client;
 mod command;
 mod command_dispatch;
+mod command_palette;
 mod comment;
 mod comment_entry;
 mod comment_hit;
 mod comment_response;
 mod comment_thread;
 mod comment_view;
+mod config;
+mod contrast;
+mod digest;
 mod effect;
 mod event;
+mod event_source;
+mod export;
+mod export_format;
+mod followed_users;
+mod guide;
 mod help_view;
 mod item;
+mod item_link;
+mod key_binding;
+mod keymap;
+mod keymap_file;
 mod list_entry;
 mod list_view;
 mod mode;
+mod opener;
 mod pending_comment;
 mod pending_search;
+mod pending_selection;
+mod preview;
+mod print_subcommand;
+mod read_items;
+mod render_context;
+mod saved_threads;
 mod search_hit;
 mod search_input;
 mod search_response;
+mod search_sort;
+mod search_tab;
+mod separator;
+mod session;
+mod spinner;
 mod state;
+mod status;
 mod story;
+mod subcommand;
 mod tab;
+mod tab_position;
+mod tab_switcher;
+mod terminal_events;
+mod theme;
 mod transient_message;
+mod tree_guides;
 mod utils;
 
-const INITIAL_BATCH_SIZE: usize = 30;
+const DAY: u64 = 24 * 60 * 60;
 
-const LIST_STATUS: &str = "↑/k up • ↓/j down • enter comments • o open link • b bookmark • q/esc quit • ? help";
-
-const COMMENTS_STATUS: &str = "↑/k up • ↓/j down • ←/h collapse • →/l expand • enter toggle • o open comment • b bookmark • esc back";
+const DETAIL_SEPARATOR: &str = " • ";
 
 const HELP_TITLE: &str = "Help";
-const HELP_STATUS: &str = "Press ? or esc to close help";
 
 const LOADING_ENTRIES_STATUS: &str = "Loading more entries...";
+const LOADING_MORE_ROW: &str = "Loading more…";
+const LOADING_REPLIES_ROW: &str = "Loading replies…";
 const LOADING_COMMENTS_STATUS: &str = "Loading comments...";
 const LOADING_SEARCH_STATUS: &str = "Searching...";
 
 const BASE_INDENT: &str = " ";
 
-const HELP_TEXT: &str = "\
-Navigation:
-  ← / h   previous tab
-  → / l   next tab
-  ↑ / k   move selection up
-  ↓ / j   move selection down
-  pg↓     page down
-  pg↑     page up
-  ctrl+d  page down
-  ctrl+u  page up
-  home    jump to first item
-  end     jump to last item
-
-Actions:
-  enter   view comments for the selected item
-  o       open the selected item in your browser
-  b       toggle a bookmark for the selected item
-  /       start a search (type to edit, enter to submit)
-  q       quit hn
-  esc     close help or quit from the list
-  scroll  keep going past the end to load more stories
-  ?       toggle this help
-
-Comments:
-  ↑ / k   move selection up
-  ↓ / j   move selection down
-  pg↓     page down
-  pg↑     page up
-  ← / h   collapse or go to parent
-  → / l   expand or go to first child
-  enter   toggle collapse or expand
-  o       open the selected comment in your browser
-  b       toggle a bookmark for the selected comment
-  esc     return to the story list
-";
+const BOOKMARK_MARKER: &str = "★ ";
+
+const FOLLOWED_MARKER: &str = " ★";
+
+const LINK_BATCH_CONFIRM_THRESHOLD: usize = 3;
+
+const LINK_BATCH_LIMIT: usize = 10;
+
+const PIN_MARKER: &str = "▲ ";
+
+const TRACKING_PARAMS: &[&str] = &[
+  "_hsenc", "_hsmi", "dclid", "fbclid", "gclid", "igshid", "mc_cid", "mc_eid",
+  "mkt_tok", "msclkid", "ref_src", "yclid",
+];
+
+const UNREAD_MARKER: &str = "• ";
 
 type Result<T = (), E = anyhow::Error> = std::result::Result<T, E>;
 
@@ -163,7 +235,7 @@ fn initialize_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
   enable_raw_mode()?;
 
   let mut stdout = io::stdout();
-  execute!(stdout, EnterAlternateScreen)?;
+  execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
 
   Ok(Terminal::new(CrosstermBackend::new(stdout))?)
 }
@@ -173,27 +245,128 @@ fn restore_terminal(
 ) -> Result {
   disable_raw_mode()?;
 
-  execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+  execute!(
+    terminal.backend_mut(),
+    LeaveAlternateScreen,
+    DisableMouseCapture
+  )?;
 
   terminal.show_cursor()?;
 
   Ok(())
 }
 
+fn resume_terminal(
+  terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+) -> Result {
+  enable_raw_mode()?;
+
+  execute!(
+    terminal.backend_mut(),
+    EnterAlternateScreen,
+    EnableMouseCapture
+  )?;
+
+  terminal.clear()?;
+
+  Ok(())
+}
+
 async fn run() -> Result {
-  let client = Client::default();
+  let mut arguments = Arguments::parse();
+
+  let config = Config::load().context("could not load config")?;
+
+  let mut client = Client::with_cache(config.cache_ttl().is_some());
 
-  let tabs = client.load_tabs(INITIAL_BATCH_SIZE).await?;
+  client.set_max_requests(config.max_requests());
+
+  if let Ok(value) = env::var("HN_BATCH_SIZE") {
+    client.set_batch_size(
+      parse_clamped(&value, Client::BATCH_SIZE_RANGE)
+        .context("invalid `HN_BATCH_SIZE`")?,
+    );
+  }
+
+  if let Ok(value) = env::var("HN_CONCURRENCY") {
+    client.set_concurrency(
+      parse_clamped(&value, Client::CONCURRENCY_RANGE)
+        .context("invalid `HN_CONCURRENCY`")?,
+    );
+  }
+
+  if let Some(ttl) = config.cache_ttl() {
+    client.set_cache_ttls(ttl, config.category_cache_ttls());
+  }
+
+  if arguments.offline() {
+    if config.cache_ttl().is_none() {
+      bail!(
+        "offline mode needs the disk cache, which `cache_ttl = 0` disables"
+      );
+    }
+
+    client.set_offline(true);
+  }
+
+  let item_link = match arguments.subcommand.take() {
+    Some(Subcommand::Item(item_link)) => Some(item_link),
+    Some(Subcommand::Print(subcommand)) => {
+      return subcommand.run(&client, config.keep_tracking()).await;
+    }
+    None => None,
+  };
+
+  let categories = arguments.categories()?;
+
+  let tabs = categories
+    .iter()
+    .map(|&category| (Tab::new(category), ListView::default()))
+    .collect();
 
   let bookmarks = Bookmarks::load().context("could not load bookmarks")?;
 
+  let read_items = ReadItems::load();
+
+  let saved_threads = SavedThreads::load();
+
+  let followed_users = FollowedUsers::load();
+
+  let mut session = Session::load().context("could not load session")?;
+
+  let mut app = App::new(
+    client,
+    tabs,
+    bookmarks,
+    read_items,
+    saved_threads,
+    followed_users,
+    config,
+    &session,
+  )?;
+
+  if let Ok(value) = env::var("HN_MESSAGE_TIMEOUT_MS") {
+    let millis = parse_clamped(&value, TransientMessage::TIMEOUT_RANGE_MS)
+      .context("invalid `HN_MESSAGE_TIMEOUT_MS`")?;
+
+    app.set_message_timeout(Duration::from_millis(u64::try_from(millis)?));
+  }
+
+  app.load_tabs(arguments.lazy)?;
+
+  if let Some(item_link) = item_link {
+    app.open_item(item_link.id, item_link.comment);
+  }
+
   let mut terminal = initialize_terminal()?;
 
-  let mut app = App::new(client, tabs, bookmarks);
+  app.run(&mut terminal, &mut TerminalEvents)?;
+
+  restore_terminal(&mut terminal)?;
 
-  app.run(&mut terminal)?;
+  app.store_session(&mut session);
 
-  restore_terminal(&mut terminal)
+  session.save().context("could not save session")
 }
 
 #[tokio::main]
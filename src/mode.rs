@@ -1,117 +1,135 @@
 use super::{command::Command, *};
 
 pub(crate) enum Mode {
-  Comments(CommentView),
+  Comments(Box<CommentView>),
   List(ListView<ListEntry>),
 }
 
 impl Mode {
-  pub(crate) fn handle_key(&mut self, key: KeyEvent, page: usize) -> Command {
+  pub(crate) fn apply(&mut self, command: Command, page: usize) -> Command {
     match self {
-      Mode::List(view) => {
-        let modifiers = key.modifiers;
-
-        match key.code {
-          KeyCode::Char('q' | 'Q') | KeyCode::Esc => Command::Quit,
-          KeyCode::Char('?') => Command::ShowHelp,
-          KeyCode::Left | KeyCode::Char('h') => Command::SwitchTabLeft,
-          KeyCode::Right | KeyCode::Char('l') => Command::SwitchTabRight,
-          KeyCode::Down | KeyCode::Char('j') => Command::SelectNext,
-          KeyCode::Up | KeyCode::Char('k') => Command::SelectPrevious,
-          KeyCode::PageDown => Command::PageDown,
-          KeyCode::PageUp => Command::PageUp,
-          KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => {
-            Command::PageDown
+      Mode::List(view) => match command {
+        Command::SelectLast => {
+          if !view.is_empty() {
+            let last = view.len().saturating_sub(1);
+            view.set_selected(last);
           }
-          KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
-            Command::PageUp
-          }
-          KeyCode::Char('/') => Command::StartSearch,
-          KeyCode::Char('b' | 'B') => Command::ToggleBookmark,
-          KeyCode::Home => Command::SelectFirst,
-          KeyCode::End => {
-            if !view.is_empty() {
-              let last = view.len().saturating_sub(1);
-              view.set_selected(last);
-            }
 
-            Command::None
-          }
-          KeyCode::Enter => Command::OpenComments,
-          KeyCode::Char('o' | 'O') => Command::OpenCurrentInBrowser,
-          _ => Command::None,
+          Command::None
         }
-      }
+        command => command,
+      },
       Mode::Comments(view) => {
-        let modifiers = key.modifiers;
-
-        match key.code {
-          KeyCode::Char('q' | 'Q') => Command::Quit,
-          KeyCode::Esc => Command::CloseComments,
-          KeyCode::Char('?') => Command::ShowHelp,
-          KeyCode::Char('o' | 'O') => Command::OpenCommentLink,
-          KeyCode::Down | KeyCode::Char('j') => {
-            view.select_next();
-            Command::None
-          }
-          KeyCode::Up | KeyCode::Char('k') => {
-            view.select_previous();
-            Command::None
-          }
-          KeyCode::PageDown => {
-            view.page_down(page);
-            Command::None
-          }
-          KeyCode::PageUp => {
-            view.page_up(page);
-            Command::None
-          }
-          KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => {
-            view.page_down(page);
-            Command::None
-          }
-          KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
-            view.page_up(page);
-            Command::None
-          }
-          KeyCode::Char('/') => Command::StartSearch,
-          KeyCode::Left | KeyCode::Char('h') => {
-            view.collapse_selected();
-            Command::None
-          }
-          KeyCode::Right | KeyCode::Char('l') => {
-            view.expand_selected();
-            Command::None
+        view.mark_visited();
+
+        if view.is_scrolling() && Self::scroll(view, command, page) {
+          view.mark_visited();
+          view.sync_horizontal_offset();
+          return Command::None;
+        }
+
+        match command {
+          Command::SelectNext => view.select_next(),
+          Command::SelectPrevious => view.select_previous(),
+          Command::PageDown => view.page_down(page),
+          Command::PageUp => view.page_up(page),
+          Command::CollapseComment => view.collapse_selected(),
+          Command::ExpandComment => view.expand_selected(),
+          Command::ToggleComment => view.toggle_selected(),
+          Command::CollapseAll => view.collapse_all(),
+          Command::CollapseSubtree => {
+            if let Some(selected) = view.selected {
+              view.collapse_subtree(selected);
+            }
           }
-          KeyCode::Enter | KeyCode::Char(' ') => {
-            view.toggle_selected();
-            Command::None
+          Command::ExpandAll => view.expand_all(),
+          Command::ScrollLeft => {
+            view.scroll_horizontally(-CommentView::HORIZONTAL_SCROLL_STEP);
           }
-          KeyCode::Home => {
-            view.select_index_at(0);
-            Command::None
+          Command::ScrollRight => {
+            view.scroll_horizontally(CommentView::HORIZONTAL_SCROLL_STEP);
           }
-          KeyCode::Char('b' | 'B') => Command::ToggleBookmark,
-          KeyCode::End => {
+          Command::NextRoot => view.advance_root(true),
+          Command::PreviousRoot => view.advance_root(false),
+          Command::SelectFirst => view.select_index_at(0),
+          Command::SelectIndex(position) => view.select_index_at(position),
+          Command::SelectLast => {
             let (visible, _) = view.visible_with_selection();
 
             if !visible.is_empty() {
               view.select_index_at(visible.len().saturating_sub(1));
             }
-
-            Command::None
           }
-          _ => Command::None,
+          Command::SelectNextSibling => view.select_next_sibling(),
+          Command::SelectNextUnread => {
+            view.select_next_unread();
+          }
+          Command::SelectRootNext => view.select_root_next(),
+          Command::ToggleFlat => view.toggle_flat(),
+          Command::ToggleFocus => view.toggle_focus_only(),
+          Command::ToggleHideDead => view.toggle_hide_dead(),
+          Command::ToggleListing => view.toggle_listing(),
+          Command::ToggleScroll => view.toggle_scrolling(),
+          command => return command,
         }
+
+        view.mark_visited();
+        view.sync_horizontal_offset();
+
+        Command::None
       }
     }
   }
 
+  pub(crate) fn command(&self, key: KeyEvent, keymap: &Keymap) -> Command {
+    match self {
+      Mode::List(_) => keymap.list_command(key),
+      Mode::Comments(_) => keymap.comments_command(key),
+    }
+  }
+
+  pub(crate) fn handle_key(
+    &mut self,
+    key: KeyEvent,
+    page: usize,
+    keymap: &Keymap,
+  ) -> Command {
+    let command = self.command(key, keymap);
+
+    self.apply(command, page)
+  }
+
+  pub(crate) fn help_headings(&self) -> &'static [&'static str] {
+    match self {
+      Mode::List(_) => &["Navigation:", "Actions:"],
+      Mode::Comments(_) => &["Comments:"],
+    }
+  }
+
+  fn scroll(view: &mut CommentView, command: Command, page: usize) -> bool {
+    let page =
+      isize::try_from(page.saturating_sub(1).max(1)).unwrap_or(isize::MAX);
+
+    match command {
+      Command::SelectNext => view.scroll_by(1),
+      Command::SelectPrevious => view.scroll_by(-1),
+      Command::PageDown => view.scroll_by(page),
+      Command::PageUp => view.scroll_by(-page),
+      Command::SelectFirst => view.scroll_by(isize::MIN),
+      Command::SelectLast => view.scroll_to_end(),
+      _ => return false,
+    }
+
+    true
+  }
+
   pub(crate) fn set_offset(&mut self, offset: usize) {
     match self {
       Mode::List(view) => view.set_offset(offset),
       Mode::Comments(view) => {
-        view.offset = offset;
+        if !view.is_scrolling() {
+          view.offset = offset;
+        }
       }
     }
   }
@@ -124,14 +142,22 @@ mod tests {
   fn sample_list_entries() -> Vec<ListEntry> {
     vec![
       ListEntry {
+        comments: None,
         detail: None,
         id: "1".to_string(),
+        score: None,
+        tags: Vec::new(),
+        time: None,
         title: "First".to_string(),
         url: None,
       },
       ListEntry {
+        comments: None,
         detail: None,
         id: "2".to_string(),
+        score: None,
+        tags: Vec::new(),
+        time: None,
         title: "Second".to_string(),
         url: None,
       },
@@ -143,20 +169,32 @@ mod tests {
   }
 
   fn make_comments_mode() -> Mode {
-    Mode::Comments(CommentView::new(
+    Mode::Comments(Box::new(CommentView::new(
       CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
         focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
         roots: vec![Comment {
           author: Some("user".to_string()),
           children: Vec::new(),
           dead: false,
           deleted: false,
+          html: None,
           id: 1,
+          links: Vec::new(),
+          loaded: true,
           text: Some("body".to_string()),
+          time: None,
         }],
+        score: None,
+        title: None,
       },
       "https://news.ycombinator.com/item?id=1".to_string(),
-    ))
+    )))
   }
 
   fn key(code: KeyCode) -> KeyEvent {
@@ -166,7 +204,11 @@ mod tests {
   #[test]
   fn quitting_from_list_mode_uses_quit_command() {
     assert_eq!(
-      make_list_mode().handle_key(key(KeyCode::Char('q')), 0),
+      make_list_mode().handle_key(
+        key(KeyCode::Char('q')),
+        0,
+        &Keymap::default()
+      ),
       Command::Quit
     );
   }
@@ -174,7 +216,11 @@ mod tests {
   #[test]
   fn starting_search_from_comments_mode_returns_command() {
     assert_eq!(
-      make_comments_mode().handle_key(key(KeyCode::Char('/')), 0),
+      make_comments_mode().handle_key(
+        key(KeyCode::Char('/')),
+        0,
+        &Keymap::default()
+      ),
       Command::StartSearch
     );
   }
@@ -183,7 +229,10 @@ mod tests {
   fn end_key_in_list_mode_selects_last_item() {
     let mut mode = make_list_mode();
 
-    assert_eq!(mode.handle_key(key(KeyCode::End), 0), Command::None);
+    assert_eq!(
+      mode.handle_key(key(KeyCode::End), 0, &Keymap::default()),
+      Command::None
+    );
 
     if let Mode::List(ref view) = mode {
       assert_eq!(view.selected_index(), Some(1));
@@ -196,10 +245,28 @@ mod tests {
   fn navigation_keys_in_list_mode_return_expected_commands() {
     let mut mode = make_list_mode();
 
-    let next = mode.handle_key(key(KeyCode::Down), 0);
+    let next = mode.handle_key(key(KeyCode::Down), 0, &Keymap::default());
     assert_eq!(next, Command::SelectNext);
 
-    let prev = mode.handle_key(key(KeyCode::Up), 0);
+    let prev = mode.handle_key(key(KeyCode::Up), 0, &Keymap::default());
     assert_eq!(prev, Command::SelectPrevious);
   }
+
+  #[test]
+  fn select_index_is_applied_in_comments_and_passed_through_in_list() {
+    let mut comments = make_comments_mode();
+
+    assert_eq!(comments.apply(Command::SelectIndex(0), 0), Command::None);
+
+    if let Mode::Comments(ref view) = comments {
+      assert_eq!(view.selected_entry().map(|entry| entry.id), Some(1));
+    } else {
+      panic!("expected comments mode");
+    }
+
+    assert_eq!(
+      make_list_mode().apply(Command::SelectIndex(1), 0),
+      Command::SelectIndex(1)
+    );
+  }
 }
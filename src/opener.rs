@@ -0,0 +1,113 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Opener {
+  Browser,
+  Clipboard,
+  Command,
+}
+
+impl Opener {
+  pub(crate) const DEFAULT_ORDER: [Self; 3] =
+    [Self::Browser, Self::Command, Self::Clipboard];
+
+  pub(crate) fn browser_command() -> Option<String> {
+    env::var("HN_BROWSER")
+      .ok()
+      .filter(|command| !command.trim().is_empty())
+  }
+
+  fn command_line(template: &str, url: &str) -> Option<(String, Vec<String>)> {
+    let mut parts = template.split_whitespace();
+
+    let program = parts.next()?.to_string();
+
+    let mut args = parts.map(str::to_string).collect::<Vec<String>>();
+
+    if args.iter().any(|arg| arg.contains("%u")) {
+      for arg in &mut args {
+        *arg = arg.replace("%u", url);
+      }
+    } else {
+      args.push(url.to_string());
+    }
+
+    Some((program, args))
+  }
+
+  pub(crate) fn name(self) -> &'static str {
+    match self {
+      Self::Browser => "browser",
+      Self::Clipboard => "clipboard",
+      Self::Command => "$HN_BROWSER",
+    }
+  }
+
+  pub(crate) fn run_command(url: &str) -> Result {
+    let command = env::var("HN_BROWSER").context("`HN_BROWSER` is not set")?;
+
+    let (program, args) =
+      Self::command_line(&command, url).context("`HN_BROWSER` is empty")?;
+
+    process::Command::new(&program)
+      .args(args)
+      .stdin(Stdio::null())
+      .stdout(Stdio::null())
+      .stderr(Stdio::null())
+      .spawn()
+      .with_context(|| format!("failed to run `{program}`"))?;
+
+    Ok(())
+  }
+
+  pub(crate) fn success_message(self, url: &str) -> String {
+    let url = truncate(url, 80);
+
+    match self {
+      Self::Browser => format!("Opened in browser: {url}"),
+      Self::Clipboard => format!("Copied link to clipboard: {url}"),
+      Self::Command => format!("Opened with $HN_BROWSER: {url}"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn command_line_substitutes_url_placeholder() {
+    let url = "https://example.com/a b?c=$(d)";
+
+    assert_eq!(
+      Opener::command_line("firefox -P hn --new-tab %u", url),
+      Some((
+        "firefox".to_string(),
+        vec![
+          "-P".to_string(),
+          "hn".to_string(),
+          "--new-tab".to_string(),
+          url.to_string(),
+        ]
+      ))
+    );
+  }
+
+  #[test]
+  fn command_line_appends_url_without_placeholder() {
+    assert_eq!(
+      Opener::command_line("open -a Safari", "https://example.com"),
+      Some((
+        "open".to_string(),
+        vec![
+          "-a".to_string(),
+          "Safari".to_string(),
+          "https://example.com".to_string(),
+        ]
+      ))
+    );
+
+    assert_eq!(Opener::command_line("  ", "https://example.com"), None);
+  }
+}
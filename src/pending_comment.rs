@@ -2,5 +2,7 @@ use super::*;
 
 pub(crate) struct PendingComment {
   pub(crate) comment_link: String,
+  pub(crate) focus: Option<u64>,
+  pub(crate) listing: bool,
   pub(crate) request_id: u64,
 }
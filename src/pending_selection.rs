@@ -0,0 +1,6 @@
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PendingSelection {
+  pub(crate) generation: u64,
+  pub(crate) index: usize,
+  pub(crate) offset: Option<usize>,
+}
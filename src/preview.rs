@@ -0,0 +1,102 @@
+use super::*;
+
+pub(crate) struct Preview {
+  changed_at: Instant,
+  error: Option<String>,
+  item_id: u64,
+  request_id: Option<u64>,
+  view: Option<CommentView>,
+}
+
+impl Preview {
+  pub(crate) const DEBOUNCE: Duration = Duration::from_millis(300);
+
+  pub(crate) const ROOT_COUNT: usize = 5;
+
+  pub(crate) fn error(&self) -> Option<&str> {
+    self.error.as_deref()
+  }
+
+  pub(crate) fn is_due(&self) -> bool {
+    self.request_id.is_none() && self.changed_at.elapsed() >= Self::DEBOUNCE
+  }
+
+  pub(crate) fn is_for(&self, item_id: u64) -> bool {
+    self.item_id == item_id
+  }
+
+  pub(crate) fn is_request(&self, request_id: u64) -> bool {
+    self.request_id == Some(request_id)
+  }
+
+  pub(crate) fn new(item_id: u64) -> Self {
+    Self {
+      changed_at: Instant::now(),
+      error: None,
+      item_id,
+      request_id: None,
+      view: None,
+    }
+  }
+
+  pub(crate) fn roots(&self) -> Option<Vec<&CommentEntry>> {
+    self.view.as_ref().map(|view| {
+      view
+        .entries
+        .iter()
+        .filter(|entry| entry.parent.is_none())
+        .take(Self::ROOT_COUNT)
+        .collect()
+    })
+  }
+
+  pub(crate) fn set_comments(&mut self, comments: Vec<Comment>) {
+    let link = format!("https://news.ycombinator.com/item?id={}", self.item_id);
+
+    self.view = Some(CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: self.item_id,
+        op_author: None,
+        partial: false,
+        roots: comments,
+        score: None,
+        title: None,
+      },
+      link,
+    ));
+  }
+
+  pub(crate) fn set_error(&mut self, error: String) {
+    self.error = Some(error);
+  }
+
+  pub(crate) fn set_request(&mut self, request_id: u64) {
+    self.request_id = Some(request_id);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn preview_is_due_once_after_debounce() {
+    let mut preview = Preview::new(42);
+
+    assert!(!preview.is_due());
+
+    preview.changed_at = Instant::now().checked_sub(Preview::DEBOUNCE).unwrap();
+
+    assert!(preview.is_due());
+
+    preview.set_request(7);
+
+    assert!(!preview.is_due());
+    assert!(preview.is_request(7));
+    assert!(preview.roots().is_none());
+  }
+}
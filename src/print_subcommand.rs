@@ -0,0 +1,29 @@
+use super::*;
+
+#[derive(Debug, clap::Subcommand)]
+pub(crate) enum PrintSubcommand {
+  #[command(external_subcommand)]
+  Category(Vec<String>),
+  #[command(about = "Print a markdown digest of the front page")]
+  Digest(Digest),
+  #[command(about = "Print your bookmarks as markdown or json")]
+  Export(Export),
+}
+
+impl PrintSubcommand {
+  pub(crate) async fn run(
+    self,
+    client: &Client,
+    keep_tracking: bool,
+  ) -> Result {
+    match self {
+      Self::Category(arguments) => {
+        CategoryListing::parse(arguments)
+          .run(client, keep_tracking)
+          .await
+      }
+      Self::Digest(digest) => digest.run(client, keep_tracking).await,
+      Self::Export(export) => export.run(keep_tracking),
+    }
+  }
+}
@@ -0,0 +1,183 @@
+use super::*;
+
+#[derive(Debug, Default)]
+pub(crate) struct ReadItems {
+  backup: Option<PathBuf>,
+  ids: HashSet<String>,
+  path: Option<PathBuf>,
+}
+
+impl ReadItems {
+  const LIMIT: usize = 10_000;
+
+  pub(crate) fn backup(&self) -> Option<&Path> {
+    self.backup.as_deref()
+  }
+
+  pub(crate) fn contains(&self, id: &str) -> bool {
+    self.ids.contains(id)
+  }
+
+  pub(crate) fn insert(&mut self, id: &str) -> Result<bool> {
+    if !self.ids.insert(id.to_string()) {
+      return Ok(false);
+    }
+
+    self.persist()?;
+
+    Ok(true)
+  }
+
+  pub(crate) fn insert_all<'a>(
+    &mut self,
+    ids: impl IntoIterator<Item = &'a str>,
+  ) -> Result<usize> {
+    let count = ids
+      .into_iter()
+      .filter(|id| self.ids.insert((*id).to_string()))
+      .count();
+
+    if count > 0 {
+      self.persist()?;
+    }
+
+    Ok(count)
+  }
+
+  pub(crate) fn load() -> Self {
+    let Ok(path) = Cache::dir().map(|dir| dir.join("read.json")) else {
+      return Self::default();
+    };
+
+    Self::load_from(path)
+  }
+
+  pub(crate) fn load_from(path: PathBuf) -> Self {
+    let (ids, backup) = read_json_file::<HashSet<String>>(&path)
+      .unwrap_or_else(|_| (HashSet::new(), None));
+
+    Self {
+      backup,
+      ids,
+      path: Some(path),
+    }
+  }
+
+  fn persist(&mut self) -> Result {
+    self.prune();
+
+    let Some(path) = &self.path else {
+      return Ok(());
+    };
+
+    ensure_parent_dir(path)?;
+
+    let mut ids = self.ids.iter().collect::<Vec<&String>>();
+
+    ids.sort();
+
+    fs::write(path, serde_json::to_vec_pretty(&ids)?)?;
+
+    Ok(())
+  }
+
+  fn prune(&mut self) {
+    if self.ids.len() <= Self::LIMIT {
+      return;
+    }
+
+    let mut ids = self.ids.drain().collect::<Vec<String>>();
+
+    ids.sort_by_key(|id| Reverse(id.parse::<u64>().unwrap_or_default()));
+    ids.truncate(Self::LIMIT);
+
+    self.ids = ids.into_iter().collect();
+  }
+
+  pub(crate) fn toggle(&mut self, id: &str) -> Result<bool> {
+    let read = if self.ids.remove(id) {
+      false
+    } else {
+      self.ids.insert(id.to_string())
+    };
+
+    self.persist()?;
+
+    Ok(read)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn temp_read_file() -> PathBuf {
+    let unique = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .unwrap()
+      .as_nanos();
+
+    env::temp_dir()
+      .join(format!("hn_read_test_{unique}"))
+      .join("read.json")
+  }
+
+  #[test]
+  fn read_items_round_trip_through_file() {
+    let path = temp_read_file();
+
+    let mut read = ReadItems::load_from(path.clone());
+
+    assert!(!read.contains("1"));
+
+    assert!(read.insert("1").unwrap());
+    assert!(!read.insert("1").unwrap());
+    assert_eq!(read.insert_all(["1", "2", "3"]).unwrap(), 2);
+    assert!(!read.toggle("3").unwrap());
+
+    let reloaded = ReadItems::load_from(path.clone());
+
+    assert!(reloaded.contains("1"));
+    assert!(reloaded.contains("2"));
+    assert!(!reloaded.contains("3"));
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+  }
+
+  #[test]
+  fn oldest_items_are_forgotten_past_the_limit() {
+    let path = temp_read_file();
+
+    let mut read = ReadItems::load_from(path.clone());
+
+    let ids = (1..=ReadItems::LIMIT + 1)
+      .map(|id| id.to_string())
+      .collect::<Vec<String>>();
+
+    read.insert_all(ids.iter().map(String::as_str)).unwrap();
+
+    let reloaded = ReadItems::load_from(path.clone());
+
+    assert!(!reloaded.contains("1"));
+    assert!(reloaded.contains("2"));
+    assert!(reloaded.contains(&(ReadItems::LIMIT + 1).to_string()));
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+  }
+
+  #[test]
+  fn corrupt_file_is_backed_up_and_ignored() {
+    let path = temp_read_file();
+
+    ensure_parent_dir(&path).unwrap();
+
+    fs::write(&path, "not json").unwrap();
+
+    let read = ReadItems::load_from(path.clone());
+
+    assert!(!read.contains("1"));
+    assert!(read.backup().is_some());
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+  }
+}
@@ -0,0 +1,14 @@
+use super::*;
+
+#[derive(Clone, Copy)]
+pub(crate) struct RenderContext<'a> {
+  pub(crate) contrast: Contrast,
+  pub(crate) followed_users: &'a FollowedUsers,
+  pub(crate) now: u64,
+  pub(crate) query: Option<&'a str>,
+  pub(crate) separator: Separator,
+  pub(crate) theme: Theme,
+  pub(crate) tree_guides: TreeGuides,
+  pub(crate) width: u16,
+  pub(crate) wrap: bool,
+}
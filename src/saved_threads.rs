@@ -0,0 +1,152 @@
+use super::*;
+
+#[derive(Debug, Default)]
+pub(crate) struct SavedThreads {
+  dir: Option<PathBuf>,
+  entries: Vec<ListEntry>,
+}
+
+impl SavedThreads {
+  pub(crate) fn entries_vec(&self) -> Vec<ListEntry> {
+    self.entries.clone()
+  }
+
+  pub(crate) fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  pub(crate) fn load() -> Self {
+    let Ok(dir) = Cache::dir().map(|dir| dir.join("saved")) else {
+      return Self::default();
+    };
+
+    Self::load_from(dir)
+  }
+
+  pub(crate) fn load_from(dir: PathBuf) -> Self {
+    let mut threads = fs::read_dir(&dir)
+      .into_iter()
+      .flatten()
+      .flatten()
+      .filter_map(|entry| {
+        let modified = entry.metadata().ok()?.modified().ok()?;
+
+        let thread = serde_json::from_slice::<CommentThread>(
+          &fs::read(entry.path()).ok()?,
+        )
+        .ok()?;
+
+        Some((modified, thread))
+      })
+      .collect::<Vec<(SystemTime, CommentThread)>>();
+
+    threads.sort_by_key(|(modified, _)| Reverse(*modified));
+
+    Self {
+      dir: Some(dir),
+      entries: threads
+        .into_iter()
+        .map(|(_, thread)| ListEntry::from(&thread))
+        .collect(),
+    }
+  }
+
+  fn path(&self, id: u64) -> Result<PathBuf> {
+    let dir = self
+      .dir
+      .as_ref()
+      .context("could not determine the cache directory")?;
+
+    Ok(dir.join(format!("{id}.json")))
+  }
+
+  pub(crate) fn read(&self, id: u64) -> Result<CommentThread> {
+    let path = self.path(id)?;
+
+    let contents = fs::read(&path)
+      .with_context(|| format!("could not read {}", path.display()))?;
+
+    serde_json::from_slice(&contents)
+      .with_context(|| format!("could not parse {}", path.display()))
+  }
+
+  pub(crate) fn save(&mut self, thread: &CommentThread) -> Result {
+    let path = self.path(thread.id)?;
+
+    ensure_parent_dir(&path)?;
+
+    fs::write(&path, serde_json::to_vec(thread)?)
+      .with_context(|| format!("could not write {}", path.display()))?;
+
+    let entry = ListEntry::from(thread);
+
+    self.entries.retain(|saved| saved.id != entry.id);
+    self.entries.insert(0, entry);
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn temp_saved_dir() -> PathBuf {
+    let unique = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .unwrap()
+      .as_nanos();
+
+    env::temp_dir().join(format!("hn_saved_test_{unique}"))
+  }
+
+  fn thread(id: u64, title: &str) -> CommentThread {
+    CommentThread {
+      body: None,
+      body_links: Vec::new(),
+      descendants: Some(0),
+      focus: None,
+      id,
+      op_author: Some("pg".into()),
+      partial: false,
+      roots: Vec::new(),
+      score: Some(10),
+      title: Some(title.into()),
+    }
+  }
+
+  #[test]
+  fn saved_threads_round_trip_through_directory() {
+    let dir = temp_saved_dir();
+
+    let mut saved = SavedThreads::load_from(dir.clone());
+
+    assert!(saved.is_empty());
+
+    saved.save(&thread(1, "First")).unwrap();
+    saved.save(&thread(2, "Second")).unwrap();
+    saved.save(&thread(1, "First again")).unwrap();
+
+    assert_eq!(
+      saved
+        .entries_vec()
+        .iter()
+        .map(|entry| entry.title.as_str())
+        .collect::<Vec<&str>>(),
+      ["First again", "Second"]
+    );
+
+    let reloaded = SavedThreads::load_from(dir.clone());
+
+    assert_eq!(reloaded.entries_vec().len(), 2);
+    assert_eq!(reloaded.read(2).unwrap().title.as_deref(), Some("Second"));
+    assert!(reloaded.read(3).is_err());
+
+    fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[test]
+  fn saving_without_a_cache_directory_fails() {
+    assert!(SavedThreads::default().save(&thread(1, "First")).is_err());
+  }
+}
@@ -3,6 +3,7 @@ use super::*;
 #[derive(Debug, Deserialize)]
 pub(crate) struct SearchHit {
   pub(crate) author: Option<String>,
+  pub(crate) created_at_i: Option<u64>,
   #[serde(rename = "objectID")]
   pub(crate) object_id: String,
   pub(crate) points: Option<u64>,
@@ -1,18 +1,35 @@
 pub(crate) struct SearchInput {
   pub(crate) buffer: String,
+  label: &'static str,
   pub(crate) message_backup: String,
+  pub(crate) search_tab: Option<usize>,
 }
 
 impl SearchInput {
-  pub(crate) fn new(message_backup: String) -> Self {
+  pub(crate) fn new(label: &'static str, message_backup: String) -> Self {
     Self {
       buffer: String::new(),
+      label,
       message_backup,
+      search_tab: None,
     }
   }
 
   pub(crate) fn prompt(&self) -> String {
-    format!("Search: {}", self.buffer)
+    format!("{}: {}", self.label, self.buffer)
+  }
+
+  pub(crate) fn with_buffer(
+    label: &'static str,
+    buffer: String,
+    message_backup: String,
+  ) -> Self {
+    Self {
+      buffer,
+      label,
+      message_backup,
+      search_tab: None,
+    }
   }
 }
 
@@ -22,10 +39,23 @@ mod tests {
 
   #[test]
   fn prompt_reflects_current_buffer() {
-    let mut input = SearchInput::new("status".to_string());
+    let mut input = SearchInput::new("Search", "status".to_string());
     assert_eq!(input.prompt(), "Search: ");
 
     input.buffer.push_str("rust");
     assert_eq!(input.prompt(), "Search: rust");
   }
+
+  #[test]
+  fn with_buffer_seeds_the_prompt() {
+    let input =
+      SearchInput::with_buffer("Search", "rust".to_string(), String::new());
+    assert_eq!(input.prompt(), "Search: rust");
+  }
+
+  #[test]
+  fn prompt_uses_label() {
+    let input = SearchInput::new("Filter", "status".to_string());
+    assert_eq!(input.prompt(), "Filter: ");
+  }
 }
@@ -0,0 +1,29 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum SearchSort {
+  Date,
+  Points,
+  #[default]
+  Relevance,
+}
+
+impl Display for SearchSort {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      Self::Date => "date",
+      Self::Points => "points",
+      Self::Relevance => "relevance",
+    })
+  }
+}
+
+impl SearchSort {
+  pub(crate) fn next(self) -> Self {
+    match self {
+      Self::Date => Self::Points,
+      Self::Points => Self::Relevance,
+      Self::Relevance => Self::Date,
+    }
+  }
+}
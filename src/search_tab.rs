@@ -0,0 +1,7 @@
+use super::*;
+
+pub(crate) struct SearchTab {
+  pub(crate) query: String,
+  pub(crate) sort: SearchSort,
+  pub(crate) tab_index: usize,
+}
@@ -0,0 +1,79 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default)]
+pub(crate) struct Separator {
+  character: char,
+  #[serde(deserialize_with = "deserialize_color")]
+  color: Color,
+  enabled: bool,
+}
+
+impl Default for Separator {
+  fn default() -> Self {
+    Self {
+      character: '─',
+      color: Color::DarkGray,
+      enabled: false,
+    }
+  }
+}
+
+impl Separator {
+  pub(crate) fn line(
+    &self,
+    indent: &str,
+    available_width: u16,
+  ) -> Line<'static> {
+    if !self.enabled {
+      return Line::from(Span::raw(indent.to_string()));
+    }
+
+    let width =
+      (available_width as usize).saturating_sub(indent.chars().count());
+
+    Line::from(vec![
+      Span::raw(indent.to_string()),
+      Span::styled(
+        self.character.to_string().repeat(width),
+        Style::default().fg(self.color),
+      ),
+    ])
+  }
+}
+
+fn deserialize_color<'de, D>(deserializer: D) -> Result<Color, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let name = String::deserialize(deserializer)?;
+
+  name
+    .parse::<Color>()
+    .map_err(|_| de::Error::custom(format!("invalid color `{name}`")))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn disabled_separator_is_blank() {
+    let line = Separator::default().line(" ", 10);
+
+    assert_eq!(line.to_string(), " ");
+  }
+
+  #[test]
+  fn enabled_separator_renders_configured_character() {
+    let separator = toml::from_str::<Separator>(
+      "enabled = true\ncharacter = \"·\"\ncolor = \"blue\"",
+    )
+    .unwrap();
+
+    let line = separator.line("  ", 6);
+
+    assert_eq!(line.to_string(), "  ····");
+    assert_eq!(line.spans[1].style.fg, Some(Color::Blue));
+  }
+}
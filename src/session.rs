@@ -0,0 +1,118 @@
+use super::*;
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Session {
+  #[serde(default)]
+  active_tab: Option<String>,
+  #[serde(skip)]
+  backup: Option<PathBuf>,
+  #[serde(default)]
+  contrast: Contrast,
+  #[serde(skip)]
+  path: PathBuf,
+  #[serde(default)]
+  tabs: BTreeMap<String, TabPosition>,
+}
+
+impl Session {
+  pub(crate) fn active_tab(&self) -> Option<&str> {
+    self.active_tab.as_deref()
+  }
+
+  pub(crate) fn backup(&self) -> Option<&Path> {
+    self.backup.as_deref()
+  }
+
+  pub(crate) fn contrast(&self) -> Contrast {
+    self.contrast
+  }
+
+  pub(crate) fn load() -> Result<Self> {
+    Self::load_from(config_path("HN_SESSION_FILE", "session.json")?)
+  }
+
+  fn load_from(path: PathBuf) -> Result<Self> {
+    let (mut session, backup) = read_json_file::<Self>(&path)?;
+
+    session.backup = backup;
+    session.path = path;
+
+    Ok(session)
+  }
+
+  pub(crate) fn save(&self) -> Result {
+    ensure_parent_dir(&self.path)?;
+
+    fs::write(&self.path, serde_json::to_vec_pretty(self)?)?;
+
+    Ok(())
+  }
+
+  pub(crate) fn set_active_tab(&mut self, label: Option<&str>) {
+    self.active_tab = label.map(str::to_string);
+  }
+
+  pub(crate) fn set_contrast(&mut self, contrast: Contrast) {
+    self.contrast = contrast;
+  }
+
+  pub(crate) fn set_tab_position(
+    &mut self,
+    label: &str,
+    position: TabPosition,
+  ) {
+    self.tabs.insert(label.to_string(), position);
+  }
+
+  pub(crate) fn tab_position(&self, label: &str) -> Option<TabPosition> {
+    self.tabs.get(label).copied()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    std::sync::atomic::{AtomicUsize, Ordering},
+  };
+
+  static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+  fn temp_session_file() -> PathBuf {
+    env::temp_dir().join(format!(
+      "hn_session_test_{}_{}.json",
+      process::id(),
+      COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+  }
+
+  #[test]
+  fn load_from_missing_file_is_empty() {
+    let session = Session::load_from(temp_session_file()).unwrap();
+    assert_eq!(session.tab_position("top"), None);
+  }
+
+  #[test]
+  fn tab_positions_round_trip_through_file() {
+    let path = temp_session_file();
+
+    let mut session = Session::load_from(path.clone()).unwrap();
+
+    let position = TabPosition {
+      offset: 4,
+      selected: 7,
+    };
+
+    session.set_tab_position("top", position);
+    session.set_active_tab(Some("ask"));
+    session.save().unwrap();
+
+    let reloaded = Session::load_from(path.clone()).unwrap();
+
+    assert_eq!(reloaded.active_tab(), Some("ask"));
+    assert_eq!(reloaded.tab_position("top"), Some(position));
+    assert_eq!(reloaded.tab_position("new"), None);
+
+    let _ = fs::remove_file(path);
+  }
+}
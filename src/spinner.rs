@@ -0,0 +1,39 @@
+#[derive(Debug, Default)]
+pub(crate) struct Spinner {
+  frame: usize,
+}
+
+impl Spinner {
+  const FRAMES: [&'static str; 10] =
+    ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+  pub(crate) fn advance(&mut self) {
+    self.frame = (self.frame + 1) % Self::FRAMES.len();
+  }
+
+  pub(crate) fn glyph(&self) -> &'static str {
+    Self::FRAMES[self.frame]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn advancing_cycles_through_frames() {
+    let mut spinner = Spinner::default();
+
+    assert_eq!(spinner.glyph(), "⠋");
+
+    spinner.advance();
+
+    assert_eq!(spinner.glyph(), "⠙");
+
+    for _ in 0..9 {
+      spinner.advance();
+    }
+
+    assert_eq!(spinner.glyph(), "⠋");
+  }
+}
@@ -2,32 +2,163 @@ use super::*;
 
 pub(crate) struct State {
   active_tab: usize,
+  batch_size: usize,
   bookmarks: Bookmarks,
   bookmarks_tab_index: Option<usize>,
+  center_selection: bool,
+  command_palette: Option<CommandPalette>,
+  config: Config,
+  confirm_open_links: Option<u64>,
+  confirm_quit: bool,
+  contrast: Contrast,
+  count: Option<usize>,
+  filtering: bool,
+  followed_users: FollowedUsers,
   help: HelpView,
+  key_prefix: Option<(KeyCode, Instant)>,
+  last_search: Option<String>,
   list_height: usize,
+  local_filter: Option<String>,
   message: String,
+  message_timeout: Duration,
+  min_score: u64,
   mode: Mode,
   next_request_id: u64,
   pending_comment: Option<PendingComment>,
+  pending_comment_children: HashMap<u64, u64>,
   pending_effects: Vec<Effect>,
-  pending_search: Option<PendingSearch>,
-  pending_selections: Vec<Option<usize>>,
+  pending_searches: Vec<PendingSearch>,
+  pending_selections: Vec<Option<PendingSelection>>,
+  pinned: HashSet<String>,
+  preview: Option<Preview>,
+  quit_armed_at: Option<Instant>,
+  read_items: ReadItems,
+  saved_tab_index: Option<usize>,
+  saved_threads: SavedThreads,
   search_input: Option<SearchInput>,
-  search_tab_index: Option<usize>,
+  search_tabs: Vec<SearchTab>,
+  split_view: bool,
   tab_loading: Vec<bool>,
+  tab_requests: Vec<Option<u64>>,
+  tab_switcher: Option<TabSwitcher>,
   tab_views: Vec<Option<ListView<ListEntry>>>,
   tabs: Vec<Tab>,
+  tag_filter: Option<String>,
+  tagging: Option<String>,
   transient_message: Option<TransientMessage>,
+  wrap_comments: bool,
 }
 
 impl State {
+  const KEY_PREFIX_TIMEOUT: Duration = Duration::from_millis(750);
+
+  const MAX_COUNT: usize = 9999;
+
+  const MAX_SEARCH_TABS: usize = 3;
+
+  const QUIT_CONFIRM_TIMEOUT: Duration = Duration::from_secs(2);
+
+  pub(crate) fn accent_color(&self) -> Color {
+    self
+      .resolved_active_tab()
+      .and_then(|index| self.tabs.get(index))
+      .map_or_else(
+        || self.config.accent(""),
+        |tab| self.config.accent(tab.category.label),
+      )
+  }
+
+  fn activate_tab(&mut self, index: usize) {
+    self.store_active_list_view();
+
+    if index != self.active_tab {
+      self.set_local_filter(None);
+    }
+
+    self.active_tab = index;
+    self.restore_active_list_view();
+  }
+
+  fn active_search_query(&self) -> Option<String> {
+    self
+      .search_tabs
+      .iter()
+      .find(|search_tab| search_tab.tab_index == self.active_tab)
+      .map(|search_tab| search_tab.query.clone())
+  }
+
+  fn adjust_score_filter(&mut self, delta: i64) {
+    self.min_score = self.min_score.saturating_add_signed(delta);
+
+    let message = match self.min_score {
+      0 => "Showing stories of any score".to_string(),
+      min_score => format!("Hiding stories under {}", format_points(min_score)),
+    };
+
+    self.set_transient_message(message);
+  }
+
+  fn apply_pins(&mut self, tab_index: usize, entries: Vec<ListEntry>) {
+    let pinned = &self.pinned;
+
+    let list = match &mut self.mode {
+      Mode::List(view) if tab_index == self.active_tab => Some(view),
+      _ => self
+        .tab_views
+        .get_mut(tab_index)
+        .and_then(|slot| slot.as_mut()),
+    };
+
+    if let Some(list) = list {
+      list.extend(entries);
+      list.sort_by_key(|entry| !pinned.contains(&entry.id));
+    }
+  }
+
+  pub(crate) fn bookmarks(&self) -> &Bookmarks {
+    &self.bookmarks
+  }
+
+  fn bookmarks_tab() -> Tab {
+    let category = Category {
+      label: "bookmarks",
+      kind: CategoryKind::Bookmarks,
+    };
+
+    Tab {
+      category,
+      has_more: false,
+      label: category.label.into(),
+      loaded: true,
+    }
+  }
+
   fn cancel_search(&mut self) {
+    self.tagging = None;
+
+    if std::mem::take(&mut self.filtering) {
+      self.set_local_filter(None);
+    }
+
     if let Some(input) = self.search_input.take() {
       self.message = input.message_backup;
     }
   }
 
+  fn cancel_tab_load(&mut self, tab_index: usize) {
+    if let Some(flag) = self.tab_loading.get_mut(tab_index) {
+      *flag = false;
+    }
+
+    if let Some(request) = self.tab_requests.get_mut(tab_index) {
+      *request = None;
+    }
+  }
+
+  pub(crate) fn center_selection(&self) -> bool {
+    self.center_selection
+  }
+
   pub(crate) fn clear_pending_effects(&mut self) {
     self.pending_effects.clear();
   }
@@ -35,17 +166,216 @@ impl State {
   fn close_comments(&mut self) {
     self.restore_active_list_view();
 
-    if !self.help.is_visible() {
-      self.message = LIST_STATUS.into();
+    self.refresh_status();
+  }
+
+  fn close_search_tab(&mut self) -> Result {
+    let Some(search_tab) = self
+      .search_tabs
+      .iter()
+      .find(|search_tab| search_tab.tab_index == self.active_tab)
+    else {
+      self.set_transient_message("Only search tabs can be closed".into());
+      return Ok(());
+    };
+
+    self.remove_tab(search_tab.tab_index);
+    self.refresh_status();
+    self.load_active_tab_if_needed()
+  }
+
+  pub(crate) fn command_palette(&self) -> Option<&CommandPalette> {
+    self.command_palette.as_ref()
+  }
+
+  pub(crate) fn command_palette_command(
+    &mut self,
+    key: KeyEvent,
+    page: usize,
+  ) -> Option<Command> {
+    if self.command_palette.is_some() {
+      Some(self.handle_command_palette_key(key, page))
+    } else {
+      None
+    }
+  }
+
+  pub(crate) fn config(&self) -> &Config {
+    &self.config
+  }
+
+  pub(crate) fn contrast(&self) -> Contrast {
+    self.contrast
+  }
+
+  fn copy_comment_link(&mut self) {
+    if let Mode::Comments(view) = &self.mode
+      && let Some(text) = view.selected_comment_link()
+    {
+      self.pending_effects.push(Effect::CopyToClipboard {
+        label: "comment link",
+        text,
+      });
+    }
+  }
+
+  fn copy_comment_text(&mut self) {
+    let Mode::Comments(view) = &self.mode else {
+      return;
+    };
+
+    match view.selected_entry().filter(|entry| !entry.is_fold()) {
+      Some(entry) => {
+        let text = format!(
+          "{} wrote:\n{}",
+          entry.author.as_deref().unwrap_or("unknown"),
+          entry.body()
+        );
+
+        self.pending_effects.push(Effect::CopyToClipboard {
+          label: "comment text",
+          text,
+        });
+      }
+      None => self.set_transient_message("Nothing selected to copy".into()),
+    }
+  }
+
+  fn copy_link(&mut self) {
+    if matches!(self.mode, Mode::Comments(_)) {
+      self.copy_comment_link();
+      return;
+    }
+
+    match self.current_entry() {
+      Some(entry) => {
+        let text = entry.resolved_url(self.config.keep_tracking());
+
+        self.pending_effects.push(Effect::CopyToClipboard {
+          label: "link",
+          text,
+        });
+      }
+      None => self.set_transient_message("Nothing selected to copy".into()),
+    }
+  }
+
+  fn copy_summary(&mut self) {
+    if !matches!(self.mode, Mode::List(_)) {
+      return;
+    }
+
+    match self.current_entry() {
+      Some(entry) => {
+        let text = entry
+          .summary(self.config.summary_format(), self.config.keep_tracking());
+
+        self.pending_effects.push(Effect::CopyToClipboard {
+          label: "summary",
+          text,
+        });
+      }
+      None => self.set_transient_message("Nothing selected to copy".into()),
     }
   }
 
+  pub(crate) fn count(&self) -> Option<usize> {
+    self.count
+  }
+
   fn current_entry(&self) -> Option<&ListEntry> {
     self
       .list_view(self.active_tab)
       .and_then(|view| view.selected_item())
   }
 
+  fn cycle_contrast(&mut self) {
+    self.contrast = self.contrast.next();
+
+    if !self.help.is_visible() {
+      self
+        .set_transient_message(format!("Comment contrast: {}", self.contrast));
+    }
+  }
+
+  fn cycle_search_sort(&mut self) {
+    let active_tab = self.active_tab;
+
+    let Some(search_tab) = self
+      .search_tabs
+      .iter_mut()
+      .find(|search_tab| search_tab.tab_index == active_tab)
+    else {
+      self
+        .set_transient_message("Sorting only applies to search results".into());
+      return;
+    };
+
+    search_tab.sort = search_tab.sort.next();
+
+    let query = search_tab.query.clone();
+
+    self.run_search(query, Some(active_tab));
+  }
+
+  fn cycle_tag_filter(&mut self) {
+    let Some(index) = self
+      .bookmarks_tab_index
+      .filter(|&index| index == self.active_tab)
+    else {
+      self.set_transient_message(
+        "Tag filters only apply to the bookmarks tab".into(),
+      );
+      return;
+    };
+
+    let tags = self.bookmarks.tags();
+
+    if tags.is_empty() && self.tag_filter.is_none() {
+      let hint =
+        self
+          .config
+          .keymap()
+          .press(false, Command::EditTags, "to tag one.");
+
+      self.set_transient_message(
+        ["No tagged bookmarks yet.".to_string()]
+          .into_iter()
+          .chain(hint)
+          .collect::<Vec<_>>()
+          .join(" "),
+      );
+      return;
+    }
+
+    let next = match &self.tag_filter {
+      None => 0,
+      Some(current) => tags
+        .iter()
+        .position(|tag| tag == current)
+        .map_or(0, |position| position + 1),
+    };
+
+    self.tag_filter = tags.get(next).cloned();
+
+    self.refresh_bookmarks_view(index);
+
+    let message = match &self.tag_filter {
+      Some(tag) => format!("Showing bookmarks tagged \"{tag}\""),
+      None => "Showing all bookmarks".to_string(),
+    };
+
+    self.set_transient_message(message);
+  }
+
+  fn default_status(&self) -> String {
+    let category = self.tabs.get(self.active_tab).map(|tab| tab.category.kind);
+
+    let has_selection = self.current_entry().is_some();
+
+    status_for(&self.mode, category, has_selection, self.config.keymap())
+  }
+
   pub(crate) fn dispatch_command(
     &mut self,
     command: Command,
@@ -57,58 +387,167 @@ impl State {
 
     let mut should_exit = false;
 
+    let confirm_open_links = self.confirm_open_links.take();
+
+    let quit_armed_at = self.quit_armed_at.take();
+
     match command {
-      Command::Quit => {
-        should_exit = true;
+      Command::AdjustScoreFilter(delta) => self.adjust_score_filter(delta),
+      Command::Quit => should_exit = self.quit(quit_armed_at),
+      Command::ShowBookmarks => self.show_bookmarks(),
+      Command::ShowHelp => {
+        self
+          .help
+          .show(&mut self.message, &self.mode, self.config.keymap());
       }
-      Command::ShowHelp => self.help.show(&mut self.message),
       Command::HideHelp => self.help.hide(&mut self.message),
+      Command::ScrollHelp(delta) => self.help.scroll_by(delta),
       Command::StartSearch => self.start_search(),
+      Command::RepeatSearch => self.repeat_search(),
       Command::CancelSearch => self.cancel_search(),
       Command::SubmitSearch => self.submit_search()?,
-      Command::SwitchTabLeft => self.switch_tab_left(),
-      Command::SwitchTabRight => self.switch_tab_right(),
+      Command::ShowTabSwitcher => self.show_tab_switcher(),
+      Command::StartLocalFilter => self.start_local_filter(),
+      Command::CancelTabSwitcher => self.tab_switcher = None,
+      Command::ShowCommandPalette => {
+        self.command_palette = Some(CommandPalette::default());
+      }
+      Command::CancelCommandPalette => self.command_palette = None,
+      Command::ClearLocalFilter => self.set_local_filter(None),
+      Command::SubmitTabSwitcher => self.submit_tab_switcher()?,
+      Command::SwitchTabLeft => self.switch_tab_left()?,
+      Command::SwitchTabRight => self.switch_tab_right()?,
+      Command::SwitchToTab(index) => self.switch_to_tab(index)?,
+      Command::RefreshTab => self.refresh_tab()?,
       Command::SelectNext => self.select_next()?,
       Command::SelectPrevious => self.select_previous()?,
       Command::PageDown => self.page_down()?,
       Command::PageUp => self.page_up()?,
       Command::SelectFirst => self.select_index(0)?,
-      Command::OpenComments => self.open_comments()?,
+      Command::SelectIndex(index) => self.select_index(index)?,
+      Command::ShiftDay(delta) => self.shift_day(delta)?,
+      Command::OpenComments => self.open_comments(),
+      Command::OpenCommentsInBrowser => self.open_comments_in_browser(),
       Command::OpenCurrentInBrowser => self.open_current_in_browser(),
+      Command::OpenSectionInBrowser => self.open_section_in_browser(),
+      Command::LoadFullThread => self.load_full_thread(),
+      Command::LoadNewer => self.load_newer(),
       Command::OpenCommentLink => self.open_comment_link(),
+      Command::OpenCommentLinks => self.open_comment_links(confirm_open_links),
       Command::CloseComments => self.close_comments(),
+      Command::CloseSearchTab => self.close_search_tab()?,
+      Command::CopyCommentLink => self.copy_comment_link(),
+      Command::CopyCommentText => self.copy_comment_text(),
+      Command::CopyLink => self.copy_link(),
+      Command::CopySummary => self.copy_summary(),
+      Command::CycleContrast => self.cycle_contrast(),
+      Command::CycleSearchSort => self.cycle_search_sort(),
+      Command::CycleTagFilter => self.cycle_tag_filter(),
+      Command::EditTags => self.edit_tags(),
+      Command::DumpComment => self.dump_comment(),
+      Command::NextMatch => self.find_comment_match(true),
+      Command::PreviousMatch => self.find_comment_match(false),
+      Command::SaveThread => self.save_thread(),
       Command::ToggleBookmark => self.toggle_bookmark()?,
-      Command::None => {}
+      Command::ToggleCenterSelection => self.toggle_center_selection(),
+      Command::ToggleCommentWrap => self.toggle_comment_wrap(),
+      Command::ToggleFollow => self.toggle_follow()?,
+      Command::TogglePin => self.toggle_pin(),
+      Command::ToggleRead => self.toggle_read()?,
+      Command::ToggleSplitView => self.toggle_split_view(),
+      Command::ViewInPager => self.view_in_pager(),
+      Command::MarkAllRead => self.mark_all_read()?,
+      Command::CollapseAll
+      | Command::CollapseComment
+      | Command::CollapseSubtree
+      | Command::ExpandAll
+      | Command::ExpandComment
+      | Command::NextRoot
+      | Command::PreviousRoot
+      | Command::ScrollLeft
+      | Command::ScrollRight
+      | Command::SelectLast
+      | Command::SelectNextSibling
+      | Command::SelectNextUnread
+      | Command::SelectRootNext
+      | Command::ToggleComment
+      | Command::ToggleFlat
+      | Command::ToggleFocus
+      | Command::ToggleHideDead
+      | Command::ToggleListing
+      | Command::ToggleScroll
+      | Command::None => {}
     }
 
+    self.request_comment_children();
+
+    self.snap_selection(self.active_tab);
+
     Ok(CommandDispatch {
       effects: std::mem::take(&mut self.pending_effects),
       should_exit,
     })
   }
 
+  fn dump_comment(&mut self) {
+    let Mode::Comments(view) = &self.mode else {
+      return;
+    };
+
+    let Some(entry) = view.selected_entry() else {
+      return;
+    };
+
+    let path = env::temp_dir().join(format!("hn-comment-{}.txt", entry.id));
+
+    let message = match fs::write(&path, entry.diagnostics()) {
+      Ok(()) => format!("Wrote comment diagnostics to {}", path.display()),
+      Err(error) => format!("Could not write comment diagnostics: {error}"),
+    };
+
+    self.set_transient_message(message);
+  }
+
+  fn edit_tags(&mut self) {
+    if self.search_input.is_some() {
+      return;
+    }
+
+    let Some(entry) = self.current_entry() else {
+      return;
+    };
+
+    let id = entry.id.clone();
+
+    let Some(tags) = self.bookmarks.tags_for(&id) else {
+      self.set_transient_message("Bookmark this item before tagging it".into());
+      return;
+    };
+
+    self.search_input = Some(SearchInput::with_buffer(
+      "Tags",
+      tags.join(", "),
+      self.message.clone(),
+    ));
+    self.tagging = Some(id);
+
+    self.update_search_message();
+  }
+
   fn ensure_bookmarks_tab(&mut self) -> usize {
     if let Some(index) = self.bookmarks_tab_index {
       return index;
     }
 
-    let entries = self.bookmarks.entries_vec();
+    let entries = self.filtered_bookmarks();
 
     let tab_index = self.tabs.len();
 
-    let category = Category {
-      label: "bookmarks",
-      kind: CategoryKind::Bookmarks,
-    };
-
-    self.tabs.push(Tab {
-      category,
-      has_more: false,
-      label: category.label,
-    });
+    self.tabs.push(Self::bookmarks_tab());
 
     self.tab_views.push(Some(ListView::new(entries)));
     self.tab_loading.push(false);
+    self.tab_requests.push(None);
     self.pending_selections.push(None);
     self.bookmarks_tab_index = Some(tab_index);
 
@@ -132,49 +571,214 @@ impl State {
       return Ok(());
     }
 
+    let generation = self
+      .list_view(tab_index)
+      .map_or(0, ListView::<ListEntry>::selection_generation);
+
     if let Some(slot) = self.pending_selections.get_mut(tab_index) {
-      *slot = Some(target_index);
+      *slot = Some(PendingSelection {
+        generation,
+        index: target_index,
+        offset: None,
+      });
     }
 
     let is_loading = self.tab_loading.get(tab_index).copied().unwrap_or(false);
 
     if !is_loading {
-      self.start_load_for_tab(tab_index)?;
+      self.start_load_for_tab(tab_index, false)?;
     }
 
     Ok(())
   }
 
-  fn ensure_search_tab(&mut self) -> usize {
-    if let Some(index) = self.search_tab_index {
+  fn ensure_saved_tab(&mut self) -> usize {
+    if let Some(index) = self.saved_tab_index {
       return index;
     }
 
     let tab_index = self.tabs.len();
 
+    self.tabs.push(Tab {
+      category: Category {
+        label: "saved",
+        kind: CategoryKind::Saved,
+      },
+      has_more: false,
+      label: "saved".into(),
+      loaded: true,
+    });
+
+    self
+      .tab_views
+      .push(Some(ListView::new(self.saved_threads.entries_vec())));
+    self.tab_loading.push(false);
+    self.tab_requests.push(None);
+    self.pending_selections.push(None);
+    self.saved_tab_index = Some(tab_index);
+
+    tab_index
+  }
+
+  fn ensure_search_tab(&mut self, query: &str, reuse: Option<usize>) -> usize {
+    let label = format!("\"{}\"", truncate(query, 20));
+
+    let existing = reuse
+      .and_then(|index| {
+        self
+          .search_tabs
+          .iter()
+          .position(|search_tab| search_tab.tab_index == index)
+      })
+      .or_else(|| {
+        self
+          .search_tabs
+          .iter()
+          .position(|search_tab| search_tab.query == query)
+      })
+      .or_else(|| {
+        (self.search_tabs.len() >= Self::MAX_SEARCH_TABS).then_some(0)
+      });
+
+    if let Some(position) = existing {
+      let mut search_tab = self.search_tabs.remove(position);
+
+      if search_tab.query != query {
+        search_tab.query = query.to_string();
+        search_tab.sort = SearchSort::default();
+      }
+
+      if let Some(tab) = self.tabs.get_mut(search_tab.tab_index) {
+        tab.label = label;
+      }
+
+      let tab_index = search_tab.tab_index;
+
+      self.search_tabs.push(search_tab);
+
+      return tab_index;
+    }
+
+    let tab_index = self.tabs.len();
+
     self.tabs.push(Tab {
       category: Category {
         label: "search",
         kind: CategoryKind::Search,
       },
       has_more: false,
-      label: "search",
+      label,
+      loaded: true,
     });
 
     self.tab_views.push(Some(ListView::default()));
     self.tab_loading.push(false);
+    self.tab_requests.push(None);
     self.pending_selections.push(None);
-    self.search_tab_index = Some(tab_index);
+    self.search_tabs.push(SearchTab {
+      query: query.to_string(),
+      sort: SearchSort::default(),
+      tab_index,
+    });
 
     tab_index
   }
 
+  fn filtered_bookmarks(&self) -> Vec<ListEntry> {
+    let mut entries = self.bookmarks.entries_vec();
+
+    if let Some(tag) = &self.tag_filter {
+      entries.retain(|entry| entry.tags.contains(tag));
+    }
+
+    entries
+  }
+
+  fn find_comment_match(&mut self, forward: bool) {
+    let Mode::Comments(view) = &mut self.mode else {
+      return;
+    };
+
+    let Some(query) = view.search_query().map(str::to_string) else {
+      let hint = self
+        .config
+        .keymap()
+        .press(true, Command::StartSearch, "to find text in this thread")
+        .unwrap_or_else(|| "Search this thread to find text".into());
+
+      self.set_transient_message(hint);
+      return;
+    };
+
+    let found = if forward {
+      view.next_match()
+    } else {
+      view.prev_match()
+    };
+
+    if !found {
+      self.set_transient_message(format!("No comments match \"{query}\""));
+    }
+  }
+
+  fn finish_tab_request(&mut self, request_id: u64) -> Option<usize> {
+    let tab_index = self
+      .tab_requests
+      .iter()
+      .position(|request| *request == Some(request_id))?;
+
+    self.cancel_tab_load(tab_index);
+
+    Some(tab_index)
+  }
+
+  pub(crate) fn followed_users(&self) -> &FollowedUsers {
+    &self.followed_users
+  }
+
+  fn handle_command_palette_key(
+    &mut self,
+    key: KeyEvent,
+    page: usize,
+  ) -> Command {
+    let entries = self.palette_entries();
+
+    let Some(palette) = self.command_palette.as_mut() else {
+      return Command::None;
+    };
+
+    match key.code {
+      KeyCode::Esc => return Command::CancelCommandPalette,
+      KeyCode::Enter => {
+        let command = palette.selected_command(&entries);
+
+        self.command_palette = None;
+
+        return command
+          .map_or(Command::None, |command| self.mode.apply(command, page));
+      }
+      KeyCode::Up => palette.select_previous(),
+      KeyCode::Down => palette.select_next(&entries),
+      KeyCode::Backspace => palette.pop(),
+      KeyCode::Char(ch)
+        if !key.modifiers.intersects(
+          KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER,
+        ) =>
+      {
+        palette.push(ch);
+      }
+      _ => {}
+    }
+
+    Command::None
+  }
+
   pub(crate) fn handle_event(&mut self, event: Event) {
     match event {
-      Event::TabItems { tab_index, result } => {
-        if let Some(flag) = self.tab_loading.get_mut(tab_index) {
-          *flag = false;
-        }
+      Event::TabItems { request_id, result } => {
+        let Some(tab_index) = self.finish_tab_request(request_id) else {
+          return;
+        };
 
         let target = self
           .pending_selections
@@ -184,49 +788,82 @@ impl State {
         match result {
           Ok(entries) => {
             if let Some(tab) = self.tabs.get_mut(tab_index) {
-              tab.has_more = entries.len() >= INITIAL_BATCH_SIZE;
+              tab.has_more = entries.len() >= self.batch_size;
+              tab.loaded = true;
             }
 
-            if let Some(list) = self.list_view_mut(tab_index) {
-              if !entries.is_empty() {
-                list.extend(entries);
+            self.apply_pins(tab_index, entries);
+
+            if let Some(list) = self.list_view_mut(tab_index)
+              && let Some(target) = target
+              && target.generation == list.selection_generation()
+            {
+              if target.index < list.len() {
+                list.set_selected(target.index);
+              } else if !list.is_empty() {
+                list.set_selected(list.len().saturating_sub(1));
               }
 
-              if let Some(target) = target {
-                if target < list.len() {
-                  list.set_selected(target);
-                } else if !list.is_empty() {
-                  list.set_selected(list.len().saturating_sub(1));
-                }
+              if let Some(offset) = target.offset {
+                list.set_offset(offset);
               }
             }
 
+            self.snap_selection(tab_index);
+
             if !self.help.is_visible() {
-              self.message = LIST_STATUS.into();
+              self.message = self.default_status();
             }
           }
           Err(error) => {
             if !self.help.is_visible() {
-              self.set_transient_message(format!(
-                "Could not load more entries: {error}"
-              ));
-            }
-          }
+              let message = match self.tabs.get(tab_index) {
+                Some(tab) if !tab.loaded => {
+                  format!("Could not load {}: {error}", tab.label)
+                }
+                _ => format!("Could not load more entries: {error}"),
+              };
+
+              self.set_transient_message(message);
+            }
+          }
         }
       }
-      Event::SearchResults { request_id, result } => {
-        let Some(pending) = self.pending_search.as_ref() else {
+      Event::NewerTabItems { request_id, result } => {
+        let Some(tab_index) = self.finish_tab_request(request_id) else {
           return;
         };
 
-        if pending.request_id != request_id {
-          return;
+        match result {
+          Ok(entries) => self.prepend_newer(tab_index, entries),
+          Err(error) => self.set_transient_message(format!(
+            "Could not load newer entries: {error}"
+          )),
         }
-
-        let Some(pending) = self.pending_search.take() else {
+      }
+      Event::Preview { request_id, result } => {
+        if let Some(preview) = self
+          .preview
+          .as_mut()
+          .filter(|preview| preview.is_request(request_id))
+        {
+          match result {
+            Ok(comments) => preview.set_comments(comments),
+            Err(error) => preview.set_error(error.to_string()),
+          }
+        }
+      }
+      Event::SearchResults { request_id, result } => {
+        let Some(position) = self
+          .pending_searches
+          .iter()
+          .position(|pending| pending.request_id == request_id)
+        else {
           return;
         };
 
+        let pending = self.pending_searches.remove(position);
+
         if let Some(flag) = self.tab_loading.get_mut(pending.tab_index) {
           *flag = false;
         }
@@ -252,7 +889,11 @@ impl State {
               *slot = Some(view);
             }
 
-            if !self.help.is_visible() {
+            self.apply_pins(pending.tab_index, Vec::new());
+
+            if self.search_input.is_some() {
+              self.update_search_message();
+            } else if !self.help.is_visible() {
               let truncated = truncate(&pending.query, 40);
 
               self.message = match result_count {
@@ -271,6 +912,33 @@ impl State {
           }
         }
       }
+      Event::CommentChildren { request_id, result } => {
+        let Some(item_id) = self.pending_comment_children.remove(&request_id)
+        else {
+          return;
+        };
+
+        let Mode::Comments(view) = &mut self.mode else {
+          return;
+        };
+
+        match result {
+          Ok(children) => view.insert_children(item_id, children),
+          Err(error) => {
+            if let Some(entry) =
+              view.entries.iter_mut().find(|entry| entry.id == item_id)
+            {
+              entry.expanded = false;
+            }
+
+            if !self.help.is_visible() {
+              self.set_transient_message(format!(
+                "Could not load replies: {error}"
+              ));
+            }
+          }
+        }
+      }
       Event::Comments { request_id, result } => {
         let Some(pending) = self.pending_comment.as_ref() else {
           return;
@@ -285,16 +953,12 @@ impl State {
         };
 
         match result {
-          Ok(thread) => {
-            let view = CommentView::new(thread, pending.comment_link);
-
-            self.store_active_list_view();
-
-            self.mode = Mode::Comments(view);
-
-            if !self.help.is_visible() {
-              self.message = COMMENTS_STATUS.into();
+          Ok(mut thread) => {
+            if pending.focus.is_some() {
+              thread.focus = pending.focus;
             }
+
+            self.show_thread(thread, pending.comment_link, pending.listing);
           }
           Err(error) => {
             if !self.help.is_visible() {
@@ -308,6 +972,100 @@ impl State {
     }
   }
 
+  pub(crate) fn handle_key(&mut self, key: KeyEvent, page: usize) -> Command {
+    if key.code == KeyCode::Esc
+      && key.modifiers == KeyModifiers::NONE
+      && self.local_filter.is_some()
+      && matches!(self.mode, Mode::List(_))
+    {
+      return Command::ClearLocalFilter;
+    }
+
+    if let KeyCode::Char(ch) = key.code
+      && key.modifiers == KeyModifiers::NONE
+      && let Some(digit) = ch.to_digit(10)
+      && (digit > 0 || self.count.is_some())
+      && self.mode.command(key, self.config.keymap()) == Command::None
+    {
+      let count = self
+        .count
+        .unwrap_or(0)
+        .saturating_mul(10)
+        .saturating_add(digit as usize)
+        .min(Self::MAX_COUNT);
+
+      self.count = Some(count);
+
+      return Command::None;
+    }
+
+    if let Some(count) = self.count.take() {
+      let command = self.mode.command(key, self.config.keymap());
+
+      if matches!(
+        command,
+        Command::PageDown
+          | Command::PageUp
+          | Command::SelectNext
+          | Command::SelectPrevious
+      ) {
+        if let Mode::List(_) = self.mode {
+          self.count = Some(count);
+          return command;
+        }
+
+        for _ in 0..count {
+          self.mode.apply(command, page);
+        }
+
+        return Command::None;
+      }
+    }
+
+    let prefix = self
+      .key_prefix
+      .take()
+      .filter(|(_, pressed)| pressed.elapsed() < Self::KEY_PREFIX_TIMEOUT)
+      .map(|(code, _)| code);
+
+    if let KeyCode::Char(ch @ ('g' | 'z')) = key.code
+      && key.modifiers == KeyModifiers::NONE
+      && self.mode.command(key, self.config.keymap()) == Command::None
+    {
+      if prefix == Some(key.code) {
+        return if ch == 'g' {
+          self.mode.apply(Command::SelectFirst, page)
+        } else {
+          Command::ToggleCenterSelection
+        };
+      }
+
+      self.key_prefix = Some((key.code, Instant::now()));
+
+      return Command::None;
+    }
+
+    self.mode.handle_key(key, page, self.config.keymap())
+  }
+
+  pub(crate) fn handle_mouse(
+    &mut self,
+    command: Command,
+    page: usize,
+  ) -> Command {
+    self.count = None;
+
+    if self.help.is_visible()
+      || self.search_input.is_some()
+      || self.tab_switcher.is_some()
+      || self.command_palette.is_some()
+    {
+      return Command::None;
+    }
+
+    self.mode.apply(command, page)
+  }
+
   fn handle_search_key(&mut self, key: KeyEvent) -> Command {
     if self.search_input.is_none() {
       return Command::None;
@@ -347,19 +1105,80 @@ impl State {
     }
   }
 
-  pub(crate) fn help(&self) -> &HelpView {
-    &self.help
+  fn handle_tab_switcher_key(&mut self, key: KeyEvent) -> Command {
+    let tabs = self.switcher_tabs();
+
+    let Some(switcher) = self.tab_switcher.as_mut() else {
+      return Command::None;
+    };
+
+    match key.code {
+      KeyCode::Esc => return Command::CancelTabSwitcher,
+      KeyCode::Enter => return Command::SubmitTabSwitcher,
+      KeyCode::Up => switcher.select_previous(),
+      KeyCode::Down => switcher.select_next(&tabs),
+      KeyCode::Backspace => switcher.pop(),
+      KeyCode::Char(ch)
+        if !key.modifiers.intersects(
+          KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER,
+        ) =>
+      {
+        switcher.push(ch);
+      }
+      _ => {}
+    }
+
+    Command::None
   }
 
   pub(crate) fn help_is_visible(&self) -> bool {
     self.help.is_visible()
   }
 
+  pub(crate) fn help_mut(&mut self) -> &mut HelpView {
+    &mut self.help
+  }
+
+  pub(crate) fn incremental_query(&self) -> Option<&str> {
+    if self.tagging.is_some()
+      || self.filtering
+      || matches!(self.mode, Mode::Comments(_))
+    {
+      return None;
+    }
+
+    self
+      .search_input
+      .as_ref()
+      .map(|input| input.buffer.as_str())
+  }
+
+  pub(crate) fn is_filtering(&self) -> bool {
+    self.filtering
+  }
+
+  pub(crate) fn is_loading(&self) -> bool {
+    self.pending_comment.is_some() || self.tab_loading.contains(&true)
+  }
+
+  pub(crate) fn is_pinned(&self, id: &str) -> bool {
+    self.pinned.contains(id)
+  }
+
+  pub(crate) fn is_read(&self, id: &str) -> bool {
+    self.read_items.contains(id)
+  }
+
+  fn is_shown(&self, entry: &ListEntry) -> bool {
+    entry.meets_score(self.min_score)
+      && entry.matches_filter(self.local_filter.as_deref())
+  }
+
   pub(crate) fn list_height(&self) -> usize {
     self.list_height
   }
 
-  fn list_view(&self, index: usize) -> Option<&ListView<ListEntry>> {
+  pub(crate) fn list_view(&self, index: usize) -> Option<&ListView<ListEntry>> {
     if index >= self.tabs.len() {
       return None;
     }
@@ -387,10 +1206,131 @@ impl State {
     }
   }
 
+  fn load_active_tab_if_needed(&mut self) -> Result {
+    let needs_load = self
+      .tabs
+      .get(self.active_tab)
+      .is_some_and(|tab| !tab.loaded);
+
+    if needs_load {
+      self.start_load_for_tab(self.active_tab, false)?;
+    }
+
+    Ok(())
+  }
+
+  fn load_full_thread(&mut self) {
+    let Mode::Comments(view) = &self.mode else {
+      return;
+    };
+
+    if !view.is_partial() {
+      return;
+    }
+
+    let request_id = self.next_request_id;
+
+    self.next_request_id = self.next_request_id.wrapping_add(1);
+
+    self.pending_comment = Some(PendingComment {
+      comment_link: view.link().to_string(),
+      focus: None,
+      listing: view.is_listing(),
+      request_id,
+    });
+
+    self.pending_effects.push(Effect::FetchComments {
+      fresh: true,
+      item_id: view.item_id(),
+      request_id,
+      timeout: None,
+    });
+
+    if !self.help.is_visible() {
+      self.message = LOADING_COMMENTS_STATUS.into();
+    }
+  }
+
+  fn load_newer(&mut self) {
+    if !matches!(self.mode, Mode::List(_)) {
+      return;
+    }
+
+    let tab_index = self.active_tab;
+
+    let Some(category) = self
+      .tabs
+      .get(tab_index)
+      .map(|tab| tab.category)
+      .filter(|category| matches!(category.kind, CategoryKind::Comments))
+    else {
+      self.set_transient_message(
+        "Only the comments tab can load newer entries".into(),
+      );
+      return;
+    };
+
+    if self.tab_loading.get(tab_index).copied().unwrap_or(false) {
+      return;
+    }
+
+    let request_id = self.start_tab_request(tab_index);
+
+    self.pending_effects.push(Effect::FetchNewerTabItems {
+      category,
+      request_id,
+    });
+  }
+
+  pub(crate) fn local_filter(&self) -> Option<&str> {
+    self.local_filter.as_deref()
+  }
+
+  fn mark_all_read(&mut self) -> Result {
+    let Some(view) = self.list_view(self.active_tab) else {
+      return Ok(());
+    };
+
+    let ids = view
+      .items()
+      .iter()
+      .filter(|entry| self.is_shown(entry))
+      .map(|entry| entry.id.clone())
+      .collect::<Vec<String>>();
+
+    let count = self.read_items.insert_all(ids.iter().map(String::as_str))?;
+
+    if !self.help.is_visible() {
+      let message = match count {
+        0 => "Everything in this tab is already read".to_string(),
+        1 => "Marked 1 item as read".to_string(),
+        count => format!("Marked {count} items as read"),
+      };
+
+      self.set_transient_message(message);
+    }
+
+    Ok(())
+  }
+
+  fn mark_read(&mut self, id: &str) {
+    if let Err(error) = self.read_items.insert(id) {
+      self.set_transient_message(format!("Could not mark as read: {error}"));
+    }
+  }
+
   pub(crate) fn message(&self) -> &str {
     &self.message
   }
 
+  pub(crate) fn min_score(&self) -> u64 {
+    self.min_score
+  }
+
+  pub(crate) fn mode(&self) -> &Mode {
+    &self.mode
+  }
+
   pub(crate) fn mode_mut(&mut self) -> &mut Mode {
     &mut self.mode
   }
@@ -398,6 +1338,10 @@ impl State {
   pub(crate) fn new(
     tabs: Vec<(Tab, ListView<ListEntry>)>,
     bookmarks: Bookmarks,
+    read_items: ReadItems,
+    saved_threads: SavedThreads,
+    followed_users: FollowedUsers,
+    config: Config,
   ) -> Self {
     let (mut tab_views, mut tab_meta) = (Vec::new(), Vec::new());
 
@@ -414,32 +1358,119 @@ impl State {
     let tab_count = tab_meta.len();
 
     let tab_loading = vec![false; tab_count];
+    let tab_requests = vec![None; tab_count];
     let pending_selections = vec![None; tab_count];
 
+    let wrap_comments = config.wrap_comments;
+
+    let message = list_status(config.keymap());
+
     let mut state = Self {
       active_tab: 0,
+      batch_size: Client::DEFAULT_BATCH_SIZE,
       bookmarks,
       bookmarks_tab_index: None,
+      center_selection: false,
+      command_palette: None,
+      config,
+      confirm_open_links: None,
+      confirm_quit: env::var_os("HN_CONFIRM_QUIT")
+        .is_some_and(|value| value == "1"),
+      contrast: Contrast::default(),
+      count: None,
+      filtering: false,
+      followed_users,
       help: HelpView::new(),
+      key_prefix: None,
+      last_search: None,
       list_height: 0,
-      message: LIST_STATUS.into(),
+      local_filter: None,
+      message,
+      message_timeout: TransientMessage::DEFAULT_TIMEOUT,
+      min_score: 0,
       mode: Mode::List(initial_view),
       next_request_id: 0,
       pending_comment: None,
+      pending_comment_children: HashMap::new(),
       pending_effects: Vec::new(),
-      pending_search: None,
+      pending_searches: Vec::new(),
       pending_selections,
+      pinned: HashSet::new(),
+      preview: None,
+      quit_armed_at: None,
+      read_items,
+      saved_tab_index: None,
+      saved_threads,
       search_input: None,
-      search_tab_index: None,
+      search_tabs: Vec::new(),
+      split_view: false,
       tab_loading,
+      tab_requests,
+      tab_switcher: None,
       tab_views,
       tabs: tab_meta,
+      tag_filter: None,
+      tagging: None,
       transient_message: None,
+      wrap_comments,
     };
 
-    if !state.bookmarks.is_empty() {
-      let index = state.ensure_bookmarks_tab();
-      state.refresh_bookmarks_view(index);
+    if !state.config.defer_bookmarks {
+      state.reveal_bookmarks_tab();
+    }
+
+    if !state.saved_threads.is_empty() {
+      state.ensure_saved_tab();
+    }
+
+    state.refresh_status();
+
+    let mut notices = Vec::new();
+
+    if let Some(backup) = state.bookmarks.backup() {
+      notices.push(format!(
+        "Bookmarks file was corrupt, moved it to {}",
+        backup.display()
+      ));
+    }
+
+    if let Some(backup) = state.read_items.backup() {
+      notices.push(format!(
+        "Read items file was corrupt, moved it to {}",
+        backup.display()
+      ));
+    }
+
+    if let Some(backup) = state.followed_users.backup() {
+      notices.push(format!(
+        "Followed users file was corrupt, moved it to {}",
+        backup.display()
+      ));
+    }
+
+    if let Some(backup) = state.config.backup() {
+      notices.push(format!(
+        "Config file was corrupt, moved it to {}",
+        backup.display()
+      ));
+    }
+
+    let keymap = state.config.keymap();
+
+    if let Some(backup) = keymap.backup() {
+      notices.push(format!(
+        "Keys file was corrupt, moved it to {}",
+        backup.display()
+      ));
+    } else if !keymap.errors().is_empty() {
+      notices.push(format!(
+        "Invalid key bindings: {}",
+        keymap.errors().join("; ")
+      ));
+    }
+
+    if !notices.is_empty() {
+      state.set_transient_message(notices.join(DETAIL_SEPARATOR));
     }
 
     state
@@ -455,67 +1486,151 @@ impl State {
     }
   }
 
-  fn open_comments(&mut self) -> Result {
+  fn open_comment_links(&mut self, confirmed: Option<u64>) {
+    let Mode::Comments(view) = &self.mode else {
+      return;
+    };
+
+    let Some(entry) = view.selected_entry() else {
+      return;
+    };
+
+    let (id, mut links) = (entry.id, entry.links.clone());
+
+    if links.is_empty() {
+      self.set_transient_message("No links in this comment".into());
+      return;
+    }
+
+    let total = links.len();
+
+    links.truncate(LINK_BATCH_LIMIT);
+
+    if links.len() > LINK_BATCH_CONFIRM_THRESHOLD && confirmed != Some(id) {
+      self.confirm_open_links = Some(id);
+
+      self.set_transient_message(self.press_again(
+        Command::OpenCommentLinks,
+        &format!("open {} links", links.len()),
+      ));
+
+      return;
+    }
+
+    self.set_transient_message(match (links.len(), total) {
+      (1, 1) => "Opened 1 link".into(),
+      (opened, total) if opened == total => format!("Opened {opened} links"),
+      (opened, total) => format!("Opened {opened} of {total} links"),
+    });
+
+    self
+      .pending_effects
+      .extend(links.into_iter().map(|url| Effect::OpenUrl { url }));
+  }
+
+  fn open_comments(&mut self) {
     let Some(entry) = self.current_entry() else {
-      return Ok(());
+      return;
     };
 
+    let is_jobs = self
+      .tabs
+      .get(self.active_tab)
+      .is_some_and(|tab| tab.category.kind.is_jobs());
+
+    if is_jobs
+      && entry.comments.unwrap_or(0) == 0
+      && entry.url.as_deref().is_some_and(|url| !url.is_empty())
+    {
+      let entry_id = entry.id.clone();
+
+      self.open_current_in_browser();
+      self.mark_read(&entry_id);
+
+      return;
+    }
+
     let entry_id = entry.id.clone();
 
+    let listing = entry.is_hiring_thread();
+
     let id = match entry_id.parse::<u64>() {
       Ok(id) => id,
       Err(error) => {
         self.set_transient_message(format!("Could not load comments: {error}"));
-        return Ok(());
+        return;
       }
     };
 
-    if !self.help.is_visible() {
-      self.message = LOADING_COMMENTS_STATUS.into();
+    if self.saved_tab_index == Some(self.active_tab) {
+      match self.saved_threads.read(id) {
+        Ok(thread) => self.show_thread(
+          thread,
+          format!("https://news.ycombinator.com/item?id={id}"),
+          listing,
+        ),
+        Err(error) => self.set_transient_message(format!(
+          "Could not open saved thread: {error}"
+        )),
+      }
+    } else {
+      self.request_comments(id, listing, None);
     }
 
-    let comment_link =
-      format!("https://news.ycombinator.com/item?id={entry_id}");
-
-    let request_id = self.next_request_id;
-
-    self.next_request_id = self.next_request_id.wrapping_add(1);
-
-    self.pending_comment = Some(PendingComment {
-      comment_link,
-      request_id,
-    });
+    self.mark_read(&entry_id);
+  }
 
-    self.pending_effects.push(Effect::FetchComments {
-      item_id: id,
-      request_id,
-    });
+  fn open_comments_in_browser(&mut self) {
+    if !matches!(self.mode, Mode::List(_)) {
+      return;
+    }
 
-    Ok(())
+    if let Some(entry) = self.current_entry() {
+      self.pending_effects.push(Effect::OpenUrl {
+        url: entry.comments_url(),
+      });
+    }
   }
 
   fn open_current_in_browser(&mut self) {
     if let Some(entry) = self.current_entry() {
       self.pending_effects.push(Effect::OpenUrl {
-        url: entry.resolved_url(),
+        url: entry.resolved_url(self.config.keep_tracking()),
       });
     }
   }
 
-  fn page_down(&mut self) -> Result {
-    if self.tabs.is_empty() {
-      return Ok(());
-    }
+  pub(crate) fn open_item(&mut self, id: u64, comment: Option<u64>) {
+    self.request_comments(id, false, comment);
+  }
 
-    let tab_index = self.active_tab.min(self.tabs.len().saturating_sub(1));
+  fn open_section_in_browser(&mut self) {
+    let Some(tab) = self.tabs.get(self.active_tab) else {
+      return;
+    };
 
-    let current = self
-      .list_view(tab_index)
-      .map_or(0, ListView::<ListEntry>::selected_raw);
+    if let Some(url) = tab.category.web_url() {
+      self
+        .pending_effects
+        .push(Effect::OpenUrl { url: url.into() });
+    } else {
+      let message = format!("The {} tab has no page on Hacker News", tab.label);
+      self.set_transient_message(message);
+    }
+  }
+
+  fn page_down(&mut self) -> Result {
+    let count = self.take_count();
+
+    if self.tabs.is_empty() {
+      return Ok(());
+    }
+
+    let tab_index = self.active_tab.min(self.tabs.len().saturating_sub(1));
 
-    let jump = self.page_jump();
+    let jump = self.page_jump().saturating_mul(count);
 
-    self.select_index(current.saturating_add(jump))
+    self.select_index(self.shown_index(tab_index, jump, true))
   }
 
   fn page_jump(&self) -> usize {
@@ -523,23 +1638,116 @@ impl State {
   }
 
   fn page_up(&mut self) -> Result {
+    let count = self.take_count();
+
     if self.tabs.is_empty() {
       return Ok(());
     }
 
     let tab_index = self.active_tab.min(self.tabs.len().saturating_sub(1));
 
-    let current = self
-      .list_view(tab_index)
-      .map_or(0, ListView::<ListEntry>::selected_raw);
+    let jump = self.page_jump().saturating_mul(count);
+
+    self.select_index(self.shown_index(tab_index, jump, false))
+  }
+
+  pub(crate) fn palette_entries(&self) -> Vec<(Command, String, &'static str)> {
+    self
+      .config
+      .keymap()
+      .palette_entries(matches!(self.mode, Mode::Comments(_)))
+  }
+
+  fn prepend_newer(&mut self, tab_index: usize, entries: Vec<ListEntry>) {
+    let pinned = &self.pinned;
+
+    let list = match &mut self.mode {
+      Mode::List(view) if tab_index == self.active_tab => Some(view),
+      _ => self
+        .tab_views
+        .get_mut(tab_index)
+        .and_then(|slot| slot.as_mut()),
+    };
+
+    let Some(list) = list else {
+      return;
+    };
+
+    let seen = list
+      .items()
+      .iter()
+      .map(|entry| entry.id.clone())
+      .collect::<HashSet<String>>();
+
+    let newer = entries
+      .into_iter()
+      .filter(|entry| !seen.contains(&entry.id))
+      .collect::<Vec<ListEntry>>();
+
+    let count = newer.len();
+
+    let selected = list.selected_item().map(|entry| entry.id.clone());
+
+    list.prepend(newer);
+    list.sort_by_key(|entry| !pinned.contains(&entry.id));
+
+    if let Some(index) = selected.and_then(|selected| {
+      list.items().iter().position(|entry| entry.id == selected)
+    }) {
+      list.set_selected(index);
+    }
+
+    self.set_transient_message(match count {
+      0 => "No new comments".into(),
+      1 => "1 new comment".into(),
+      count => format!("{count} new comments"),
+    });
+  }
+
+  fn press_again(&self, command: Command, action: &str) -> String {
+    let comments = matches!(self.mode, Mode::Comments(_));
+
+    self
+      .config
+      .keymap()
+      .press(comments, command, &format!("again to {action}"))
+      .unwrap_or_else(|| format!("Run it again to {action}"))
+  }
 
-    let jump = self.page_jump();
+  pub(crate) fn preview(&self) -> Option<&Preview> {
+    self.preview.as_ref()
+  }
+
+  fn quit(&mut self, armed_at: Option<Instant>) -> bool {
+    let has_unsaved_state = matches!(self.mode, Mode::Comments(_))
+      || !self.pending_searches.is_empty()
+      || self.search_input.is_some();
+
+    if !self.confirm_quit
+      || !has_unsaved_state
+      || armed_at
+        .is_some_and(|armed_at| armed_at.elapsed() < Self::QUIT_CONFIRM_TIMEOUT)
+    {
+      return true;
+    }
+
+    self.quit_armed_at = Some(Instant::now());
 
-    self.select_index(current.saturating_sub(jump))
+    self.set_transient_message(self.press_again(Command::Quit, "quit"));
+
+    false
   }
 
   fn refresh_bookmarks_view(&mut self, tab_index: usize) {
-    let entries = self.bookmarks.entries_vec();
+    if self
+      .tag_filter
+      .as_ref()
+      .is_some_and(|tag| !self.bookmarks.tags().contains(tag))
+    {
+      self.tag_filter = None;
+    }
+
+    let entries = self.filtered_bookmarks();
 
     if let Some(view) = self.list_view_mut(tab_index) {
       let selected = view.selected_index().unwrap_or(0);
@@ -568,24 +1776,111 @@ impl State {
 
       *slot = Some(view);
     }
+
+    self.apply_pins(tab_index, Vec::new());
   }
 
-  fn remove_bookmarks_tab(&mut self) {
-    let Some(index) = self.bookmarks_tab_index.take() else {
+  fn refresh_status(&mut self) {
+    if self.help.is_visible()
+      || self.search_input.is_some()
+      || self.transient_message.is_some()
+    {
       return;
+    }
+
+    self.message = self.default_status();
+  }
+
+  fn refresh_tab(&mut self) -> Result {
+    if !matches!(self.mode, Mode::List(_)) {
+      return Ok(());
+    }
+
+    let tab_index = self.active_tab;
+
+    let Some(tab) = self.tabs.get_mut(tab_index) else {
+      return Ok(());
     };
 
+    match tab.category.kind {
+      CategoryKind::Bookmarks | CategoryKind::Saved => return Ok(()),
+      CategoryKind::Search => {
+        if let Some(query) = self.active_search_query() {
+          self.run_search(query, Some(tab_index));
+        }
+
+        return Ok(());
+      }
+      CategoryKind::Comments
+      | CategoryKind::Front { .. }
+      | CategoryKind::Stories(_) => {}
+    }
+
+    tab.has_more = true;
+
+    self.cancel_tab_load(tab_index);
+
+    let selected = self
+      .list_view(tab_index)
+      .and_then(ListView::selected_index)
+      .unwrap_or(0);
+
+    let mut generation = 0;
+
+    if let Some(list) = self.list_view_mut(tab_index) {
+      *list = ListView::default();
+      generation = list.selection_generation();
+    }
+
+    if let Some(slot) = self.pending_selections.get_mut(tab_index) {
+      *slot = Some(PendingSelection {
+        generation,
+        index: selected,
+        offset: None,
+      });
+    }
+
+    self.start_load_for_tab(tab_index, true)
+  }
+
+  fn remove_bookmarks_tab(&mut self) {
+    if let Some(index) = self.bookmarks_tab_index {
+      self.remove_tab(index);
+    }
+  }
+
+  fn remove_tab(&mut self, index: usize) {
     if self.active_tab == index {
       self.mode = Mode::List(ListView::default());
     } else if self.active_tab > index {
       self.active_tab = self.active_tab.saturating_sub(1);
     }
 
-    if let Some(search_index) = self.search_tab_index {
-      if search_index == index {
-        self.search_tab_index = None;
-      } else if search_index > index {
-        self.search_tab_index = Some(search_index.saturating_sub(1));
+    for slot in [&mut self.bookmarks_tab_index, &mut self.saved_tab_index] {
+      *slot = match *slot {
+        Some(current) if current == index => None,
+        Some(current) if current > index => Some(current - 1),
+        current => current,
+      };
+    }
+
+    self
+      .search_tabs
+      .retain(|search_tab| search_tab.tab_index != index);
+
+    for search_tab in &mut self.search_tabs {
+      if search_tab.tab_index > index {
+        search_tab.tab_index -= 1;
+      }
+    }
+
+    self
+      .pending_searches
+      .retain(|pending| pending.tab_index != index);
+
+    for pending in &mut self.pending_searches {
+      if pending.tab_index > index {
+        pending.tab_index -= 1;
       }
     }
 
@@ -601,6 +1896,10 @@ impl State {
       self.tab_loading.remove(index);
     }
 
+    if index < self.tab_requests.len() {
+      self.tab_requests.remove(index);
+    }
+
     if index < self.pending_selections.len() {
       self.pending_selections.remove(index);
     }
@@ -611,180 +1910,177 @@ impl State {
     }
   }
 
-  pub(crate) fn resolved_active_tab(&self) -> Option<usize> {
-    if self.tabs.is_empty() {
-      None
-    } else {
-      Some(self.active_tab.min(self.tabs.len().saturating_sub(1)))
+  fn repeat_search(&mut self) {
+    if self.search_input.is_some() || !matches!(self.mode, Mode::List(_)) {
+      return;
     }
-  }
 
-  fn restore_active_list_view(&mut self) {
-    if let Some(slot) = self.tab_views.get_mut(self.active_tab) {
-      if let Some(view) = slot.take() {
-        self.mode = Mode::List(view);
-      } else if !matches!(self.mode, Mode::List(_)) {
-        self.mode = Mode::List(ListView::default());
-      }
-    } else if !matches!(self.mode, Mode::List(_)) {
-      self.mode = Mode::List(ListView::default());
-    }
-  }
+    let Some(query) = self.last_search.clone() else {
+      self.set_transient_message("No previous search".into());
+      return;
+    };
 
-  pub(crate) fn search_input_command(
-    &mut self,
-    key: KeyEvent,
-  ) -> Option<Command> {
-    if self.search_input.is_some() {
-      Some(self.handle_search_key(key))
-    } else {
-      None
-    }
+    self.search_input = Some(SearchInput::with_buffer(
+      "Search",
+      query,
+      self.message.clone(),
+    ));
+
+    self.update_search_message();
   }
 
-  fn select_index(&mut self, target: usize) -> Result {
-    if self.tabs.is_empty() {
-      return Ok(());
-    }
+  fn request_comment_children(&mut self) {
+    let Mode::Comments(view) = &self.mode else {
+      return;
+    };
 
-    let tab_index = self.active_tab.min(self.tabs.len().saturating_sub(1));
+    for item_id in view.unloaded_expanded() {
+      if self
+        .pending_comment_children
+        .values()
+        .any(|&pending| pending == item_id)
+      {
+        continue;
+      }
 
-    self.ensure_item(tab_index, target)?;
+      let request_id = self.next_request_id;
 
-    if let Some(list) = self.list_view_mut(tab_index) {
-      if target >= list.len() {
-        return Ok(());
-      }
+      self.next_request_id = self.next_request_id.wrapping_add(1);
 
-      list.set_selected(target);
-    }
+      self.pending_comment_children.insert(request_id, item_id);
 
-    Ok(())
+      self.pending_effects.push(Effect::FetchCommentChildren {
+        item_id,
+        request_id,
+      });
+    }
   }
 
-  fn select_next(&mut self) -> Result {
-    if self.tabs.is_empty() {
-      return Ok(());
+  fn request_comments(&mut self, id: u64, listing: bool, focus: Option<u64>) {
+    if !self.help.is_visible() {
+      self.message = LOADING_COMMENTS_STATUS.into();
     }
 
-    let tab_index = self.active_tab.min(self.tabs.len().saturating_sub(1));
+    let comment_link = format!("https://news.ycombinator.com/item?id={id}");
 
-    let current = self
-      .list_view(tab_index)
-      .map_or(0, ListView::<ListEntry>::selected_raw);
+    let request_id = self.next_request_id;
+
+    self.next_request_id = self.next_request_id.wrapping_add(1);
+
+    self.pending_comment = Some(PendingComment {
+      comment_link,
+      focus,
+      listing,
+      request_id,
+    });
 
-    self.select_index(current.saturating_add(1))
+    self.pending_effects.push(Effect::FetchComments {
+      fresh: false,
+      item_id: id,
+      request_id,
+      timeout: self.config.comment_timeout(),
+    });
   }
 
-  fn select_previous(&mut self) -> Result {
+  pub(crate) fn resolved_active_tab(&self) -> Option<usize> {
     if self.tabs.is_empty() {
-      return Ok(());
+      None
+    } else {
+      Some(self.active_tab.min(self.tabs.len().saturating_sub(1)))
     }
-
-    let tab_index = self.active_tab.min(self.tabs.len().saturating_sub(1));
-
-    let current = self
-      .list_view(tab_index)
-      .map_or(0, ListView::<ListEntry>::selected_raw);
-
-    self.select_index(current.saturating_sub(1))
   }
 
-  pub(crate) fn set_list_height(&mut self, height: usize) {
-    self.list_height = height;
+  fn restore_active_list_view(&mut self) {
+    if let Some(slot) = self.tab_views.get_mut(self.active_tab) {
+      if let Some(view) = slot.take() {
+        self.mode = Mode::List(view);
+      } else if !matches!(self.mode, Mode::List(_)) {
+        self.mode = Mode::List(ListView::default());
+      }
+    } else if !matches!(self.mode, Mode::List(_)) {
+      self.mode = Mode::List(ListView::default());
+    }
   }
 
-  pub(crate) fn set_transient_message(&mut self, message: String) {
-    let original = self.transient_message.as_ref().map_or_else(
-      || self.message.clone(),
-      |transient| transient.original().to_string(),
-    );
+  pub(crate) fn restore_session(&mut self, session: &Session) -> Result {
+    if let Some(backup) = session.backup() {
+      self.set_transient_message(format!(
+        "Session file was corrupt, moved it to {}",
+        backup.display()
+      ));
+    }
 
-    self.transient_message =
-      Some(TransientMessage::new(message.clone(), original));
+    self.contrast = session.contrast();
 
-    self.message = message;
-  }
+    for index in 0..self.tabs.len() {
+      let tab = &self.tabs[index];
 
-  fn start_load_for_tab(&mut self, tab_index: usize) -> Result {
-    let (category, offset) = if let Some(tab) = self.tabs.get(tab_index) {
-      if !tab.has_more {
-        return Ok(());
+      if tab.category.kind.is_dynamic() {
+        continue;
       }
 
-      let offset = self
-        .list_view(tab_index)
-        .map_or(0, ListView::<ListEntry>::len);
+      let Some(position) = session.tab_position(&tab.label) else {
+        continue;
+      };
 
-      (tab.category, offset)
-    } else {
-      return Ok(());
-    };
+      if !tab.loaded {
+        let generation = self
+          .list_view(index)
+          .map_or(0, ListView::<ListEntry>::selection_generation);
+
+        if let Some(slot) = self.pending_selections.get_mut(index) {
+          *slot = Some(PendingSelection {
+            generation,
+            index: position.selected,
+            offset: Some(position.offset),
+          });
+        }
 
-    if let Some(flag) = self.tab_loading.get_mut(tab_index) {
-      if *flag {
-        return Ok(());
+        continue;
       }
 
-      *flag = true;
-    } else {
-      return Ok(());
-    }
-
-    if !self.help.is_visible() {
-      self.message = LOADING_ENTRIES_STATUS.into();
+      if let Some(view) = self.list_view_mut(index) {
+        view.set_selected(position.selected);
+        view.set_offset(position.offset);
+      }
     }
 
-    self.pending_effects.push(Effect::FetchTabItems {
-      tab_index,
-      category,
-      offset,
+    let active_tab = session.active_tab().and_then(|label| {
+      self
+        .tabs
+        .iter()
+        .position(|tab| tab.label == label && !tab.category.kind.is_dynamic())
     });
 
-    Ok(())
-  }
-
-  fn start_search(&mut self) {
-    if self.search_input.is_some() {
-      return;
+    if let Some(index) = active_tab
+      && index != self.active_tab
+    {
+      self.activate_tab(index);
+      self.load_active_tab_if_needed()?;
     }
 
-    let backup = self.message.clone();
-
-    self.search_input = Some(SearchInput::new(backup));
-
-    self.update_search_message();
+    Ok(())
   }
 
-  fn store_active_list_view(&mut self) {
-    if let Mode::List(view) = &mut self.mode
-      && let Some(slot) = self.tab_views.get_mut(self.active_tab)
-    {
-      *slot = Some(std::mem::take(view));
+  fn reveal_bookmarks_tab(&mut self) {
+    if self.bookmarks_tab_index.is_none() && !self.bookmarks.is_empty() {
+      let index = self.ensure_bookmarks_tab();
+      self.refresh_bookmarks_view(index);
     }
   }
 
-  fn submit_search(&mut self) -> Result {
-    let Some(search) = self.search_input.take() else {
-      return Ok(());
-    };
-
-    let query = search.buffer.trim().to_string();
-
-    if query.is_empty() {
-      self.message = search.message_backup;
-      return Ok(());
-    }
-
+  fn run_search(&mut self, query: String, reuse: Option<usize>) {
     if matches!(self.mode, Mode::Comments(_)) {
       self.restore_active_list_view();
     }
 
-    let tab_index = self.ensure_search_tab();
+    let tab_index = self.ensure_search_tab(&query, reuse);
 
-    self.store_active_list_view();
-    self.active_tab = tab_index;
-    self.restore_active_list_view();
+    if let Some(input) = self.search_input.as_mut() {
+      input.search_tab = Some(tab_index);
+    }
+
+    self.activate_tab(tab_index);
 
     if let Some(list) = self.list_view_mut(tab_index) {
       *list = ListView::default();
@@ -804,245 +2100,3590 @@ impl State {
       *flag = true;
     }
 
-    self.pending_search = Some(PendingSearch {
-      query: query.clone(),
-      request_id,
+    self.last_search = Some(query.clone());
+
+    self
+      .pending_searches
+      .retain(|pending| pending.tab_index != tab_index);
+
+    self.pending_searches.push(PendingSearch {
+      query: query.clone(),
+      request_id,
       tab_index,
     });
 
     self.message = format!("Searching for \"{}\"...", truncate(&query, 40));
 
-    self
-      .pending_effects
-      .push(Effect::FetchSearchResults { query, request_id });
+    self.pending_effects.push(Effect::FetchSearchResults {
+      query,
+      request_id,
+      sort: self.search_sort().unwrap_or_default(),
+    });
+  }
+
+  fn save_tags(&mut self, id: &str, input: &str) -> Result {
+    let mut tags = Vec::<String>::new();
+
+    for tag in input
+      .split(|ch: char| ch == ',' || ch.is_whitespace())
+      .filter(|tag| !tag.is_empty())
+    {
+      if !tags.iter().any(|existing| existing == tag) {
+        tags.push(tag.to_string());
+      }
+    }
+
+    let message = if tags.is_empty() {
+      "Cleared tags".to_string()
+    } else {
+      format!("Tagged with {}", tags.join(", "))
+    };
+
+    if self.bookmarks.set_tags(id, tags)? {
+      self.sync_bookmarks_tab();
+      self.set_transient_message(message);
+    }
 
     Ok(())
   }
 
-  fn switch_tab_left(&mut self) {
-    let tab_count = self.tabs.len();
+  fn save_thread(&mut self) {
+    let Mode::Comments(view) = &self.mode else {
+      return;
+    };
 
-    if tab_count != 0 {
-      self.store_active_list_view();
-      self.active_tab = (self.active_tab + tab_count - 1) % tab_count;
-      self.restore_active_list_view();
+    let thread = view.thread();
+
+    if let Err(error) = self.saved_threads.save(&thread) {
+      self.set_transient_message(format!("Could not save thread: {error}"));
+      return;
     }
-  }
 
-  fn switch_tab_right(&mut self) {
-    let tab_count = self.tabs.len();
+    let entries = self.saved_threads.entries_vec();
 
-    if tab_count != 0 {
-      self.store_active_list_view();
-      self.active_tab = (self.active_tab + 1) % tab_count;
-      self.restore_active_list_view();
+    let index = self.ensure_saved_tab();
+
+    if let Some(list) = self.list_view_mut(index) {
+      *list = ListView::new(entries);
     }
-  }
 
-  fn sync_bookmarks_tab(&mut self) {
-    if self.bookmarks.is_empty() {
-      self.remove_bookmarks_tab();
+    let message = if thread.partial {
+      "Saved partial thread for offline reading"
     } else {
-      let index = self.ensure_bookmarks_tab();
-      self.refresh_bookmarks_view(index);
-    }
-  }
+      "Saved thread for offline reading"
+    };
 
-  pub(crate) fn tab(&self, index: usize) -> Option<&Tab> {
-    self.tabs.get(index)
+    self.set_transient_message(message.into());
   }
 
-  pub(crate) fn tab_loading(&self) -> &[bool] {
-    &self.tab_loading
-  }
+  pub(crate) fn search_incrementally(&mut self) {
+    let Some(query) = self.incremental_query().map(str::trim) else {
+      return;
+    };
 
-  pub(crate) fn tabs(&self) -> &[Tab] {
-    &self.tabs
+    if query.is_empty()
+      || hn_item_id(query).is_some()
+      || self.last_search.as_deref() == Some(query)
+    {
+      return;
+    }
+
+    let reuse = self
+      .search_input
+      .as_ref()
+      .and_then(|input| input.search_tab);
+
+    self.run_search(query.to_string(), reuse);
+    self.update_search_message();
   }
 
-  fn toggle_bookmark(&mut self) -> Result {
-    match &mut self.mode {
-      Mode::List(_) => self.toggle_list_bookmark(),
-      Mode::Comments(_) => self.toggle_comment_bookmark(),
+  pub(crate) fn search_input_command(
+    &mut self,
+    key: KeyEvent,
+  ) -> Option<Command> {
+    if self.search_input.is_some() {
+      Some(self.handle_search_key(key))
+    } else {
+      None
     }
   }
 
-  fn toggle_comment_bookmark(&mut self) -> Result {
-    let Mode::Comments(view) = &mut self.mode else {
-      return Ok(());
-    };
+  pub(crate) fn search_sort(&self) -> Option<SearchSort> {
+    self
+      .search_tabs
+      .iter()
+      .find(|search_tab| search_tab.tab_index == self.active_tab)
+      .map(|search_tab| search_tab.sort)
+  }
 
-    let Some(selected) = view.selected_entry() else {
+  fn select_index(&mut self, target: usize) -> Result {
+    if self.tabs.is_empty() {
       return Ok(());
-    };
+    }
 
-    let entry = selected.to_bookmark_entry();
+    let tab_index = self.active_tab.min(self.tabs.len().saturating_sub(1));
 
-    let added = self.bookmarks.toggle(&entry)?;
+    self.ensure_item(tab_index, target)?;
 
-    self.sync_bookmarks_tab();
+    let has_more = self.tabs.get(tab_index).is_some_and(|tab| tab.has_more);
 
-    if !self.help.is_visible() {
-      let title = truncate(&entry.title, 40);
+    if let Some(list) = self.list_view_mut(tab_index) {
+      if target >= list.len() {
+        if !has_more && !list.is_empty() {
+          list.set_selected(list.len() - 1);
+        }
 
-      let message = if added {
-        format!("Bookmarked \"{title}\"")
-      } else {
-        format!("Removed bookmark for \"{title}\"")
-      };
+        return Ok(());
+      }
 
-      self.set_transient_message(message);
+      list.set_selected(target);
     }
 
     Ok(())
   }
 
-  fn toggle_list_bookmark(&mut self) -> Result {
-    let Some(entry) = self.current_entry().cloned() else {
-      return Ok(());
-    };
+  fn select_next(&mut self) -> Result {
+    let count = self.take_count();
 
-    let added = self.bookmarks.toggle(&entry)?;
+    if self.tabs.is_empty() {
+      return Ok(());
+    }
 
-    self.sync_bookmarks_tab();
+    let tab_index = self.active_tab.min(self.tabs.len().saturating_sub(1));
 
-    if !self.help.is_visible() {
-      let title = truncate(&entry.title, 40);
+    self.select_index(self.shown_index(tab_index, count, true))
+  }
 
-      let message = if added {
-        format!("Bookmarked \"{title}\"")
-      } else {
-        format!("Removed bookmark for \"{title}\"")
-      };
+  fn select_previous(&mut self) -> Result {
+    let count = self.take_count();
 
-      self.set_transient_message(message);
+    if self.tabs.is_empty() {
+      return Ok(());
     }
 
-    Ok(())
+    let tab_index = self.active_tab.min(self.tabs.len().saturating_sub(1));
+
+    self.select_index(self.shown_index(tab_index, count, false))
   }
 
-  fn update_search_message(&mut self) {
-    if let Some(input) = &self.search_input {
-      let prompt = input.prompt();
-      self.message = truncate(&prompt, 80);
+  pub(crate) fn set_batch_size(&mut self, batch_size: usize) {
+    self.batch_size = batch_size;
+  }
+
+  pub(crate) fn set_list_height(&mut self, height: usize) {
+    self.list_height = height;
+
+    if let Mode::Comments(view) = &mut self.mode {
+      view.ensure_selection_visible();
+      view.clamp_offset(height);
     }
   }
 
-  pub(crate) fn update_transient_message(&mut self) {
-    if let Some(transient) = self.transient_message.clone() {
-      if self.message != transient.current() {
-        self.transient_message = None;
-      } else if transient.is_expired() {
-        self.message = transient.original().to_string();
-        self.transient_message = None;
-      }
+  fn set_local_filter(&mut self, filter: Option<String>) {
+    if self.local_filter == filter {
+      return;
     }
+
+    self.local_filter = filter;
+
+    self.snap_selection(self.active_tab);
   }
-}
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+  pub(crate) fn set_message_timeout(&mut self, timeout: Duration) {
+    self.message_timeout = timeout;
+  }
 
-  fn empty_bookmarks() -> Bookmarks {
-    let unique = std::time::SystemTime::now()
-      .duration_since(std::time::UNIX_EPOCH)
-      .expect("system time before UNIX_EPOCH")
-      .as_nanos();
+  pub(crate) fn set_persistent_message(&mut self, message: String) {
+    let original = self.transient_original();
 
-    let path =
-      std::env::temp_dir().join(format!("hn_app_state_test_{unique}.json"));
+    self.transient_message =
+      Some(TransientMessage::persistent(message.clone(), original));
 
-    // SAFETY: Scoped test code sets env var to isolate bookmarks file.
-    unsafe {
-      std::env::set_var("HN_BOOKMARKS_FILE", &path);
-    }
+    self.message = message;
+  }
+
+  pub(crate) fn set_transient_message(&mut self, message: String) {
+    let original = self.transient_original();
+
+    self.transient_message = Some(TransientMessage::new(
+      message.clone(),
+      original,
+      self.message_timeout,
+    ));
 
-    let bookmarks = Bookmarks::load().expect("load bookmarks");
+    self.message = message;
+  }
 
-    // SAFETY: Test restores original environment variable state before exit.
-    unsafe {
-      std::env::remove_var("HN_BOOKMARKS_FILE");
+  fn shift_day(&mut self, delta: i64) -> Result {
+    if !matches!(self.mode, Mode::List(_)) {
+      return Ok(());
     }
 
-    bookmarks
-  }
+    let tab_index = self.active_tab;
 
-  fn sample_state_with_entry() -> State {
-    let entry = ListEntry {
-      detail: None,
-      id: "42".to_string(),
-      title: "Example".to_string(),
-      url: Some("https://example.com".to_string()),
+    let Some(tab) = self.tabs.get_mut(tab_index) else {
+      return Ok(());
     };
 
-    let view = ListView::new(vec![entry]);
+    let CategoryKind::Front { days_ago, .. } = tab.category.kind else {
+      self.set_transient_message(
+        "Only the past tab can step through days".into(),
+      );
+      return Ok(());
+    };
 
-    let tab = Tab {
-      category: Category {
-        label: "top",
-        kind: CategoryKind::Stories("topstories"),
-      },
-      has_more: false,
-      label: "top",
+    let shifted = days_ago.saturating_add_signed(delta);
+
+    if shifted == days_ago {
+      self.set_transient_message("Already showing today".into());
+      return Ok(());
+    }
+
+    let now = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map_or(0, |elapsed| elapsed.as_secs());
+
+    tab.category.kind = CategoryKind::Front {
+      days_ago: shifted,
+      now: None,
     };
+    tab.has_more = true;
+    tab.label = format!(
+      "{} ({})",
+      tab.category.label,
+      format_date(now.saturating_sub(shifted.saturating_mul(DAY)))
+    );
 
-    State::new(vec![(tab, view)], empty_bookmarks())
-  }
+    self.cancel_tab_load(tab_index);
 
-  #[test]
-  fn dispatch_open_comments_emits_fetch_effect() {
-    let mut state = sample_state_with_entry();
+    if let Some(slot) = self.pending_selections.get_mut(tab_index) {
+      *slot = None;
+    }
 
-    let dispatch = state
-      .dispatch_command(Command::OpenComments)
-      .expect("dispatch succeeds");
+    if let Some(list) = self.list_view_mut(tab_index) {
+      *list = ListView::default();
+    }
 
-    assert!(!dispatch.should_exit);
+    self.start_load_for_tab(tab_index, false)
+  }
 
-    assert_eq!(dispatch.effects.len(), 1);
+  fn show_bookmarks(&mut self) {
+    self.reveal_bookmarks_tab();
 
-    match &dispatch.effects[0] {
-      Effect::FetchComments { item_id, .. } => assert_eq!(*item_id, 42),
-      _ => panic!("unexpected effect variant"),
+    let Some(index) = self.bookmarks_tab_index else {
+      self.set_transient_message("No bookmarks yet".into());
+      return;
+    };
+
+    if matches!(self.mode, Mode::Comments(_)) {
+      self.restore_active_list_view();
     }
 
-    assert_eq!(state.message, LOADING_COMMENTS_STATUS);
+    self.activate_tab(index);
   }
 
-  #[test]
-  fn open_comment_link_opens_selected_comment() {
-    let mut state = sample_state_with_entry();
+  fn show_tab_switcher(&mut self) {
+    self.tab_switcher = Some(TabSwitcher::default());
+  }
 
-    let comment_view = CommentView::new(
-      CommentThread {
-        focus: None,
-        roots: vec![Comment {
-          author: Some("user".to_string()),
-          children: Vec::new(),
-          dead: false,
-          deleted: false,
-          id: 123,
-          text: Some("body".to_string()),
-        }],
-      },
-      "https://news.ycombinator.com/item?id=42".to_string(),
+  fn show_thread(
+    &mut self,
+    thread: CommentThread,
+    comment_link: String,
+    listing: bool,
+  ) {
+    let mut view = CommentView::with_max_depth(
+      thread,
+      comment_link,
+      self.config.max_depth(),
     );
 
-    state.mode = Mode::Comments(comment_view);
+    if listing {
+      view.toggle_listing();
+    }
 
-    state.open_comment_link();
+    if self.config.auto_collapse_dead {
+      view.collapse_dead();
+    }
 
-    assert_eq!(state.pending_effects.len(), 1);
+    if self.config.scroll_comments {
+      view.toggle_scrolling();
+    }
 
-    match &state.pending_effects[0] {
-      Effect::OpenUrl { url } => {
-        assert_eq!(url, "https://news.ycombinator.com/item?id=123");
-      }
-      _ => panic!("unexpected effect variant"),
+    if let Mode::Comments(previous) = &self.mode
+      && previous.item_id() == view.item_id()
+      && let Some(entry) = previous.selected_entry()
+    {
+      view.select_comment(entry.id);
+    }
+
+    self.store_active_list_view();
+
+    self.mode = Mode::Comments(Box::new(view));
+
+    if !self.help.is_visible() {
+      self.message = self.default_status();
     }
   }
 
+  fn shown_index(
+    &self,
+    tab_index: usize,
+    steps: usize,
+    forward: bool,
+  ) -> usize {
+    let Some(view) = self.list_view(tab_index) else {
+      return 0;
+    };
+
+    let current = view.selected_raw();
+
+    let shown = |index: usize| {
+      view
+        .items()
+        .get(index)
+        .is_some_and(|entry| self.is_shown(entry))
+    };
+
+    let (mut index, mut target) = (current, current);
+
+    let mut remaining = steps;
+
+    while remaining > 0 {
+      if forward {
+        index = index.saturating_add(1);
+
+        if index >= view.len() {
+          return index.saturating_add(remaining - 1);
+        }
+      } else if let Some(previous) = index.checked_sub(1) {
+        index = previous;
+      } else {
+        break;
+      }
+
+      if shown(index) {
+        target = index;
+        remaining -= 1;
+      }
+    }
+
+    target
+  }
+
+  fn snap_selection(&mut self, tab_index: usize) {
+    let min_score = self.min_score;
+
+    let filter = self.local_filter.clone();
+
+    let Some(view) = self.list_view_mut(tab_index) else {
+      return;
+    };
+
+    let Some(selected) = view.selected_index() else {
+      return;
+    };
+
+    let items = view.items();
+
+    let shown = |index: usize| {
+      items[index].meets_score(min_score)
+        && items[index].matches_filter(filter.as_deref())
+    };
+
+    if shown(selected) {
+      return;
+    }
+
+    let target = (selected..items.len())
+      .chain((0..selected).rev())
+      .find(|&index| shown(index));
+
+    if let Some(target) = target {
+      view.set_selected(target);
+    }
+  }
+
+  pub(crate) fn split_view(&self) -> bool {
+    self.split_view
+  }
+
+  fn start_load_for_tab(&mut self, tab_index: usize, fresh: bool) -> Result {
+    if !self.tabs.get(tab_index).is_some_and(|tab| tab.has_more) {
+      return Ok(());
+    }
+
+    if self.tab_loading.get(tab_index).copied().unwrap_or(true) {
+      return Ok(());
+    }
+
+    let offset = self
+      .list_view(tab_index)
+      .map_or(0, ListView::<ListEntry>::len);
+
+    let tab = &mut self.tabs[tab_index];
+
+    if offset == 0
+      && let CategoryKind::Front { now, .. } = &mut tab.category.kind
+    {
+      *now = Some(
+        SystemTime::now()
+          .duration_since(UNIX_EPOCH)
+          .map_or(0, |elapsed| elapsed.as_secs()),
+      );
+    }
+
+    let category = tab.category;
+
+    let request_id = self.start_tab_request(tab_index);
+
+    if !self.help.is_visible() {
+      self.message = LOADING_ENTRIES_STATUS.into();
+    }
+
+    self.pending_effects.push(Effect::FetchTabItems {
+      category,
+      fresh,
+      offset,
+      request_id,
+    });
+
+    Ok(())
+  }
+
+  pub(crate) fn start_loading_tabs(&mut self, lazy: bool) -> Result {
+    for index in 0..self.tabs.len() {
+      if !self.tabs[index].loaded && (!lazy || index == self.active_tab) {
+        self.start_load_for_tab(index, false)?;
+      }
+    }
+
+    Ok(())
+  }
+
+  fn start_local_filter(&mut self) {
+    if self.search_input.is_some() || !matches!(self.mode, Mode::List(_)) {
+      return;
+    }
+
+    self.search_input = Some(SearchInput::with_buffer(
+      "Filter",
+      self.local_filter.clone().unwrap_or_default(),
+      self.message.clone(),
+    ));
+    self.filtering = true;
+
+    self.update_search_message();
+  }
+
+  fn start_search(&mut self) {
+    if self.search_input.is_some() {
+      return;
+    }
+
+    let backup = self.message.clone();
+
+    let label = match &self.mode {
+      Mode::Comments(view) if view.is_listing() => "Filter",
+      Mode::Comments(_) => "Find",
+      Mode::List(_) => "Search",
+    };
+
+    self.search_input = Some(SearchInput::new(label, backup));
+
+    self.update_search_message();
+  }
+
+  fn start_tab_request(&mut self, tab_index: usize) -> u64 {
+    let request_id = self.next_request_id;
+
+    self.next_request_id = self.next_request_id.wrapping_add(1);
+
+    if let Some(flag) = self.tab_loading.get_mut(tab_index) {
+      *flag = true;
+    }
+
+    if let Some(request) = self.tab_requests.get_mut(tab_index) {
+      *request = Some(request_id);
+    }
+
+    request_id
+  }
+
+  fn store_active_list_view(&mut self) {
+    if let Mode::List(view) = &mut self.mode
+      && let Some(slot) = self.tab_views.get_mut(self.active_tab)
+    {
+      *slot = Some(std::mem::take(view));
+    }
+  }
+
+  pub(crate) fn store_session(&self, session: &mut Session) {
+    session.set_contrast(self.contrast);
+
+    session.set_active_tab(
+      self
+        .tabs
+        .get(self.active_tab)
+        .filter(|tab| !tab.category.kind.is_dynamic())
+        .map(|tab| tab.label.as_str()),
+    );
+
+    for (index, tab) in self.tabs.iter().enumerate() {
+      if !tab.loaded || tab.category.kind.is_dynamic() {
+        continue;
+      }
+
+      if let Some(view) = self.list_view(index) {
+        session.set_tab_position(
+          &tab.label,
+          TabPosition {
+            offset: view.offset(),
+            selected: view.selected_index().unwrap_or(0),
+          },
+        );
+      }
+    }
+  }
+
+  fn submit_search(&mut self) -> Result {
+    let Some(search) = self.search_input.take() else {
+      return Ok(());
+    };
+
+    if let Some(id) = self.tagging.take() {
+      self.message = search.message_backup;
+      return self.save_tags(&id, &search.buffer);
+    }
+
+    if std::mem::take(&mut self.filtering) {
+      self.message = search.message_backup;
+      return Ok(());
+    }
+
+    let query = search.buffer.trim().to_string();
+
+    if let Mode::Comments(view) = &mut self.mode
+      && view.is_listing()
+    {
+      view.set_filter(query);
+      self.message = search.message_backup;
+      return Ok(());
+    }
+
+    if query.is_empty() {
+      if let Mode::Comments(view) = &mut self.mode {
+        view.set_search_query(query);
+      }
+
+      self.message = search.message_backup;
+      return Ok(());
+    }
+
+    if let Some(id) = hn_item_id(&query) {
+      self.request_comments(id, false, None);
+      return Ok(());
+    }
+
+    if let Mode::Comments(view) = &mut self.mode {
+      view.set_search_query(query);
+      self.message = search.message_backup;
+      self.find_comment_match(true);
+      return Ok(());
+    }
+
+    self.run_search(query, search.search_tab);
+
+    Ok(())
+  }
+
+  fn submit_tab_switcher(&mut self) -> Result {
+    let Some(switcher) = self.tab_switcher.take() else {
+      return Ok(());
+    };
+
+    let Some(index) = switcher.selected_tab(&self.switcher_tabs()) else {
+      return Ok(());
+    };
+
+    if index == self.tabs.len() {
+      self.reveal_bookmarks_tab();
+    }
+
+    if index != self.active_tab {
+      self.activate_tab(index);
+      self.load_active_tab_if_needed()?;
+    }
+
+    Ok(())
+  }
+
+  fn switch_tab_left(&mut self) -> Result {
+    if self.active_tab == 0 {
+      self.reveal_bookmarks_tab();
+    }
+
+    let tab_count = self.tabs.len();
+
+    if tab_count != 0 {
+      self.activate_tab((self.active_tab + tab_count - 1) % tab_count);
+      self.refresh_status();
+      self.load_active_tab_if_needed()?;
+    }
+
+    Ok(())
+  }
+
+  fn switch_tab_right(&mut self) -> Result {
+    if self.active_tab + 1 >= self.tabs.len() {
+      self.reveal_bookmarks_tab();
+    }
+
+    let tab_count = self.tabs.len();
+
+    if tab_count != 0 {
+      self.activate_tab((self.active_tab + 1) % tab_count);
+      self.refresh_status();
+      self.load_active_tab_if_needed()?;
+    }
+
+    Ok(())
+  }
+
+  fn switch_to_tab(&mut self, index: usize) -> Result {
+    if index >= self.tabs.len() || index == self.active_tab {
+      return Ok(());
+    }
+
+    self.activate_tab(index);
+    self.refresh_status();
+    self.load_active_tab_if_needed()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn switcher_tabs(&self) -> Vec<Tab> {
+    let mut tabs = self.tabs.clone();
+
+    if self.bookmarks_tab_index.is_none() && !self.bookmarks.is_empty() {
+      tabs.push(Self::bookmarks_tab());
+    }
+
+    tabs
+  }
+
+  fn sync_bookmarks_tab(&mut self) {
+    if self.bookmarks.is_empty() {
+      self.remove_bookmarks_tab();
+    } else {
+      let index = self.ensure_bookmarks_tab();
+      self.refresh_bookmarks_view(index);
+    }
+  }
+
+  pub(crate) fn tab(&self, index: usize) -> Option<&Tab> {
+    self.tabs.get(index)
+  }
+
+  pub(crate) fn tab_loading(&self) -> &[bool] {
+    &self.tab_loading
+  }
+
+  pub(crate) fn tab_switcher(&self) -> Option<&TabSwitcher> {
+    self.tab_switcher.as_ref()
+  }
+
+  pub(crate) fn tab_switcher_command(
+    &mut self,
+    key: KeyEvent,
+  ) -> Option<Command> {
+    if self.tab_switcher.is_some() {
+      Some(self.handle_tab_switcher_key(key))
+    } else {
+      None
+    }
+  }
+
+  pub(crate) fn tabs(&self) -> &[Tab] {
+    &self.tabs
+  }
+
+  pub(crate) fn tag_filter(&self) -> Option<&str> {
+    self
+      .tag_filter
+      .as_deref()
+      .filter(|_| self.bookmarks_tab_index == Some(self.active_tab))
+  }
+
+  fn take_count(&mut self) -> usize {
+    self.count.take().unwrap_or(1)
+  }
+
+  pub(crate) fn take_pending_effects(&mut self) -> Vec<Effect> {
+    std::mem::take(&mut self.pending_effects)
+  }
+
+  fn toggle_bookmark(&mut self) -> Result {
+    match &mut self.mode {
+      Mode::List(_) => self.toggle_list_bookmark(),
+      Mode::Comments(_) => self.toggle_comment_bookmark(),
+    }
+  }
+
+  fn toggle_center_selection(&mut self) {
+    self.center_selection = !self.center_selection;
+
+    if !self.help.is_visible() {
+      self.set_transient_message(
+        if self.center_selection {
+          "Keeping the selection centered"
+        } else {
+          "Scrolling only at the edges"
+        }
+        .into(),
+      );
+    }
+  }
+
+  fn toggle_comment_bookmark(&mut self) -> Result {
+    let Mode::Comments(view) = &mut self.mode else {
+      return Ok(());
+    };
+
+    let Some(selected) = view.selected_entry() else {
+      return Ok(());
+    };
+
+    let entry = selected.to_bookmark_entry();
+
+    let added = self.bookmarks.toggle(&entry)?;
+
+    self.sync_bookmarks_tab();
+
+    if !self.help.is_visible() {
+      let title = truncate_words(&entry.title, 40);
+
+      let message = if added {
+        format!("Bookmarked \"{title}\"")
+      } else {
+        format!("Removed bookmark for \"{title}\"")
+      };
+
+      self.set_transient_message(message);
+    }
+
+    Ok(())
+  }
+
+  fn toggle_comment_wrap(&mut self) {
+    self.wrap_comments = !self.wrap_comments;
+
+    if !self.help.is_visible() {
+      self.set_transient_message(
+        if self.wrap_comments {
+          "Comment wrapping on"
+        } else {
+          "Comment wrapping off"
+        }
+        .into(),
+      );
+    }
+  }
+
+  fn toggle_follow(&mut self) -> Result {
+    let Mode::Comments(view) = &self.mode else {
+      return Ok(());
+    };
+
+    let Some(author) = view
+      .selected_entry()
+      .filter(|entry| !entry.is_fold())
+      .and_then(|entry| entry.author.clone())
+    else {
+      self.set_transient_message("Nothing selected to follow".into());
+      return Ok(());
+    };
+
+    let message = if self.followed_users.toggle(&author)? {
+      format!("Following {author}")
+    } else {
+      format!("Unfollowed {author}")
+    };
+
+    self.set_transient_message(message);
+
+    Ok(())
+  }
+
+  fn toggle_list_bookmark(&mut self) -> Result {
+    let Some(entry) = self.current_entry().cloned() else {
+      return Ok(());
+    };
+
+    let added = self.bookmarks.toggle(&entry)?;
+
+    self.sync_bookmarks_tab();
+
+    if !self.help.is_visible() {
+      let title = truncate_words(&entry.title, 40);
+
+      let message = if added {
+        format!("Bookmarked \"{title}\"")
+      } else {
+        format!("Removed bookmark for \"{title}\"")
+      };
+
+      self.set_transient_message(message);
+    }
+
+    Ok(())
+  }
+
+  fn toggle_pin(&mut self) {
+    let Some(entry) = self.current_entry().cloned() else {
+      return;
+    };
+
+    let pinned = if self.pinned.remove(&entry.id) {
+      false
+    } else {
+      self.pinned.insert(entry.id.clone())
+    };
+
+    for tab_index in 0..self.tabs.len() {
+      self.apply_pins(tab_index, Vec::new());
+    }
+
+    if !self.help.is_visible() {
+      let title = truncate_words(&entry.title, 40);
+
+      let message = if pinned {
+        format!("Pinned \"{title}\"")
+      } else {
+        format!("Unpinned \"{title}\"")
+      };
+
+      self.set_transient_message(message);
+    }
+  }
+
+  fn toggle_read(&mut self) -> Result {
+    let Some(entry) = self.current_entry().cloned() else {
+      return Ok(());
+    };
+
+    let read = self.read_items.toggle(&entry.id)?;
+
+    if !self.help.is_visible() {
+      let title = truncate_words(&entry.title, 40);
+
+      let message = if read {
+        format!("Marked \"{title}\" as read")
+      } else {
+        format!("Marked \"{title}\" as unread")
+      };
+
+      self.set_transient_message(message);
+    }
+
+    Ok(())
+  }
+
+  fn toggle_split_view(&mut self) {
+    self.split_view = !self.split_view;
+
+    if !self.split_view {
+      self.preview = None;
+    }
+
+    if !self.help.is_visible() {
+      self.set_transient_message(
+        if self.split_view {
+          "Split view on"
+        } else {
+          "Split view off"
+        }
+        .into(),
+      );
+    }
+  }
+
+  fn transient_original(&self) -> String {
+    self.transient_message.as_ref().map_or_else(
+      || self.message.clone(),
+      |transient| transient.original().to_string(),
+    )
+  }
+
+  pub(crate) fn update_preview(&mut self, visible: bool) {
+    if !self.split_view || !visible || !matches!(self.mode, Mode::List(_)) {
+      return;
+    }
+
+    let Some(item_id) = self
+      .current_entry()
+      .and_then(|entry| entry.id.parse::<u64>().ok())
+    else {
+      self.preview = None;
+      return;
+    };
+
+    match &mut self.preview {
+      Some(preview) if preview.is_for(item_id) => {
+        if !preview.is_due() {
+          return;
+        }
+
+        let request_id = self.next_request_id;
+
+        self.next_request_id = self.next_request_id.wrapping_add(1);
+
+        preview.set_request(request_id);
+
+        self.pending_effects.push(Effect::FetchPreview {
+          item_id,
+          request_id,
+        });
+      }
+      _ => self.preview = Some(Preview::new(item_id)),
+    }
+  }
+
+  fn update_search_message(&mut self) {
+    if let Some(input) = &self.search_input {
+      let prompt = input.prompt();
+
+      let filter = self
+        .filtering
+        .then(|| input.buffer.trim().to_string())
+        .filter(|filter| !filter.is_empty());
+
+      self.message = truncate(&prompt, 80);
+
+      if self.filtering {
+        self.set_local_filter(filter);
+      }
+    }
+  }
+
+  pub(crate) fn update_transient_message(&mut self) {
+    if let Some(transient) = self.transient_message.clone() {
+      if self.message != transient.current() {
+        self.transient_message = None;
+      } else if transient.is_expired() {
+        self.message = transient.original().to_string();
+        self.transient_message = None;
+      }
+    }
+  }
+
+  fn view_in_pager(&mut self) {
+    let Mode::Comments(view) = &self.mode else {
+      return;
+    };
+
+    let Some(entry) = view.selected_entry() else {
+      return;
+    };
+
+    self.pending_effects.push(Effect::RunPager {
+      text: format!("{}\n", entry.body()),
+    });
+  }
+
+  pub(crate) fn wrap_comments(&self) -> bool {
+    self.wrap_comments
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn empty_bookmarks() -> Bookmarks {
+    let unique = std::time::SystemTime::now()
+      .duration_since(std::time::UNIX_EPOCH)
+      .expect("system time before UNIX_EPOCH")
+      .as_nanos();
+
+    let path =
+      std::env::temp_dir().join(format!("hn_app_state_test_{unique}.json"));
+
+    Bookmarks::load_from(path).expect("load bookmarks")
+  }
+
+  fn sample_state_with_entry() -> State {
+    let entry = ListEntry {
+      comments: None,
+      detail: None,
+      id: "42".to_string(),
+      score: None,
+      tags: Vec::new(),
+      time: None,
+      title: "Example".to_string(),
+      url: Some("https://example.com".to_string()),
+    };
+
+    let view = ListView::new(vec![entry]);
+
+    let tab = Tab {
+      category: Category {
+        label: "top",
+        kind: CategoryKind::Stories("topstories"),
+      },
+      has_more: false,
+      label: "top".into(),
+      loaded: true,
+    };
+
+    State::new(
+      vec![(tab, view)],
+      empty_bookmarks(),
+      ReadItems::default(),
+      SavedThreads::default(),
+      FollowedUsers::default(),
+      Config::default(),
+    )
+  }
+
+  #[test]
+  fn corrupt_file_notices_are_shown_together() {
+    let dir = std::env::temp_dir().join(format!(
+      "hn_state_corrupt_test_{}",
+      std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+    ));
+
+    fs::create_dir_all(&dir).unwrap();
+
+    let (bookmarks_path, read_path) =
+      (dir.join("bookmarks.json"), dir.join("read.json"));
+
+    fs::write(&bookmarks_path, "[{\"id\": ").unwrap();
+    fs::write(&read_path, "not json").unwrap();
+
+    let state = State::new(
+      Vec::new(),
+      Bookmarks::load_from(bookmarks_path).unwrap(),
+      ReadItems::load_from(read_path),
+      SavedThreads::default(),
+      FollowedUsers::default(),
+      Config::default(),
+    );
+
+    assert!(state.message.starts_with("Bookmarks file was corrupt"));
+    assert!(
+      state
+        .message
+        .contains(&format!("{DETAIL_SEPARATOR}Read items file was corrupt"))
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[test]
+  fn copy_summary_copies_selected_entry_summary() {
+    let mut state = sample_state_with_entry();
+
+    let dispatch = state.dispatch_command(Command::CopySummary).unwrap();
+
+    match &dispatch.effects[..] {
+      [Effect::CopyToClipboard { label, text }] => {
+        assert_eq!(*label, "summary");
+        assert_eq!(text, "Example — ? points — https://example.com");
+      }
+      _ => panic!("expected a single copy effect"),
+    }
+  }
+
+  #[test]
+  fn copy_link_copies_story_url_or_comment_permalink() {
+    let mut state = sample_state_with_entry();
+
+    let dispatch = state.dispatch_command(Command::CopyLink).unwrap();
+
+    match &dispatch.effects[..] {
+      [Effect::CopyToClipboard { label, text }] => {
+        assert_eq!(*label, "link");
+        assert_eq!(text, "https://example.com");
+      }
+      _ => panic!("expected a single copy effect"),
+    }
+
+    state.dispatch_command(Command::OpenComments).unwrap();
+
+    state.handle_event(Event::Comments {
+      request_id: 0,
+      result: Ok(CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 42,
+        op_author: None,
+        partial: false,
+        roots: vec![Comment {
+          author: Some("user".to_string()),
+          children: Vec::new(),
+          dead: false,
+          deleted: false,
+          html: None,
+          id: 123,
+          links: Vec::new(),
+          loaded: true,
+          text: Some("body".to_string()),
+          time: None,
+        }],
+        score: None,
+        title: None,
+      }),
+    });
+
+    let dispatch = state.dispatch_command(Command::CopyLink).unwrap();
+
+    match &dispatch.effects[..] {
+      [Effect::CopyToClipboard { label, text }] => {
+        assert_eq!(*label, "comment link");
+        assert_eq!(text, "https://news.ycombinator.com/item?id=123");
+      }
+      _ => panic!("expected a single copy effect"),
+    }
+  }
+
+  #[test]
+  fn open_comments_in_browser_opens_discussion_page() {
+    let mut state = sample_state_with_entry();
+
+    let dispatch = state
+      .dispatch_command(Command::OpenCommentsInBrowser)
+      .unwrap();
+
+    match &dispatch.effects[..] {
+      [Effect::OpenUrl { url }] => {
+        assert_eq!(url, "https://news.ycombinator.com/item?id=42");
+      }
+      _ => panic!("expected a single open url effect"),
+    }
+  }
+
+  fn numbered_state(count: u64) -> State {
+    let entries = (1..=count)
+      .map(|id| ListEntry {
+        comments: None,
+        detail: None,
+        id: id.to_string(),
+        score: None,
+        tags: Vec::new(),
+        time: None,
+        title: format!("Story {id}"),
+        url: None,
+      })
+      .collect();
+
+    State::new(
+      vec![(
+        Tab {
+          category: Category::all()[0],
+          has_more: false,
+          label: "top".into(),
+          loaded: true,
+        },
+        ListView::new(entries),
+      )],
+      empty_bookmarks(),
+      ReadItems::default(),
+      SavedThreads::default(),
+      FollowedUsers::default(),
+      Config::default(),
+    )
+  }
+
+  #[test]
+  fn count_prefix_repeats_movement_and_clamps() {
+    let mut state = numbered_state(5);
+
+    let press = |state: &mut State, ch| {
+      let command = state
+        .handle_key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE), 1);
+      state.dispatch_command(command).unwrap();
+    };
+
+    let selected = |state: &State| state.current_entry().unwrap().id.clone();
+
+    press(&mut state, '3');
+
+    assert_eq!(state.count(), Some(3));
+
+    press(&mut state, 'j');
+
+    assert_eq!(state.count(), None);
+    assert_eq!(selected(&state), "4");
+
+    press(&mut state, '1');
+    press(&mut state, '0');
+    press(&mut state, 'j');
+
+    assert_eq!(selected(&state), "5");
+
+    press(&mut state, '2');
+    press(&mut state, 'k');
+
+    assert_eq!(selected(&state), "3");
+
+    press(&mut state, '2');
+    press(&mut state, 'z');
+    press(&mut state, 'j');
+
+    assert_eq!(selected(&state), "4");
+  }
+
+  #[test]
+  fn double_g_jumps_to_first_and_lone_g_is_discarded() {
+    let mut state = numbered_state(3);
+
+    let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+    let selected = |state: &State| state.current_entry().unwrap().id.clone();
+
+    state.handle_key(press(KeyCode::Char('G')), 1);
+
+    assert_eq!(selected(&state), "3");
+
+    assert_eq!(
+      state.handle_key(press(KeyCode::Char('g')), 1),
+      Command::None
+    );
+
+    assert_eq!(
+      state.handle_key(press(KeyCode::Char('k')), 1),
+      Command::SelectPrevious
+    );
+
+    assert_eq!(
+      state.handle_key(press(KeyCode::Char('g')), 1),
+      Command::None
+    );
+
+    let command = state.handle_key(press(KeyCode::Char('g')), 1);
+
+    assert_eq!(command, Command::SelectFirst);
+
+    state.dispatch_command(command).unwrap();
+
+    assert_eq!(selected(&state), "1");
+  }
+
+  #[test]
+  fn pressing_z_twice_toggles_centered_selection() {
+    let mut state = numbered_state(3);
+
+    let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+    assert!(!state.center_selection());
+
+    assert_eq!(
+      state.handle_key(press(KeyCode::Char('z')), 1),
+      Command::None
+    );
+
+    let command = state.handle_key(press(KeyCode::Char('z')), 1);
+
+    assert_eq!(command, Command::ToggleCenterSelection);
+
+    state.dispatch_command(command).unwrap();
+
+    assert!(state.center_selection());
+    assert_eq!(state.message, "Keeping the selection centered");
+  }
+
+  #[test]
+  fn confirm_quit_requires_a_second_quit_while_comments_are_open() {
+    let mut state = sample_state_with_entry();
+
+    state.confirm_quit = true;
+
+    assert!(state.dispatch_command(Command::Quit).unwrap().should_exit);
+
+    state.dispatch_command(Command::OpenComments).unwrap();
+
+    state.handle_event(Event::Comments {
+      request_id: 0,
+      result: Ok(CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 42,
+        op_author: None,
+        partial: false,
+        roots: Vec::new(),
+        score: None,
+        title: None,
+      }),
+    });
+
+    assert!(!state.dispatch_command(Command::Quit).unwrap().should_exit);
+
+    assert_eq!(state.message(), "Press q again to quit");
+
+    state.dispatch_command(Command::SelectNext).unwrap();
+
+    assert!(!state.dispatch_command(Command::Quit).unwrap().should_exit);
+
+    assert!(state.dispatch_command(Command::Quit).unwrap().should_exit);
+  }
+
+  fn tab_request(state: &State, tab_index: usize) -> u64 {
+    state.tab_requests[tab_index].expect("tab request in flight")
+  }
+
+  #[test]
+  fn shift_day_reloads_the_past_tab_and_clamps_at_today() {
+    let tab = Tab {
+      category: Category {
+        label: "past",
+        kind: CategoryKind::Front {
+          days_ago: 1,
+          now: None,
+        },
+      },
+      has_more: false,
+      label: "past".into(),
+      loaded: true,
+    };
+
+    let mut state = State::new(
+      vec![(tab, ListView::new(more_entries()))],
+      empty_bookmarks(),
+      ReadItems::default(),
+      SavedThreads::default(),
+      FollowedUsers::default(),
+      Config::default(),
+    );
+
+    let dispatch = state.dispatch_command(Command::ShiftDay(1)).unwrap();
+
+    assert!(matches!(
+      state.tabs[0].category.kind,
+      CategoryKind::Front { days_ago: 2, .. }
+    ));
+    assert!(state.tabs[0].label.starts_with("past (20"));
+    assert!(state.list_view(0).unwrap().is_empty());
+    assert!(matches!(
+      dispatch.effects.as_slice(),
+      [Effect::FetchTabItems { offset: 0, .. }]
+    ));
+
+    let stale = tab_request(&state, 0);
+
+    state.dispatch_command(Command::ShiftDay(-1)).unwrap();
+
+    state.handle_event(Event::TabItems {
+      request_id: stale,
+      result: Ok(more_entries()),
+    });
+
+    assert!(state.list_view(0).unwrap().is_empty());
+    assert_eq!(state.tab_loading(), [true]);
+
+    state.handle_event(Event::TabItems {
+      request_id: tab_request(&state, 0),
+      result: Ok(more_entries()),
+    });
+
+    assert!(!state.list_view(0).unwrap().is_empty());
+
+    let pinned = |category: &Category| match category.kind {
+      CategoryKind::Front { now, .. } => now,
+      _ => None,
+    };
+
+    let first_page = pinned(&state.tabs[0].category);
+
+    assert!(first_page.is_some());
+
+    state.tabs[0].has_more = true;
+    state.start_load_for_tab(0, false).unwrap();
+
+    let effects = state.take_pending_effects();
+
+    let [
+      Effect::FetchTabItems {
+        category, offset, ..
+      },
+    ] = effects.as_slice()
+    else {
+      panic!("expected a next page fetch");
+    };
+
+    assert!(*offset > 0);
+    assert_eq!(pinned(category), first_page);
+
+    state.handle_event(Event::TabItems {
+      request_id: tab_request(&state, 0),
+      result: Ok(Vec::new()),
+    });
+
+    state.dispatch_command(Command::ShiftDay(-1)).unwrap();
+    state.handle_event(Event::TabItems {
+      request_id: tab_request(&state, 0),
+      result: Ok(more_entries()),
+    });
+    state.dispatch_command(Command::ShiftDay(-1)).unwrap();
+
+    assert!(matches!(
+      state.tabs[0].category.kind,
+      CategoryKind::Front { days_ago: 0, .. }
+    ));
+
+    let dispatch = state.dispatch_command(Command::ShiftDay(-1)).unwrap();
+
+    assert!(dispatch.effects.is_empty());
+    assert_eq!(state.message, "Already showing today");
+  }
+
+  #[test]
+  fn split_view_debounces_preview_and_clears_it_when_closed() {
+    let mut state = sample_state_with_entry();
+
+    state.update_preview(true);
+
+    assert!(state.preview().is_none());
+
+    state.dispatch_command(Command::ToggleSplitView).unwrap();
+
+    assert_eq!(state.message(), "Split view on");
+
+    state.update_preview(false);
+
+    assert!(state.preview().is_none());
+
+    state.update_preview(true);
+    state.update_preview(true);
+
+    assert!(state.preview().is_some_and(|preview| preview.is_for(42)));
+    assert!(state.pending_effects.is_empty());
+
+    std::thread::sleep(Preview::DEBOUNCE);
+
+    state.update_preview(true);
+
+    let [
+      Effect::FetchPreview {
+        item_id: 42,
+        request_id,
+      },
+    ] = state.take_pending_effects()[..]
+    else {
+      panic!("expected a single preview fetch");
+    };
+
+    state.update_preview(true);
+
+    assert!(state.pending_effects.is_empty());
+
+    state.handle_event(Event::Preview {
+      request_id,
+      result: Ok(vec![Comment {
+        author: Some("user".to_string()),
+        children: Vec::new(),
+        dead: false,
+        deleted: false,
+        html: None,
+        id: 43,
+        links: Vec::new(),
+        loaded: false,
+        text: Some("First!".to_string()),
+        time: None,
+      }]),
+    });
+
+    assert_eq!(
+      state
+        .preview()
+        .and_then(Preview::roots)
+        .unwrap()
+        .iter()
+        .map(|entry| entry.id)
+        .collect::<Vec<u64>>(),
+      [43]
+    );
+
+    state.dispatch_command(Command::ToggleSplitView).unwrap();
+
+    assert!(state.preview().is_none());
+  }
+
+  #[test]
+  fn open_item_focuses_requested_comment() {
+    let mut state = sample_state_with_entry();
+
+    state.open_item(7, Some(9));
+
+    match &state.take_pending_effects()[..] {
+      [Effect::FetchComments { item_id, .. }] => assert_eq!(*item_id, 7),
+      _ => panic!("expected a single fetch comments effect"),
+    }
+
+    let comment = |id, children| Comment {
+      author: Some("user".to_string()),
+      children,
+      dead: false,
+      deleted: false,
+      html: None,
+      id,
+      links: Vec::new(),
+      loaded: true,
+      text: Some("body".to_string()),
+      time: None,
+    };
+
+    state.handle_event(Event::Comments {
+      request_id: 0,
+      result: Ok(CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 7,
+        op_author: None,
+        partial: false,
+        roots: vec![comment(8, vec![comment(9, Vec::new())])],
+        score: None,
+        title: None,
+      }),
+    });
+
+    let Mode::Comments(view) = state.mode() else {
+      panic!("expected the comment view to open");
+    };
+
+    assert_eq!(view.selected_entry().map(|entry| entry.id), Some(9));
+  }
+
+  #[test]
+  fn opening_comments_marks_entry_read() {
+    let mut state = sample_state_with_entry();
+
+    assert!(!state.is_read("42"));
+
+    state.dispatch_command(Command::OpenComments).unwrap();
+
+    assert!(state.is_read("42"));
+
+    state.close_comments();
+
+    state.dispatch_command(Command::ToggleRead).unwrap();
+
+    assert!(!state.is_read("42"));
+
+    state.dispatch_command(Command::MarkAllRead).unwrap();
+
+    assert!(state.is_read("42"));
+  }
+
+  #[test]
+  fn open_comments_on_job_posting_opens_url() {
+    let mut state = sample_state_with_entry();
+
+    state.tabs[0].category = Category {
+      label: "jobs",
+      kind: CategoryKind::Stories("jobstories"),
+    };
+
+    let dispatch = state.dispatch_command(Command::OpenComments).unwrap();
+
+    match &dispatch.effects[..] {
+      [Effect::OpenUrl { url }] => assert_eq!(url, "https://example.com"),
+      _ => panic!("expected a single open url effect"),
+    }
+
+    assert!(matches!(state.mode(), Mode::List(_)));
+  }
+
+  #[test]
+  fn dispatch_open_comments_emits_fetch_effect() {
+    let mut state = sample_state_with_entry();
+
+    let dispatch = state
+      .dispatch_command(Command::OpenComments)
+      .expect("dispatch succeeds");
+
+    assert!(!dispatch.should_exit);
+
+    assert_eq!(dispatch.effects.len(), 1);
+
+    match &dispatch.effects[0] {
+      Effect::FetchComments { item_id, .. } => assert_eq!(*item_id, 42),
+      _ => panic!("unexpected effect variant"),
+    }
+
+    assert_eq!(state.message, LOADING_COMMENTS_STATUS);
+  }
+
+  #[test]
+  fn open_comments_reports_read_marker_failures_and_still_opens() {
+    let mut state = sample_state_with_entry();
+
+    let blocker =
+      env::temp_dir().join(format!("hn_read_blocker_test_{}", process::id()));
+
+    fs::write(&blocker, "").unwrap();
+
+    state.read_items = ReadItems::load_from(blocker.join("read.json"));
+
+    let dispatch = state.dispatch_command(Command::OpenComments).unwrap();
+
+    fs::remove_file(&blocker).unwrap();
+
+    assert!(matches!(
+      dispatch.effects[..],
+      [Effect::FetchComments { item_id: 42, .. }]
+    ));
+    assert!(state.message.starts_with("Could not mark as read: "));
+  }
+
+  #[test]
+  fn load_full_thread_refetches_partial_thread_without_timeout() {
+    let mut state = state_with_comment_links(0);
+
+    let dispatch = state.dispatch_command(Command::LoadFullThread).unwrap();
+
+    assert!(dispatch.effects.is_empty());
+
+    state.mode = Mode::Comments(Box::new(CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 42,
+        op_author: None,
+        partial: true,
+        roots: Vec::new(),
+        score: None,
+        title: None,
+      },
+      "https://news.ycombinator.com/item?id=42".to_string(),
+    )));
+
+    let dispatch = state.dispatch_command(Command::LoadFullThread).unwrap();
+
+    match &dispatch.effects[..] {
+      [
+        Effect::FetchComments {
+          item_id, timeout, ..
+        },
+      ] => {
+        assert_eq!(*item_id, 42);
+        assert_eq!(*timeout, None);
+      }
+      _ => panic!("expected a single fetch comments effect"),
+    }
+
+    assert_eq!(state.message, LOADING_COMMENTS_STATUS);
+  }
+
+  #[test]
+  fn saved_threads_reopen_from_the_saved_tab_without_fetching() {
+    let mut state = sample_state_with_entry();
+
+    let dir = std::env::temp_dir().join(format!(
+      "hn_state_saved_test_{}",
+      std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+    ));
+
+    state.saved_threads = SavedThreads::load_from(dir.clone());
+
+    state.mode = Mode::Comments(Box::new(CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: Some(0),
+        focus: None,
+        id: 42,
+        op_author: None,
+        partial: false,
+        roots: Vec::new(),
+        score: None,
+        title: Some("Example".to_string()),
+      },
+      "https://news.ycombinator.com/item?id=42".to_string(),
+    )));
+
+    state.dispatch_command(Command::SaveThread).unwrap();
+
+    assert_eq!(state.saved_tab_index, Some(1));
+    assert_eq!(state.message, "Saved thread for offline reading");
+
+    state.dispatch_command(Command::CloseComments).unwrap();
+    state.dispatch_command(Command::SwitchTabRight).unwrap();
+
+    let dispatch = state.dispatch_command(Command::OpenComments).unwrap();
+
+    assert!(dispatch.effects.is_empty());
+
+    match &state.mode {
+      Mode::Comments(view) => {
+        assert_eq!(view.item_id(), 42);
+        assert_eq!(view.title().as_deref(), Some("Example • 0 comments"));
+      }
+      Mode::List(_) => panic!("expected the saved thread to open"),
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[test]
+  fn open_comment_link_opens_selected_comment() {
+    let mut state = sample_state_with_entry();
+
+    let comment_view = CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![Comment {
+          author: Some("user".to_string()),
+          children: Vec::new(),
+          dead: false,
+          deleted: false,
+          html: None,
+          id: 123,
+          links: Vec::new(),
+          loaded: true,
+          text: Some("body".to_string()),
+          time: None,
+        }],
+        score: None,
+        title: None,
+      },
+      "https://news.ycombinator.com/item?id=42".to_string(),
+    );
+
+    state.mode = Mode::Comments(Box::new(comment_view));
+
+    state.open_comment_link();
+
+    assert_eq!(state.pending_effects.len(), 1);
+
+    match &state.pending_effects[0] {
+      Effect::OpenUrl { url } => {
+        assert_eq!(url, "https://news.ycombinator.com/item?id=123");
+      }
+      _ => panic!("unexpected effect variant"),
+    }
+  }
+
+  #[test]
+  fn find_in_comments_selects_matches_instead_of_searching() {
+    let mut state = sample_state_with_entry();
+
+    let comment = |id: u64, text: &str| Comment {
+      author: Some("user".to_string()),
+      children: Vec::new(),
+      dead: false,
+      deleted: false,
+      html: None,
+      id,
+      links: Vec::new(),
+      loaded: true,
+      text: Some(text.to_string()),
+      time: None,
+    };
+
+    state.mode = Mode::Comments(Box::new(CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![comment(1, "first"), comment(2, "Second")],
+        score: None,
+        title: None,
+      },
+      "https://news.ycombinator.com/item?id=42".to_string(),
+    )));
+
+    state.dispatch_command(Command::StartSearch).unwrap();
+
+    assert_eq!(state.message, "Find: ");
+
+    if let Some(input) = state.search_input.as_mut() {
+      input.buffer.push_str("second");
+    }
+
+    let dispatch = state.dispatch_command(Command::SubmitSearch).unwrap();
+
+    assert!(dispatch.effects.is_empty());
+
+    let selected = |state: &State| match &state.mode {
+      Mode::Comments(view) => view.selected_entry().map(|entry| entry.id),
+      Mode::List(_) => None,
+    };
+
+    assert_eq!(selected(&state), Some(2));
+
+    state.dispatch_command(Command::NextMatch).unwrap();
+
+    assert_eq!(selected(&state), Some(2));
+
+    if let Mode::Comments(view) = &mut state.mode {
+      view.set_search_query("missing".to_string());
+    }
+
+    state.dispatch_command(Command::PreviousMatch).unwrap();
+
+    assert_eq!(state.message, "No comments match \"missing\"");
+  }
+
+  #[test]
+  fn expanding_unloaded_comment_fetches_its_replies_once() {
+    let mut state = sample_state_with_entry();
+
+    let comment = |id: u64, loaded: bool| Comment {
+      author: Some("user".to_string()),
+      children: Vec::new(),
+      dead: false,
+      deleted: false,
+      html: None,
+      id,
+      links: Vec::new(),
+      loaded,
+      text: Some("body".to_string()),
+      time: None,
+    };
+
+    state.mode = Mode::Comments(Box::new(CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![comment(123, false)],
+        score: None,
+        title: None,
+      },
+      "https://news.ycombinator.com/item?id=42".to_string(),
+    )));
+
+    if let Mode::Comments(view) = &mut state.mode {
+      view.expand_selected();
+    }
+
+    let dispatch = state.dispatch_command(Command::None).unwrap();
+
+    let request_id = match &dispatch.effects[..] {
+      [
+        Effect::FetchCommentChildren {
+          item_id,
+          request_id,
+        },
+      ] => {
+        assert_eq!(*item_id, 123);
+        *request_id
+      }
+      _ => panic!("expected a single fetch comment children effect"),
+    };
+
+    let dispatch = state.dispatch_command(Command::None).unwrap();
+
+    assert!(dispatch.effects.is_empty());
+
+    state.handle_event(Event::CommentChildren {
+      request_id,
+      result: Ok(vec![comment(456, true)]),
+    });
+
+    let Mode::Comments(view) = &state.mode else {
+      panic!("expected comments mode");
+    };
+
+    assert_eq!(view.visible_indexes(), [0, 1]);
+    assert_eq!(view.entries[1].id, 456);
+    assert!(view.entries[0].loaded);
+  }
+
+  #[test]
+  fn dump_comment_writes_raw_and_rendered_text() {
+    let mut state = sample_state_with_entry();
+
+    state.mode = Mode::Comments(Box::new(CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![Comment {
+          author: Some("user".to_string()),
+          children: Vec::new(),
+          dead: false,
+          deleted: false,
+          html: Some("<p>a &amp; b".to_string()),
+          id: 987_654_321,
+          links: Vec::new(),
+          loaded: true,
+          text: Some("a & b".to_string()),
+          time: None,
+        }],
+        score: None,
+        title: None,
+      },
+      "https://news.ycombinator.com/item?id=42".to_string(),
+    )));
+
+    state.dispatch_command(Command::DumpComment).unwrap();
+
+    let path = env::temp_dir().join("hn-comment-987654321.txt");
+
+    let contents = fs::read_to_string(&path).unwrap();
+
+    fs::remove_file(&path).unwrap();
+
+    assert!(contents.contains("--- raw ---\n<p>a &amp; b\n"));
+    assert!(contents.contains("--- rendered ---\na & b\n"));
+    assert_eq!(
+      state.message(),
+      format!("Wrote comment diagnostics to {}", path.display())
+    );
+  }
+
+  #[test]
+  fn view_in_pager_runs_pager_with_plain_body() {
+    let mut state = sample_state_with_entry();
+
+    state.mode = Mode::Comments(Box::new(CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![Comment {
+          author: Some("user".to_string()),
+          children: Vec::new(),
+          dead: false,
+          deleted: false,
+          html: Some("<p>a &amp; b".to_string()),
+          id: 987_654_322,
+          links: Vec::new(),
+          loaded: true,
+          text: Some("a & b".to_string()),
+          time: None,
+        }],
+        score: None,
+        title: None,
+      },
+      "https://news.ycombinator.com/item?id=42".to_string(),
+    )));
+
+    let dispatch = state.dispatch_command(Command::ViewInPager).unwrap();
+
+    match &dispatch.effects[..] {
+      [Effect::RunPager { text }] => assert_eq!(text, "a & b\n"),
+      _ => panic!("expected a single run pager effect"),
+    }
+  }
+
+  #[test]
+  fn copy_comment_link_copies_selected_comment_permalink() {
+    let mut state = sample_state_with_entry();
+
+    state.mode = Mode::Comments(Box::new(CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![Comment {
+          author: Some("user".to_string()),
+          children: Vec::new(),
+          dead: false,
+          deleted: false,
+          html: None,
+          id: 123,
+          links: Vec::new(),
+          loaded: true,
+          text: Some("body".to_string()),
+          time: None,
+        }],
+        score: None,
+        title: None,
+      },
+      "https://news.ycombinator.com/item?id=42".to_string(),
+    )));
+
+    let dispatch = state
+      .dispatch_command(Command::CopyCommentLink)
+      .expect("dispatch succeeds");
+
+    assert_eq!(dispatch.effects.len(), 1);
+
+    match &dispatch.effects[0] {
+      Effect::CopyToClipboard { label, text } => {
+        assert_eq!(*label, "comment link");
+        assert_eq!(text, "https://news.ycombinator.com/item?id=123");
+      }
+      _ => panic!("unexpected effect variant"),
+    }
+  }
+
+  #[test]
+  fn copy_comment_text_quotes_selected_comment_with_author() {
+    let mut state = state_with_comment_links(0);
+
+    let dispatch = state
+      .dispatch_command(Command::CopyCommentText)
+      .expect("dispatch succeeds");
+
+    match &dispatch.effects[..] {
+      [Effect::CopyToClipboard { label, text }] => {
+        assert_eq!(*label, "comment text");
+        assert_eq!(text, "user wrote:\nbody");
+      }
+      _ => panic!("expected a single copy effect"),
+    }
+  }
+
+  #[test]
+  fn toggle_follow_follows_and_unfollows_the_selected_author() {
+    let mut state = state_with_comment_links(0);
+
+    state.dispatch_command(Command::ToggleFollow).unwrap();
+
+    assert!(state.followed_users().contains("user"));
+    assert_eq!(state.message, "Following user");
+
+    state.dispatch_command(Command::ToggleFollow).unwrap();
+
+    assert!(!state.followed_users().contains("user"));
+    assert_eq!(state.message, "Unfollowed user");
+  }
+
+  #[test]
+  fn switching_to_unloaded_tab_emits_fetch_effect() {
+    let mut state = sample_state_with_entry();
+
+    let category = Category {
+      label: "new",
+      kind: CategoryKind::Stories("newstories"),
+    };
+
+    state.tabs.push(Tab {
+      category,
+      has_more: true,
+      label: category.label.into(),
+      loaded: false,
+    });
+
+    state.tab_views.push(Some(ListView::default()));
+    state.tab_loading.push(false);
+    state.tab_requests.push(None);
+    state.pending_selections.push(None);
+
+    let dispatch = state
+      .dispatch_command(Command::SwitchTabRight)
+      .expect("dispatch succeeds");
+
+    assert_eq!(state.active_tab, 1);
+    assert_eq!(dispatch.effects.len(), 1);
+
+    match &dispatch.effects[0] {
+      Effect::FetchTabItems {
+        offset, request_id, ..
+      } => {
+        assert_eq!(*request_id, tab_request(&state, 1));
+        assert_eq!(*offset, 0);
+      }
+      _ => panic!("unexpected effect variant"),
+    }
+
+    assert_eq!(state.tab_loading, vec![false, true]);
+
+    let dispatch = state
+      .dispatch_command(Command::SwitchTabLeft)
+      .expect("dispatch succeeds");
+
+    assert!(dispatch.effects.is_empty());
+  }
+
+  #[test]
+  fn restore_session_clamps_positions_to_shorter_lists() {
+    let mut state = sample_state_with_entry();
+
+    let mut session = Session::default();
+
+    session.set_tab_position(
+      "top",
+      TabPosition {
+        offset: 12,
+        selected: 15,
+      },
+    );
+
+    state.restore_session(&session).unwrap();
+
+    let view = state.list_view(0).expect("top tab exists");
+
+    assert_eq!(view.selected_index(), Some(0));
+    assert_eq!(view.offset(), 0);
+  }
+
+  #[test]
+  fn score_filter_skips_low_scoring_entries_when_navigating() {
+    let entries = [Some(5), Some(50), None, Some(8), Some(70)]
+      .into_iter()
+      .enumerate()
+      .map(|(id, score)| ListEntry {
+        comments: None,
+        detail: None,
+        id: id.to_string(),
+        score,
+        tags: Vec::new(),
+        time: None,
+        title: format!("Story {id}"),
+        url: None,
+      })
+      .collect::<Vec<_>>();
+
+    let tab = Tab {
+      category: Category {
+        label: "top",
+        kind: CategoryKind::Stories("topstories"),
+      },
+      has_more: false,
+      label: "top".into(),
+      loaded: true,
+    };
+
+    let mut state = State::new(
+      vec![(tab, ListView::new(entries))],
+      empty_bookmarks(),
+      ReadItems::default(),
+      SavedThreads::default(),
+      FollowedUsers::default(),
+      Config::default(),
+    );
+
+    let selected =
+      |state: &State| state.list_view(0).and_then(ListView::selected_index);
+
+    state
+      .dispatch_command(Command::AdjustScoreFilter(10))
+      .unwrap();
+
+    assert_eq!(state.min_score(), 10);
+    assert_eq!(selected(&state), Some(1));
+
+    state.dispatch_command(Command::SelectNext).unwrap();
+    assert_eq!(selected(&state), Some(2));
+
+    state.dispatch_command(Command::SelectNext).unwrap();
+    assert_eq!(selected(&state), Some(4));
+
+    state.dispatch_command(Command::SelectNext).unwrap();
+    assert_eq!(selected(&state), Some(4));
+
+    state.dispatch_command(Command::PageUp).unwrap();
+    assert_eq!(selected(&state), Some(2));
+
+    state.dispatch_command(Command::SelectPrevious).unwrap();
+    assert_eq!(selected(&state), Some(1));
+
+    state.dispatch_command(Command::SelectFirst).unwrap();
+    assert_eq!(selected(&state), Some(1));
+
+    state
+      .dispatch_command(Command::AdjustScoreFilter(-20))
+      .unwrap();
+
+    assert_eq!(state.min_score(), 0);
+
+    state.dispatch_command(Command::SelectFirst).unwrap();
+    assert_eq!(selected(&state), Some(0));
+
+    state
+      .dispatch_command(Command::AdjustScoreFilter(10))
+      .unwrap();
+
+    state.dispatch_command(Command::MarkAllRead).unwrap();
+
+    assert_eq!(state.message, "Marked 3 items as read");
+    assert!(state.is_read("1") && state.is_read("2") && state.is_read("4"));
+    assert!(!state.is_read("0") && !state.is_read("3"));
+  }
+
+  #[test]
+  fn local_filter_moves_selection_onto_a_shown_story() {
+    let mut state = numbered_state(12);
+
+    state.dispatch_command(Command::SelectNext).unwrap();
+    state.dispatch_command(Command::StartLocalFilter).unwrap();
+
+    let command = state
+      .search_input_command(KeyEvent::new(
+        KeyCode::Char('1'),
+        KeyModifiers::NONE,
+      ))
+      .unwrap();
+
+    state.dispatch_command(command).unwrap();
+
+    assert_eq!(state.current_entry().unwrap().id, "10");
+
+    state.dispatch_command(Command::SubmitSearch).unwrap();
+
+    state.tabs.push(Tab::new(Category::all()[1]));
+    state.tab_views.push(Some(ListView::default()));
+    state.tab_loading.push(false);
+    state.tab_requests.push(None);
+    state.pending_selections.push(None);
+
+    state.dispatch_command(Command::SwitchTabRight).unwrap();
+
+    assert_eq!(state.local_filter(), None);
+  }
+
+  #[test]
+  fn local_filter_narrows_navigation_until_cleared() {
+    let mut state = numbered_state(12);
+
+    let selected =
+      |state: &State| state.list_view(0).and_then(ListView::selected_index);
+
+    state.dispatch_command(Command::StartLocalFilter).unwrap();
+
+    let command = state
+      .search_input_command(KeyEvent::new(
+        KeyCode::Char('1'),
+        KeyModifiers::NONE,
+      ))
+      .unwrap();
+
+    state.dispatch_command(command).unwrap();
+
+    assert_eq!(state.local_filter(), Some("1"));
+    assert_eq!(state.incremental_query(), None);
+
+    state.dispatch_command(Command::SubmitSearch).unwrap();
+
+    assert!(!state.is_filtering());
+    assert_eq!(selected(&state), Some(0));
+
+    state.dispatch_command(Command::SelectNext).unwrap();
+    assert_eq!(selected(&state), Some(9));
+
+    state.dispatch_command(Command::SelectNext).unwrap();
+    assert_eq!(selected(&state), Some(10));
+
+    let command = state.handle_key(
+      KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
+      state.list_height(),
+    );
+
+    assert_eq!(command, Command::ClearLocalFilter);
+
+    state.dispatch_command(command).unwrap();
+
+    assert_eq!(state.local_filter(), None);
+    assert_eq!(state.list_view(0).map(ListView::len), Some(12));
+
+    state.dispatch_command(Command::SelectPrevious).unwrap();
+    assert_eq!(selected(&state), Some(9));
+  }
+
+  #[test]
+  fn session_round_trips_selection_and_offset() {
+    let entries = (0..10)
+      .map(|id| ListEntry {
+        comments: None,
+        detail: None,
+        id: id.to_string(),
+        score: None,
+        tags: Vec::new(),
+        time: None,
+        title: format!("Story {id}"),
+        url: None,
+      })
+      .collect::<Vec<_>>();
+
+    let tab = || Tab {
+      category: Category {
+        label: "top",
+        kind: CategoryKind::Stories("topstories"),
+      },
+      has_more: false,
+      label: "top".into(),
+      loaded: true,
+    };
+
+    let mut state = State::new(
+      vec![(tab(), ListView::new(entries.clone()))],
+      empty_bookmarks(),
+      ReadItems::default(),
+      SavedThreads::default(),
+      FollowedUsers::default(),
+      Config::default(),
+    );
+
+    state.mode_mut().set_offset(3);
+
+    if let Mode::List(view) = state.mode_mut() {
+      view.set_selected(6);
+    }
+
+    let mut session = Session::default();
+
+    state.store_session(&mut session);
+
+    let mut restored = State::new(
+      vec![(tab(), ListView::new(entries))],
+      empty_bookmarks(),
+      ReadItems::default(),
+      SavedThreads::default(),
+      FollowedUsers::default(),
+      Config::default(),
+    );
+
+    restored.restore_session(&session).unwrap();
+
+    let view = restored.list_view(0).expect("top tab exists");
+
+    assert_eq!(view.selected_index(), Some(6));
+    assert_eq!(view.offset(), 3);
+  }
+
+  #[test]
+  fn session_restores_active_tab() {
+    let tab = |label: &'static str| Tab {
+      category: Category {
+        label,
+        kind: CategoryKind::Stories("topstories"),
+      },
+      has_more: false,
+      label: label.into(),
+      loaded: true,
+    };
+
+    let state = || {
+      State::new(
+        vec![
+          (tab("top"), ListView::default()),
+          (tab("ask"), ListView::default()),
+        ],
+        empty_bookmarks(),
+        ReadItems::default(),
+        SavedThreads::default(),
+        FollowedUsers::default(),
+        Config::default(),
+      )
+    };
+
+    let mut session = Session::default();
+
+    session.set_active_tab(Some("ask"));
+
+    let mut restored = state();
+
+    restored.restore_session(&session).unwrap();
+
+    assert_eq!(restored.active_tab, 1);
+
+    session.set_active_tab(Some("gone"));
+
+    let mut restored = state();
+
+    restored.restore_session(&session).unwrap();
+
+    assert_eq!(restored.active_tab, 0);
+  }
+
+  #[test]
+  fn contrast_cycles_and_persists_in_session() {
+    let mut state = sample_state_with_entry();
+
+    state.dispatch_command(Command::CycleContrast).unwrap();
+
+    assert_eq!(state.contrast(), Contrast::Normal);
+    assert_eq!(state.message, "Comment contrast: normal");
+
+    let mut session = Session::default();
+
+    state.store_session(&mut session);
+
+    let mut restored = sample_state_with_entry();
+
+    restored.restore_session(&session).unwrap();
+
+    assert_eq!(restored.contrast(), Contrast::Normal);
+  }
+
+  #[test]
+  fn search_tab_is_not_stored_as_active_tab() {
+    let mut state = sample_state_with_entry();
+
+    state.active_tab = state.ensure_search_tab("rust", None);
+
+    let mut session = Session::default();
+
+    session.set_active_tab(Some("top"));
+
+    state.store_session(&mut session);
+
+    assert_eq!(session.active_tab(), None);
+  }
+
+  #[test]
+  fn placeholder_tabs_load_in_the_background_and_restore_selection() {
+    let state = |lazy: bool| {
+      let mut state = State::new(
+        Category::all()[..2]
+          .iter()
+          .map(|&category| (Tab::new(category), ListView::default()))
+          .collect(),
+        empty_bookmarks(),
+        ReadItems::default(),
+        SavedThreads::default(),
+        FollowedUsers::default(),
+        Config::default(),
+      );
+
+      let mut session = Session::default();
+
+      session.set_tab_position(
+        "top",
+        TabPosition {
+          offset: 2,
+          selected: 3,
+        },
+      );
+
+      state.restore_session(&session).unwrap();
+      state.start_loading_tabs(lazy).unwrap();
+
+      state
+    };
+
+    let mut lazy = state(true);
+
+    assert!(matches!(
+      lazy.take_pending_effects()[..],
+      [Effect::FetchTabItems { request_id, .. }]
+        if request_id == tab_request(&lazy, 0)
+    ));
+
+    let mut eager = state(false);
+
+    assert!(matches!(
+      eager.take_pending_effects()[..],
+      [
+        Effect::FetchTabItems { request_id: first, .. },
+        Effect::FetchTabItems { request_id: second, .. },
+      ] if first == tab_request(&eager, 0) && second == tab_request(&eager, 1)
+    ));
+
+    assert_eq!(eager.tab_loading(), [true, true]);
+
+    eager.handle_event(Event::TabItems {
+      request_id: tab_request(&eager, 0),
+      result: Ok(more_entries().into_iter().chain(more_entries()).collect()),
+    });
+
+    assert!(eager.tabs[0].loaded);
+    assert_eq!(eager.list_view(0).unwrap().selected_index(), Some(3));
+    assert_eq!(eager.list_view(0).unwrap().offset(), 2);
+    assert_eq!(eager.tab_loading(), [false, true]);
+  }
+
+  fn paginated_state() -> State {
+    let tab = Tab {
+      category: Category {
+        label: "top",
+        kind: CategoryKind::Stories("topstories"),
+      },
+      has_more: true,
+      label: "top".into(),
+      loaded: true,
+    };
+
+    let entries = (0..2)
+      .map(|id| ListEntry {
+        comments: None,
+        detail: None,
+        id: id.to_string(),
+        score: None,
+        tags: Vec::new(),
+        time: None,
+        title: format!("Story {id}"),
+        url: None,
+      })
+      .collect::<Vec<_>>();
+
+    State::new(
+      vec![(tab, ListView::new(entries))],
+      empty_bookmarks(),
+      ReadItems::default(),
+      SavedThreads::default(),
+      FollowedUsers::default(),
+      Config::default(),
+    )
+  }
+
+  fn more_entries() -> Vec<ListEntry> {
+    (2..4)
+      .map(|id| ListEntry {
+        comments: None,
+        detail: None,
+        id: id.to_string(),
+        score: None,
+        tags: Vec::new(),
+        time: None,
+        title: format!("Story {id}"),
+        url: None,
+      })
+      .collect()
+  }
+
+  #[test]
+  fn load_newer_prepends_unseen_comments_and_keeps_selection() {
+    let comment = |id: u64| ListEntry {
+      comments: None,
+      detail: None,
+      id: id.to_string(),
+      score: None,
+      tags: Vec::new(),
+      time: None,
+      title: format!("Comment {id}"),
+      url: None,
+    };
+
+    let tab = Tab {
+      category: Category {
+        label: "comments",
+        kind: CategoryKind::Comments,
+      },
+      has_more: true,
+      label: "comments".into(),
+      loaded: true,
+    };
+
+    let mut state = State::new(
+      vec![(tab, ListView::new(vec![comment(3), comment(2)]))],
+      empty_bookmarks(),
+      ReadItems::default(),
+      SavedThreads::default(),
+      FollowedUsers::default(),
+      Config::default(),
+    );
+
+    state.dispatch_command(Command::SelectNext).unwrap();
+
+    let dispatch = state.dispatch_command(Command::LoadNewer).unwrap();
+
+    assert!(matches!(
+      dispatch.effects[..],
+      [Effect::FetchNewerTabItems { request_id, .. }]
+        if request_id == tab_request(&state, 0)
+    ));
+
+    state.handle_event(Event::NewerTabItems {
+      request_id: tab_request(&state, 0),
+      result: Ok(vec![comment(5), comment(4), comment(3)]),
+    });
+
+    let ids = state
+      .list_view(0)
+      .unwrap()
+      .items()
+      .iter()
+      .map(|entry| entry.id.clone())
+      .collect::<Vec<_>>();
+
+    assert_eq!(ids, ["5", "4", "3", "2"]);
+    assert_eq!(state.current_entry().unwrap().id, "2");
+    assert_eq!(state.message, "2 new comments");
+
+    state.dispatch_command(Command::TogglePin).unwrap();
+    state.dispatch_command(Command::SelectNext).unwrap();
+
+    assert_eq!(state.current_entry().unwrap().id, "5");
+
+    state.dispatch_command(Command::LoadNewer).unwrap();
+
+    state.handle_event(Event::NewerTabItems {
+      request_id: tab_request(&state, 0),
+      result: Ok(vec![comment(7), comment(6), comment(5)]),
+    });
+
+    let ids = state
+      .list_view(0)
+      .unwrap()
+      .items()
+      .iter()
+      .map(|entry| entry.id.clone())
+      .collect::<Vec<_>>();
+
+    assert_eq!(ids, ["2", "7", "6", "5", "4", "3"]);
+    assert_eq!(state.current_entry().unwrap().id, "5");
+  }
+
+  #[test]
+  fn load_newer_is_only_available_on_the_comments_tab() {
+    let mut state = paginated_state();
+
+    let dispatch = state.dispatch_command(Command::LoadNewer).unwrap();
+
+    assert!(dispatch.effects.is_empty());
+    assert_eq!(
+      state.message,
+      "Only the comments tab can load newer entries"
+    );
+  }
+
+  #[test]
+  fn loaded_page_moves_selection_to_requested_entry() {
+    let mut state = paginated_state();
+
+    state.dispatch_command(Command::SelectNext).unwrap();
+
+    let dispatch = state.dispatch_command(Command::SelectNext).unwrap();
+
+    assert_eq!(dispatch.effects.len(), 1);
+
+    state.handle_event(Event::TabItems {
+      request_id: tab_request(&state, 0),
+      result: Ok(more_entries()),
+    });
+
+    assert_eq!(state.list_view(0).unwrap().selected_index(), Some(2));
+  }
+
+  #[test]
+  fn loaded_page_keeps_selection_after_manual_move() {
+    let mut state = paginated_state();
+
+    state.dispatch_command(Command::SelectNext).unwrap();
+
+    let dispatch = state.dispatch_command(Command::SelectNext).unwrap();
+
+    assert_eq!(dispatch.effects.len(), 1);
+
+    state.dispatch_command(Command::SelectPrevious).unwrap();
+
+    state.handle_event(Event::TabItems {
+      request_id: tab_request(&state, 0),
+      result: Ok(more_entries()),
+    });
+
+    let view = state.list_view(0).unwrap();
+
+    assert_eq!(view.len(), 4);
+    assert_eq!(view.selected_index(), Some(0));
+  }
+
+  #[test]
+  fn accent_color_follows_active_tab() {
+    let config =
+      toml::from_str::<Config>("[accents]\nnew = \"green\"").unwrap();
+
+    let tab = |label: &'static str| Tab {
+      category: Category {
+        label,
+        kind: CategoryKind::Stories("topstories"),
+      },
+      has_more: false,
+      label: label.into(),
+      loaded: true,
+    };
+
+    let mut state = State::new(
+      vec![
+        (tab("top"), ListView::default()),
+        (tab("new"), ListView::default()),
+      ],
+      empty_bookmarks(),
+      ReadItems::default(),
+      SavedThreads::default(),
+      FollowedUsers::default(),
+      config,
+    );
+
+    assert_eq!(state.accent_color(), Color::Cyan);
+
+    state.dispatch_command(Command::SwitchTabRight).unwrap();
+
+    assert_eq!(state.accent_color(), Color::Green);
+  }
+
+  #[test]
+  fn pinned_entries_stay_first_after_loading_more() {
+    let mut state = paginated_state();
+
+    state.dispatch_command(Command::SelectNext).unwrap();
+    state.dispatch_command(Command::TogglePin).unwrap();
+
+    let ids = |state: &State| {
+      state
+        .list_view(0)
+        .unwrap()
+        .items()
+        .iter()
+        .map(|entry| entry.id.clone())
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(ids(&state), ["1", "0"]);
+    assert_eq!(state.current_entry().unwrap().id, "1");
+
+    state.pinned.insert("3".into());
+
+    state.start_load_for_tab(0, false).unwrap();
+    state.take_pending_effects();
+
+    state.handle_event(Event::TabItems {
+      request_id: tab_request(&state, 0),
+      result: Ok(more_entries()),
+    });
+
+    assert_eq!(ids(&state), ["1", "3", "0", "2"]);
+
+    state.dispatch_command(Command::TogglePin).unwrap();
+
+    assert!(!state.is_pinned("1"));
+    assert_eq!(ids(&state), ["3", "0", "1", "2"]);
+
+    state.dispatch_command(Command::SelectFirst).unwrap();
+    state.dispatch_command(Command::TogglePin).unwrap();
+
+    assert_eq!(ids(&state), ["0", "1", "2", "3"]);
+  }
+
+  #[test]
+  fn pinned_entries_stay_first_after_a_refresh() {
+    let mut state = paginated_state();
+
+    let entries = state
+      .list_view(0)
+      .unwrap()
+      .items()
+      .iter()
+      .cloned()
+      .chain(more_entries())
+      .collect();
+
+    state.dispatch_command(Command::SelectNext).unwrap();
+    state.dispatch_command(Command::TogglePin).unwrap();
+    state.dispatch_command(Command::RefreshTab).unwrap();
+
+    state.handle_event(Event::TabItems {
+      request_id: tab_request(&state, 0),
+      result: Ok(entries),
+    });
+
+    assert_eq!(
+      state
+        .list_view(0)
+        .unwrap()
+        .items()
+        .iter()
+        .map(|entry| entry.id.as_str())
+        .collect::<Vec<_>>(),
+      ["1", "0", "2", "3"]
+    );
+    assert_eq!(state.current_entry().unwrap().id, "1");
+  }
+
+  fn state_with_comment_links(count: usize) -> State {
+    let mut state = sample_state_with_entry();
+
+    state.mode = Mode::Comments(Box::new(CommentView::new(
+      CommentThread {
+        body: None,
+        body_links: Vec::new(),
+        descendants: None,
+        focus: None,
+        id: 1,
+        op_author: None,
+        partial: false,
+        roots: vec![Comment {
+          author: Some("user".to_string()),
+          children: Vec::new(),
+          dead: false,
+          deleted: false,
+          html: None,
+          id: 7,
+          links: (0..count)
+            .map(|index| format!("https://example.com/{index}"))
+            .collect(),
+          loaded: true,
+          text: Some("body".to_string()),
+          time: None,
+        }],
+        score: None,
+        title: None,
+      },
+      "https://news.ycombinator.com/item?id=42".to_string(),
+    )));
+
+    state
+  }
+
+  fn opened_urls(dispatch: &CommandDispatch) -> Vec<String> {
+    dispatch
+      .effects
+      .iter()
+      .filter_map(|effect| match effect {
+        Effect::OpenUrl { url } => Some(url.clone()),
+        _ => None,
+      })
+      .collect()
+  }
+
+  #[test]
+  fn open_comment_links_opens_every_link() {
+    let mut state = state_with_comment_links(2);
+
+    let dispatch = state.dispatch_command(Command::OpenCommentLinks).unwrap();
+
+    assert_eq!(
+      opened_urls(&dispatch),
+      ["https://example.com/0", "https://example.com/1"]
+    );
+  }
+
+  #[test]
+  fn open_comment_links_requires_confirmation_for_many_links() {
+    let mut state = state_with_comment_links(5);
+
+    let dispatch = state.dispatch_command(Command::OpenCommentLinks).unwrap();
+
+    assert!(dispatch.effects.is_empty());
+    assert_eq!(state.message, "Press O again to open 5 links");
+
+    let dispatch = state.dispatch_command(Command::OpenCommentLinks).unwrap();
+
+    assert_eq!(opened_urls(&dispatch).len(), 5);
+  }
+
+  #[test]
+  fn open_comment_links_caps_the_number_of_opened_links() {
+    let mut state = state_with_comment_links(14);
+
+    state.dispatch_command(Command::OpenCommentLinks).unwrap();
+
+    assert_eq!(state.message, "Press O again to open 10 links");
+
+    let dispatch = state.dispatch_command(Command::OpenCommentLinks).unwrap();
+
+    assert_eq!(opened_urls(&dispatch).len(), 10);
+    assert_eq!(state.message, "Opened 10 of 14 links");
+  }
+
+  #[test]
+  fn open_comment_links_confirmation_is_reset_by_other_commands() {
+    let mut state = state_with_comment_links(5);
+
+    state.dispatch_command(Command::OpenCommentLinks).unwrap();
+    state.dispatch_command(Command::None).unwrap();
+
+    let dispatch = state.dispatch_command(Command::OpenCommentLinks).unwrap();
+
+    assert!(dispatch.effects.is_empty());
+  }
+
+  fn deferred_bookmarks_state() -> State {
+    let mut bookmarks = empty_bookmarks();
+
+    bookmarks
+      .toggle(&ListEntry {
+        comments: None,
+        detail: None,
+        id: "7".to_string(),
+        score: None,
+        tags: Vec::new(),
+        time: None,
+        title: "Saved".to_string(),
+        url: None,
+      })
+      .unwrap();
+
+    let entry = ListEntry {
+      comments: None,
+      detail: None,
+      id: "1".to_string(),
+      score: None,
+      tags: Vec::new(),
+      time: None,
+      title: "Item".to_string(),
+      url: None,
+    };
+
+    State::new(
+      vec![(
+        Tab {
+          category: Category::all()[0],
+          has_more: false,
+          label: "top".into(),
+          loaded: true,
+        },
+        ListView::new(vec![entry]),
+      )],
+      bookmarks,
+      ReadItems::default(),
+      SavedThreads::default(),
+      FollowedUsers::default(),
+      toml::from_str("defer_bookmarks = true").unwrap(),
+    )
+  }
+
+  #[test]
+  fn tagged_bookmarks_can_be_filtered_by_tag() {
+    let mut state = deferred_bookmarks_state();
+
+    state.dispatch_command(Command::CycleTagFilter).unwrap();
+
+    assert_eq!(state.message, "Tag filters only apply to the bookmarks tab");
+
+    state.dispatch_command(Command::ToggleBookmark).unwrap();
+    state.dispatch_command(Command::ShowBookmarks).unwrap();
+
+    let ids = |state: &State| {
+      state
+        .list_view(1)
+        .unwrap()
+        .items()
+        .iter()
+        .map(|entry| entry.id.clone())
+        .collect::<Vec<String>>()
+    };
+
+    assert_eq!(ids(&state), ["1", "7"]);
+
+    state.dispatch_command(Command::EditTags).unwrap();
+
+    assert_eq!(state.message, "Tags: ");
+
+    if let Some(input) = state.search_input.as_mut() {
+      input.buffer.push_str("rust, news rust");
+    }
+
+    state.dispatch_command(Command::SubmitSearch).unwrap();
+
+    assert_eq!(state.message, "Tagged with rust, news");
+
+    state.dispatch_command(Command::CycleTagFilter).unwrap();
+
+    assert_eq!(state.tag_filter(), Some("news"));
+    assert_eq!(ids(&state), ["1"]);
+
+    state.dispatch_command(Command::CycleTagFilter).unwrap();
+    state.dispatch_command(Command::CycleTagFilter).unwrap();
+
+    assert_eq!(state.tag_filter(), None);
+    assert_eq!(ids(&state), ["1", "7"]);
+  }
+
+  #[test]
+  fn deferred_bookmarks_tab_is_created_when_requested() {
+    let mut state = deferred_bookmarks_state();
+
+    assert_eq!(state.tabs.len(), 1);
+    assert!(state.bookmarks_tab_index.is_none());
+
+    state.dispatch_command(Command::ShowBookmarks).unwrap();
+
+    assert_eq!(state.bookmarks_tab_index, Some(1));
+    assert_eq!(state.active_tab, 1);
+    assert_eq!(
+      state.current_entry().map(|entry| entry.id.as_str()),
+      Some("7")
+    );
+  }
+
+  #[test]
+  fn deferred_bookmarks_tab_is_created_when_cycling_past_the_end() {
+    let mut state = deferred_bookmarks_state();
+
+    state.dispatch_command(Command::SwitchTabRight).unwrap();
+
+    assert_eq!(state.tabs.len(), 2);
+    assert_eq!(state.active_tab, 1);
+  }
+
+  #[test]
+  fn deferred_bookmarks_tab_is_created_when_picked_in_the_switcher() {
+    let mut state = deferred_bookmarks_state();
+
+    state.dispatch_command(Command::ShowTabSwitcher).unwrap();
+
+    assert_eq!(state.tabs.len(), 1);
+    assert_eq!(state.switcher_tabs().len(), 2);
+
+    let command = state
+      .tab_switcher_command(KeyEvent::from(KeyCode::Esc))
+      .unwrap();
+
+    state.dispatch_command(command).unwrap();
+
+    assert!(state.bookmarks_tab_index.is_none());
+
+    state.dispatch_command(Command::ShowTabSwitcher).unwrap();
+
+    for ch in "book".chars() {
+      state
+        .tab_switcher_command(KeyEvent::from(KeyCode::Char(ch)))
+        .unwrap();
+    }
+
+    let command = state
+      .tab_switcher_command(KeyEvent::from(KeyCode::Enter))
+      .unwrap();
+
+    state.dispatch_command(command).unwrap();
+
+    assert_eq!(state.bookmarks_tab_index, Some(1));
+    assert_eq!(state.active_tab, 1);
+    assert_eq!(
+      state.current_entry().map(|entry| entry.id.as_str()),
+      Some("7")
+    );
+  }
+
+  #[test]
+  fn refresh_tab_refetches_and_restores_selection() {
+    let mut state = paginated_state();
+
+    state.dispatch_command(Command::SelectNext).unwrap();
+
+    let dispatch = state.dispatch_command(Command::RefreshTab).unwrap();
+
+    match &dispatch.effects[..] {
+      [
+        Effect::FetchTabItems {
+          fresh,
+          offset,
+          request_id,
+          ..
+        },
+      ] => {
+        assert!(*fresh);
+        assert_eq!(*request_id, tab_request(&state, 0));
+        assert_eq!(*offset, 0);
+      }
+      _ => panic!("expected a single fetch tab items effect"),
+    }
+
+    assert!(state.list_view(0).unwrap().is_empty());
+
+    state.handle_event(Event::TabItems {
+      request_id: tab_request(&state, 0),
+      result: Ok(more_entries()),
+    });
+
+    assert_eq!(state.list_view(0).unwrap().selected_index(), Some(1));
+  }
+
+  #[test]
+  fn tab_pages_follow_their_tab_when_an_earlier_tab_closes() {
+    let mut state = State::new(
+      Category::all()[..3]
+        .iter()
+        .map(|&category| (Tab::new(category), ListView::default()))
+        .collect(),
+      empty_bookmarks(),
+      ReadItems::default(),
+      SavedThreads::default(),
+      FollowedUsers::default(),
+      Config::default(),
+    );
+
+    state.start_load_for_tab(2, false).unwrap();
+    state.take_pending_effects();
+
+    let request_id = tab_request(&state, 2);
+
+    state.remove_tab(1);
+
+    state.handle_event(Event::TabItems {
+      request_id,
+      result: Ok(more_entries()),
+    });
+
+    assert_eq!(state.list_view(1).unwrap().len(), 2);
+    assert!(state.tabs[1].loaded);
+    assert_eq!(state.tab_loading(), [false, false]);
+  }
+
+  #[test]
+  fn refresh_tab_drops_pages_from_before_the_refresh() {
+    let mut state = paginated_state();
+
+    state.start_load_for_tab(0, false).unwrap();
+    state.take_pending_effects();
+
+    let stale = tab_request(&state, 0);
+
+    state.dispatch_command(Command::RefreshTab).unwrap();
+
+    state.handle_event(Event::TabItems {
+      request_id: stale,
+      result: Ok(more_entries()),
+    });
+
+    assert!(state.list_view(0).unwrap().is_empty());
+    assert!(state.tab_loading()[0]);
+
+    state.handle_event(Event::TabItems {
+      request_id: tab_request(&state, 0),
+      result: Ok(more_entries()),
+    });
+
+    assert_eq!(state.list_view(0).unwrap().len(), 2);
+    assert!(!state.tab_loading()[0]);
+  }
+
+  #[test]
+  fn refresh_tab_reruns_last_search() {
+    let mut state = sample_state_with_entry();
+
+    state.dispatch_command(Command::StartSearch).unwrap();
+
+    if let Some(input) = state.search_input.as_mut() {
+      input.buffer.push_str("rust");
+    }
+
+    state.dispatch_command(Command::SubmitSearch).unwrap();
+
+    let dispatch = state.dispatch_command(Command::RefreshTab).unwrap();
+
+    match &dispatch.effects[..] {
+      [Effect::FetchSearchResults { query, .. }] => assert_eq!(query, "rust"),
+      _ => panic!("expected a single fetch search results effect"),
+    }
+  }
+
+  #[test]
+  fn repeat_search_reopens_the_prompt_with_the_last_query() {
+    let mut state = sample_state_with_entry();
+
+    state.dispatch_command(Command::RepeatSearch).unwrap();
+
+    assert!(state.search_input.is_none());
+    assert_eq!(state.message(), "No previous search");
+
+    state.dispatch_command(Command::StartSearch).unwrap();
+
+    if let Some(input) = state.search_input.as_mut() {
+      input.buffer.push_str("rust");
+    }
+
+    state.dispatch_command(Command::SubmitSearch).unwrap();
+    state.dispatch_command(Command::SwitchTabLeft).unwrap();
+    state.dispatch_command(Command::RepeatSearch).unwrap();
+
+    assert_eq!(state.message(), "Search: rust");
+
+    let dispatch = state.dispatch_command(Command::SubmitSearch).unwrap();
+
+    match &dispatch.effects[..] {
+      [Effect::FetchSearchResults { query, .. }] => assert_eq!(query, "rust"),
+      _ => panic!("expected a single fetch search results effect"),
+    }
+  }
+
+  #[test]
+  fn cycling_search_sort_reruns_search_with_new_sort() {
+    let mut state = sample_state_with_entry();
+
+    let dispatch = state.dispatch_command(Command::CycleSearchSort).unwrap();
+
+    assert!(dispatch.effects.is_empty());
+
+    assert_eq!(state.message(), "Sorting only applies to search results");
+
+    state.dispatch_command(Command::StartSearch).unwrap();
+
+    if let Some(input) = state.search_input.as_mut() {
+      input.buffer.push_str("rust");
+    }
+
+    state.dispatch_command(Command::SubmitSearch).unwrap();
+
+    let dispatch = state.dispatch_command(Command::CycleSearchSort).unwrap();
+
+    match &dispatch.effects[..] {
+      [Effect::FetchSearchResults { query, sort, .. }] => {
+        assert_eq!(query, "rust");
+        assert_eq!(*sort, SearchSort::Date);
+      }
+      _ => panic!("expected a single fetch search results effect"),
+    }
+
+    assert_eq!(state.search_sort(), Some(SearchSort::Date));
+
+    state.dispatch_command(Command::StartSearch).unwrap();
+
+    if let Some(input) = state.search_input.as_mut() {
+      input.buffer.push_str("zig");
+    }
+
+    let dispatch = state.dispatch_command(Command::SubmitSearch).unwrap();
+
+    assert!(matches!(
+      &dispatch.effects[..],
+      [Effect::FetchSearchResults {
+        sort: SearchSort::Relevance,
+        ..
+      }]
+    ));
+    assert_eq!(state.search_sort(), Some(SearchSort::Relevance));
+
+    state.dispatch_command(Command::SwitchTabLeft).unwrap();
+
+    assert_eq!(state.search_sort(), Some(SearchSort::Date));
+
+    let dispatch = state.dispatch_command(Command::RefreshTab).unwrap();
+
+    assert!(matches!(
+      &dispatch.effects[..],
+      [Effect::FetchSearchResults {
+        sort: SearchSort::Date,
+        ..
+      }]
+    ));
+  }
+
+  #[test]
+  fn incremental_search_keeps_prompt_and_skips_repeated_queries() {
+    let mut state = sample_state_with_entry();
+
+    state.dispatch_command(Command::StartSearch).unwrap();
+
+    for ch in "rust ".chars() {
+      state.search_input_command(KeyEvent::new(
+        KeyCode::Char(ch),
+        KeyModifiers::NONE,
+      ));
+    }
+
+    state.search_incrementally();
+
+    let effects = state.take_pending_effects();
+
+    let request_id = match &effects[..] {
+      [
+        Effect::FetchSearchResults {
+          query, request_id, ..
+        },
+      ] => {
+        assert_eq!(query, "rust");
+        *request_id
+      }
+      _ => panic!("expected a single fetch search results effect"),
+    };
+
+    assert_eq!(state.message(), "Search: rust ");
+
+    state.search_incrementally();
+
+    assert!(state.take_pending_effects().is_empty());
+
+    state.handle_event(Event::SearchResults {
+      request_id,
+      result: Ok((Vec::new(), false)),
+    });
+
+    assert_eq!(state.message(), "Search: rust ");
+
+    state.dispatch_command(Command::SubmitSearch).unwrap();
+
+    assert!(state.search_input.is_none());
+    assert_eq!(state.incremental_query(), None);
+  }
+
+  #[test]
+  fn each_query_gets_its_own_search_tab_up_to_a_cap() {
+    let mut state = sample_state_with_entry();
+
+    let search = |state: &mut State, query: &str| {
+      state.dispatch_command(Command::StartSearch).unwrap();
+
+      if let Some(input) = state.search_input.as_mut() {
+        input.buffer.push_str(query);
+      }
+
+      match &state
+        .dispatch_command(Command::SubmitSearch)
+        .unwrap()
+        .effects[..]
+      {
+        [Effect::FetchSearchResults { request_id, .. }] => *request_id,
+        _ => panic!("expected a single fetch search results effect"),
+      }
+    };
+
+    let rust = search(&mut state, "rust");
+    let go = search(&mut state, "go");
+
+    assert_eq!(state.tabs.len(), 3);
+    assert_eq!(state.active_tab, 2);
+    assert_eq!(state.tabs[1].label, "\"rust\"");
+    assert_eq!(state.tabs[2].label, "\"go\"");
+
+    let entry = state.list_view(0).unwrap().selected_item().unwrap().clone();
+
+    state.handle_event(Event::SearchResults {
+      request_id: rust,
+      result: Ok((vec![entry], false)),
+    });
+
+    state.handle_event(Event::SearchResults {
+      request_id: go,
+      result: Ok((Vec::new(), false)),
+    });
+
+    assert_eq!(state.list_view(1).unwrap().len(), 1);
+    assert_eq!(state.list_view(2).unwrap().len(), 0);
+
+    search(&mut state, "rust");
+
+    assert_eq!(state.tabs.len(), 3);
+    assert_eq!(state.active_tab, 1);
+
+    search(&mut state, "zig");
+    search(&mut state, "ocaml");
+
+    assert_eq!(state.tabs.len(), 4);
+    assert_eq!(
+      state.tabs[1..]
+        .iter()
+        .map(|tab| tab.label.as_str())
+        .collect::<Vec<&str>>(),
+      ["\"rust\"", "\"ocaml\"", "\"zig\""]
+    );
+
+    state.active_tab = 0;
+    state.dispatch_command(Command::CloseSearchTab).unwrap();
+
+    assert_eq!(state.tabs.len(), 4);
+    assert_eq!(state.message(), "Only search tabs can be closed");
+
+    state.store_active_list_view();
+    state.active_tab = 2;
+    state.restore_active_list_view();
+    state.dispatch_command(Command::CloseSearchTab).unwrap();
+
+    assert_eq!(state.tabs.len(), 3);
+    assert_eq!(state.active_tab, 2);
+    assert_eq!(state.tabs[2].label, "\"zig\"");
+    assert_eq!(state.active_search_query().as_deref(), Some("zig"));
+  }
+
+  #[test]
+  fn submitting_item_url_opens_comments_instead_of_searching() {
+    let mut state = sample_state_with_entry();
+
+    state.dispatch_command(Command::StartSearch).unwrap();
+
+    if let Some(input) = state.search_input.as_mut() {
+      input
+        .buffer
+        .push_str("https://news.ycombinator.com/item?id=12345");
+    }
+
+    let dispatch = state.dispatch_command(Command::SubmitSearch).unwrap();
+
+    match &dispatch.effects[..] {
+      [Effect::FetchComments { item_id, .. }] => assert_eq!(*item_id, 12345),
+      _ => panic!("expected a single fetch comments effect"),
+    }
+
+    state.dispatch_command(Command::StartSearch).unwrap();
+
+    if let Some(input) = state.search_input.as_mut() {
+      input.buffer.push_str("https://example.com/item?id=12345");
+    }
+
+    let dispatch = state.dispatch_command(Command::SubmitSearch).unwrap();
+
+    assert!(matches!(
+      &dispatch.effects[..],
+      [Effect::FetchSearchResults { .. }]
+    ));
+  }
+
+  #[test]
+  fn command_palette_runs_the_chosen_command() {
+    let mut state = numbered_state(3);
+
+    let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+    let command = state.handle_key(press(KeyCode::Char(':')), 1);
+
+    assert_eq!(command, Command::ShowCommandPalette);
+
+    state.dispatch_command(command).unwrap();
+
+    for ch in "beside the list".chars() {
+      assert_eq!(
+        state.command_palette_command(press(KeyCode::Char(ch)), 1),
+        Some(Command::None)
+      );
+    }
+
+    let command = state
+      .command_palette_command(press(KeyCode::Enter), 1)
+      .unwrap();
+
+    assert_eq!(command, Command::ToggleSplitView);
+    assert!(state.command_palette().is_none());
+
+    state.dispatch_command(command).unwrap();
+
+    assert!(state.split_view);
+
+    state.dispatch_command(Command::ShowCommandPalette).unwrap();
+
+    let command = state
+      .command_palette_command(press(KeyCode::Esc), 1)
+      .unwrap();
+
+    state.dispatch_command(command).unwrap();
+
+    assert!(state.command_palette().is_none());
+    assert!(state.split_view);
+  }
+
+  #[test]
+  fn switch_to_tab_jumps_by_number_and_ignores_missing_tabs() {
+    let tab = |label: &'static str| Tab {
+      category: Category {
+        label,
+        kind: CategoryKind::Stories("topstories"),
+      },
+      has_more: false,
+      label: label.into(),
+      loaded: true,
+    };
+
+    let mut state = State::new(
+      vec![
+        (tab("top"), ListView::new(Vec::new())),
+        (tab("new"), ListView::new(Vec::new())),
+        (tab("show"), ListView::new(Vec::new())),
+      ],
+      empty_bookmarks(),
+      ReadItems::default(),
+      SavedThreads::default(),
+      FollowedUsers::default(),
+      Config::default(),
+    );
+
+    let command = state
+      .handle_key(KeyEvent::new(KeyCode::Char('3'), KeyModifiers::ALT), 10);
+
+    assert_eq!(command, Command::SwitchToTab(2));
+
+    state.dispatch_command(command).unwrap();
+    assert_eq!(state.active_tab, 2);
+
+    state.dispatch_command(Command::SwitchToTab(8)).unwrap();
+    assert_eq!(state.active_tab, 2);
+
+    assert_eq!(
+      state
+        .handle_key(KeyEvent::new(KeyCode::Char('3'), KeyModifiers::NONE), 10),
+      Command::None
+    );
+    assert_eq!(state.count(), Some(3));
+  }
+
+  #[test]
+  fn tab_switcher_jumps_to_chosen_tab() {
+    let tab = |label: &'static str| Tab {
+      category: Category {
+        label,
+        kind: CategoryKind::Stories("topstories"),
+      },
+      has_more: false,
+      label: label.into(),
+      loaded: true,
+    };
+
+    let mut state = State::new(
+      vec![
+        (tab("top"), ListView::new(Vec::new())),
+        (tab("new"), ListView::new(Vec::new())),
+        (tab("show"), ListView::new(Vec::new())),
+      ],
+      empty_bookmarks(),
+      ReadItems::default(),
+      SavedThreads::default(),
+      FollowedUsers::default(),
+      Config::default(),
+    );
+
+    state.dispatch_command(Command::ShowTabSwitcher).unwrap();
+
+    for ch in "sh".chars() {
+      let command = state
+        .tab_switcher_command(KeyEvent::new(
+          KeyCode::Char(ch),
+          KeyModifiers::NONE,
+        ))
+        .unwrap();
+
+      assert_eq!(command, Command::None);
+    }
+
+    let command = state
+      .tab_switcher_command(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+      .unwrap();
+
+    state.dispatch_command(command).unwrap();
+
+    assert_eq!(state.active_tab, 2);
+    assert!(state.tab_switcher().is_none());
+
+    state.dispatch_command(Command::ShowTabSwitcher).unwrap();
+
+    let command = state
+      .tab_switcher_command(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))
+      .unwrap();
+
+    state.dispatch_command(command).unwrap();
+
+    assert_eq!(state.active_tab, 2);
+    assert!(
+      state
+        .tab_switcher_command(KeyEvent::from(KeyCode::Enter))
+        .is_none()
+    );
+  }
+
   #[test]
   fn start_search_sets_search_input() {
     let mut state = sample_state_with_entry();
@@ -0,0 +1,266 @@
+use super::*;
+
+const NAVIGATION: &[(&[Command], &str)] = &[
+  (&[Command::SelectPrevious], "up"),
+  (&[Command::SelectNext], "down"),
+];
+
+const SWITCH_TABS: (&[Command], &str) = (
+  &[Command::SwitchTabLeft, Command::SwitchTabRight],
+  "switch tabs",
+);
+
+const QUIT: (&[Command], &str) = (&[Command::Quit], "quit");
+
+const HELP: (&[Command], &str) = (&[Command::ShowHelp], "help");
+
+pub(crate) fn comments_status(keymap: &Keymap) -> String {
+  keymap.hint(
+    true,
+    &[
+      NAVIGATION[0],
+      NAVIGATION[1],
+      (&[Command::CollapseComment], "collapse"),
+      (&[Command::ExpandComment], "expand"),
+      (&[Command::ToggleComment], "toggle"),
+      (&[Command::OpenCommentLink], "open comment"),
+      (&[Command::ToggleBookmark], "bookmark"),
+      (&[Command::CloseComments], "back"),
+    ],
+  )
+}
+
+pub(crate) fn help_status(keymap: &Keymap) -> String {
+  let close = keymap
+    .key(false, Command::ShowHelp)
+    .map_or_else(|| "esc".into(), |key| format!("{key} or esc"));
+
+  let scroll = keymap.hint(
+    false,
+    &[(&[Command::SelectNext, Command::SelectPrevious], "to scroll")],
+  );
+
+  if scroll.is_empty() {
+    format!("Press {close} to close help")
+  } else {
+    format!("Press {close} to close help, {scroll}")
+  }
+}
+
+pub(crate) fn list_status(keymap: &Keymap) -> String {
+  list_hint(keymap, (&[Command::OpenComments], "comments"))
+}
+
+fn list_hint(keymap: &Keymap, open: (&[Command], &str)) -> String {
+  keymap.hint(
+    false,
+    &[
+      NAVIGATION[0],
+      NAVIGATION[1],
+      open,
+      (&[Command::OpenCurrentInBrowser], "open link"),
+      (&[Command::ToggleBookmark], "bookmark"),
+      QUIT,
+      HELP,
+    ],
+  )
+}
+
+pub(crate) fn status_for(
+  mode: &Mode,
+  category: Option<CategoryKind>,
+  has_selection: bool,
+  keymap: &Keymap,
+) -> String {
+  let view = match mode {
+    Mode::Comments(view) if view.is_partial() => {
+      let reload =
+        keymap.hint(true, &[(&[Command::LoadFullThread], "to load the rest")]);
+
+      return if reload.is_empty() {
+        "Partial thread, fetching took too long".into()
+      } else {
+        format!("Partial thread, fetching took too long • press {reload}")
+      };
+    }
+    Mode::Comments(_) => return comments_status(keymap),
+    Mode::List(view) => view,
+  };
+
+  let hint = |message: &str, items: &[(&[Command], &str)]| {
+    let hint = keymap.hint(false, items);
+
+    if hint.is_empty() {
+      message.to_string()
+    } else {
+      format!("{message} • {hint}")
+    }
+  };
+
+  match (category, has_selection) {
+    (Some(CategoryKind::Search), false) => hint(
+      "No results",
+      &[
+        (&[Command::StartSearch], "search again"),
+        SWITCH_TABS,
+        QUIT,
+        HELP,
+      ],
+    ),
+    (Some(CategoryKind::Bookmarks), false) => hint(
+      "No bookmarks here",
+      &[
+        (&[Command::CycleTagFilter], "change tag filter"),
+        SWITCH_TABS,
+        QUIT,
+        HELP,
+      ],
+    ),
+    (_, false) if view.is_empty() => hint(
+      "Nothing to show",
+      &[(&[Command::RefreshTab], "reload"), SWITCH_TABS, QUIT, HELP],
+    ),
+    (_, false) => hint(
+      "Nothing above the score filter",
+      &[(&[Command::AdjustScoreFilter(-10)], "lower it"), QUIT, HELP],
+    ),
+    (Some(CategoryKind::Search), true) => keymap.hint(
+      false,
+      &[
+        NAVIGATION[0],
+        NAVIGATION[1],
+        (&[Command::OpenComments], "comments"),
+        (&[Command::OpenCurrentInBrowser], "open link"),
+        (&[Command::CycleSearchSort], "sort"),
+        (&[Command::StartSearch], "search again"),
+        HELP,
+      ],
+    ),
+    (Some(CategoryKind::Front { .. }), true) => keymap.hint(
+      false,
+      &[
+        NAVIGATION[0],
+        NAVIGATION[1],
+        (&[Command::OpenComments], "comments"),
+        (&[Command::ShiftDay(-1)], "newer day"),
+        (&[Command::ShiftDay(1)], "older day"),
+        QUIT,
+        HELP,
+      ],
+    ),
+    (Some(kind), true) if kind.is_jobs() => {
+      list_hint(keymap, (&[Command::OpenComments], "open posting"))
+    }
+    (_, true) => list_status(keymap),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn list_mode(titles: &[&str]) -> Mode {
+    Mode::List(ListView::new(
+      titles
+        .iter()
+        .enumerate()
+        .map(|(index, title)| ListEntry {
+          comments: None,
+          detail: None,
+          id: index.to_string(),
+          score: None,
+          tags: Vec::new(),
+          time: None,
+          title: (*title).to_string(),
+          url: None,
+        })
+        .collect(),
+    ))
+  }
+
+  #[test]
+  fn empty_search_tab_suggests_searching_again() {
+    let status = status_for(
+      &list_mode(&[]),
+      Some(CategoryKind::Search),
+      false,
+      &Keymap::default(),
+    );
+
+    assert!(status.starts_with("No results • / search again"));
+    assert!(!status.contains("enter comments"));
+  }
+
+  #[test]
+  fn front_tab_describes_day_paging() {
+    let status = status_for(
+      &list_mode(&["Story"]),
+      Some(CategoryKind::Front {
+        days_ago: 1,
+        now: None,
+      }),
+      true,
+      &Keymap::default(),
+    );
+
+    assert!(status.contains("[ newer day • ] older day"));
+  }
+
+  #[test]
+  fn jobs_tab_offers_to_open_the_posting() {
+    let status = status_for(
+      &list_mode(&["Hiring"]),
+      Some(CategoryKind::Stories("jobstories")),
+      true,
+      &Keymap::default(),
+    );
+
+    assert!(status.contains("enter open posting"));
+    assert!(!status.contains("enter comments"));
+  }
+
+  #[test]
+  fn other_story_tabs_use_the_default_hints() {
+    assert_eq!(
+      status_for(
+        &list_mode(&["Story"]),
+        Some(CategoryKind::Stories("topstories")),
+        true,
+        &Keymap::default(),
+      ),
+      list_status(&Keymap::default())
+    );
+
+    assert!(
+      status_for(
+        &list_mode(&["Story"]),
+        Some(CategoryKind::Stories("topstories")),
+        false,
+        &Keymap::default(),
+      )
+      .starts_with("Nothing above the score filter")
+    );
+  }
+
+  #[test]
+  fn hints_follow_remapped_keys() {
+    let keymap = Keymap::from_file(
+      toml::from_str(
+        "[list]\n\"enter\" = \"none\"\n\"x\" = \"open-comments\"\n\n[comments]\n\"esc\" = \"none\"\n\"backspace\" = \"close-comments\"",
+      )
+      .unwrap(),
+    );
+
+    let status = status_for(
+      &list_mode(&["Story"]),
+      Some(CategoryKind::Stories("topstories")),
+      true,
+      &keymap,
+    );
+
+    assert!(status.contains("x comments"));
+    assert!(!status.contains("enter"));
+
+    assert!(comments_status(&keymap).ends_with("backspace back"));
+  }
+}
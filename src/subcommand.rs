@@ -0,0 +1,9 @@
+use super::*;
+
+#[derive(Debug, clap::Subcommand)]
+pub(crate) enum Subcommand {
+  #[command(about = "Open a story or comment thread directly")]
+  Item(ItemLink),
+  #[command(flatten)]
+  Print(PrintSubcommand),
+}
@@ -1,7 +1,20 @@
 use super::*;
 
+#[derive(Clone)]
 pub(crate) struct Tab {
   pub(crate) category: Category,
   pub(crate) has_more: bool,
-  pub(crate) label: &'static str,
+  pub(crate) label: String,
+  pub(crate) loaded: bool,
+}
+
+impl Tab {
+  pub(crate) fn new(category: Category) -> Self {
+    Self {
+      category,
+      has_more: true,
+      label: category.label.into(),
+      loaded: false,
+    }
+  }
 }
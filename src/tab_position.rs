@@ -0,0 +1,7 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub(crate) struct TabPosition {
+  pub(crate) offset: usize,
+  pub(crate) selected: usize,
+}
@@ -0,0 +1,167 @@
+use super::*;
+
+#[derive(Debug, Default)]
+pub(crate) struct TabSwitcher {
+  filter: String,
+  selected: usize,
+}
+
+impl TabSwitcher {
+  fn area(area: Rect, width: usize, height: usize) -> Rect {
+    let width = u16::try_from(width)
+      .unwrap_or(u16::MAX)
+      .min(area.width.saturating_sub(2))
+      .max(1);
+
+    let height = u16::try_from(height)
+      .unwrap_or(u16::MAX)
+      .min(area.height.saturating_sub(2))
+      .max(1);
+
+    Rect {
+      x: area.x + (area.width.saturating_sub(width)) / 2,
+      y: area.y + (area.height.saturating_sub(height)) / 2,
+      width,
+      height,
+    }
+  }
+
+  pub(crate) fn draw(
+    &self,
+    frame: &mut Frame,
+    tabs: &[Tab],
+    titles: &[String],
+    theme: &Theme,
+  ) {
+    let matches = self.matches(tabs);
+
+    let labels = matches
+      .iter()
+      .map(|&index| titles[index].clone())
+      .collect::<Vec<String>>();
+
+    let title = format!("Tabs: {}", self.filter);
+
+    let width = labels
+      .iter()
+      .map(|label| label.chars().count() + 4)
+      .chain([title.chars().count() + 4, 30])
+      .max()
+      .unwrap_or(30);
+
+    let area = Self::area(frame.area(), width, labels.len().max(1) + 2);
+
+    frame.render_widget(Clear, area);
+
+    let items = if labels.is_empty() {
+      vec![ListItem::new(Line::from(vec![
+        Span::raw(BASE_INDENT),
+        Span::styled("No matching tabs", Style::default().fg(theme.detail)),
+      ]))]
+    } else {
+      labels
+        .into_iter()
+        .map(|label| {
+          ListItem::new(Line::from(vec![
+            Span::raw(BASE_INDENT),
+            Span::raw(label),
+          ]))
+        })
+        .collect()
+    };
+
+    let list = List::new(items)
+      .block(Block::default().title(title).borders(Borders::ALL))
+      .highlight_style(
+        Style::default()
+          .fg(theme.highlight)
+          .add_modifier(Modifier::BOLD),
+      );
+
+    let mut state = ListState::default();
+
+    if !matches.is_empty() {
+      state.select(Some(self.selected.min(matches.len() - 1)));
+    }
+
+    frame.render_stateful_widget(list, area, &mut state);
+  }
+
+  pub(crate) fn matches(&self, tabs: &[Tab]) -> Vec<usize> {
+    let mut scored = tabs
+      .iter()
+      .enumerate()
+      .filter_map(|(index, tab)| {
+        fuzzy_score(&self.filter, &tab.label).map(|score| (index, score))
+      })
+      .collect::<Vec<(usize, usize)>>();
+
+    scored.sort_by_key(|&(_, score)| Reverse(score));
+
+    scored.into_iter().map(|(index, _)| index).collect()
+  }
+
+  pub(crate) fn pop(&mut self) {
+    self.filter.pop();
+    self.selected = 0;
+  }
+
+  pub(crate) fn push(&mut self, ch: char) {
+    self.filter.push(ch);
+    self.selected = 0;
+  }
+
+  pub(crate) fn select_next(&mut self, tabs: &[Tab]) {
+    let count = self.matches(tabs).len();
+
+    self.selected = (self.selected + 1).min(count.saturating_sub(1));
+  }
+
+  pub(crate) fn select_previous(&mut self) {
+    self.selected = self.selected.saturating_sub(1);
+  }
+
+  pub(crate) fn selected_tab(&self, tabs: &[Tab]) -> Option<usize> {
+    self.matches(tabs).get(self.selected).copied()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn tab(label: &'static str) -> Tab {
+    Tab {
+      category: Category {
+        label,
+        kind: CategoryKind::Stories("topstories"),
+      },
+      has_more: false,
+      label: label.into(),
+      loaded: false,
+    }
+  }
+
+  #[test]
+  fn filter_narrows_and_ranks_tabs() {
+    let tabs = [tab("top"), tab("new"), tab("best"), tab("show")];
+
+    let mut switcher = TabSwitcher::default();
+
+    assert_eq!(switcher.matches(&tabs), vec![0, 1, 2, 3]);
+
+    switcher.push('s');
+
+    assert_eq!(switcher.matches(&tabs), vec![3, 2]);
+
+    switcher.push('t');
+
+    assert_eq!(switcher.matches(&tabs), vec![2]);
+
+    switcher.pop();
+    switcher.select_next(&tabs);
+    switcher.select_next(&tabs);
+
+    assert_eq!(switcher.selected_tab(&tabs), Some(2));
+  }
+}
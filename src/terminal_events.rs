@@ -0,0 +1,13 @@
+use super::*;
+
+pub(crate) struct TerminalEvents;
+
+impl EventSource for TerminalEvents {
+  fn poll(&mut self, timeout: Duration) -> Result<bool> {
+    Ok(crossterm_event::poll(timeout)?)
+  }
+
+  fn read(&mut self) -> Result<CrosstermEvent> {
+    Ok(crossterm_event::read()?)
+  }
+}
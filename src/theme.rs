@@ -0,0 +1,69 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Theme {
+  pub(crate) body: Color,
+  pub(crate) detail: Color,
+  pub(crate) highlight: Color,
+  pub(crate) status: Color,
+  pub(crate) tab_active: Color,
+  pub(crate) tab_inactive: Color,
+  pub(crate) title: Color,
+}
+
+impl Default for Theme {
+  fn default() -> Self {
+    Self::DARK
+  }
+}
+
+impl FromStr for Theme {
+  type Err = anyhow::Error;
+
+  fn from_str(name: &str) -> Result<Self> {
+    match name.trim() {
+      "dark" => Ok(Self::DARK),
+      "light" => Ok(Self::LIGHT),
+      name => bail!("unknown theme `{name}`, expected `dark` or `light`"),
+    }
+  }
+}
+
+impl Theme {
+  pub(crate) const DARK: Self = Self {
+    body: Color::Gray,
+    detail: Color::DarkGray,
+    highlight: Color::Yellow,
+    status: Color::DarkGray,
+    tab_active: Color::Cyan,
+    tab_inactive: Color::DarkGray,
+    title: Color::White,
+  };
+
+  pub(crate) const LIGHT: Self = Self {
+    body: Color::Black,
+    detail: Color::DarkGray,
+    highlight: Color::Magenta,
+    status: Color::DarkGray,
+    tab_active: Color::Blue,
+    tab_inactive: Color::DarkGray,
+    title: Color::Black,
+  };
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn themes_are_selected_by_name() {
+    assert_eq!("dark".parse::<Theme>().unwrap(), Theme::DARK);
+    assert_eq!("light".parse::<Theme>().unwrap(), Theme::LIGHT);
+    assert_eq!(Theme::default(), Theme::DARK);
+
+    assert_eq!(
+      "solarized".parse::<Theme>().unwrap_err().to_string(),
+      "unknown theme `solarized`, expected `dark` or `light`"
+    );
+  }
+}
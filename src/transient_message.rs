@@ -3,22 +3,32 @@ use super::*;
 #[derive(Clone)]
 pub(crate) struct TransientMessage {
   current: String,
-  expires_at: Instant,
+  expires_at: Option<Instant>,
   original: String,
 }
 
 impl TransientMessage {
+  pub(crate) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(3);
+
+  pub(crate) const TIMEOUT_RANGE_MS: RangeInclusive<usize> = 250..=60_000;
+
   pub(crate) fn current(&self) -> &str {
     &self.current
   }
 
   pub(crate) fn is_expired(&self) -> bool {
-    Instant::now() >= self.expires_at
+    self
+      .expires_at
+      .is_some_and(|expires_at| Instant::now() >= expires_at)
   }
 
-  pub(crate) fn new(current: String, original: String) -> Self {
+  pub(crate) fn new(
+    current: String,
+    original: String,
+    timeout: Duration,
+  ) -> Self {
     Self {
-      expires_at: Instant::now() + Duration::from_secs(3),
+      expires_at: Some(Instant::now() + timeout),
       current,
       original,
     }
@@ -27,6 +37,14 @@ impl TransientMessage {
   pub(crate) fn original(&self) -> &str {
     &self.original
   }
+
+  pub(crate) fn persistent(current: String, original: String) -> Self {
+    Self {
+      expires_at: None,
+      current,
+      original,
+    }
+  }
 }
 
 #[cfg(test)]
@@ -34,27 +52,42 @@ mod tests {
   use super::*;
 
   #[test]
-  fn new_sets_fields_and_expiry_three_seconds_ahead() {
+  fn new_sets_fields_and_expiry_after_the_timeout() {
     let now = Instant::now();
-    let message = TransientMessage::new("current".into(), "original".into());
+    let message = TransientMessage::new(
+      "current".into(),
+      "original".into(),
+      Duration::from_millis(500),
+    );
 
     assert_eq!(message.current(), "current");
     assert_eq!(message.original(), "original");
 
-    let remaining = message.expires_at.duration_since(now);
-    assert!(remaining >= Duration::from_secs(3));
-    assert!(remaining <= Duration::from_secs(3) + Duration::from_millis(10));
+    let remaining = message.expires_at.unwrap().duration_since(now);
+    assert!(remaining >= Duration::from_millis(500));
+    assert!(remaining <= Duration::from_millis(510));
   }
 
   #[test]
   fn is_expired_detects_elapsed_time() {
-    let mut message = TransientMessage::new("a".into(), "b".into());
+    let mut message = TransientMessage::new(
+      "a".into(),
+      "b".into(),
+      TransientMessage::DEFAULT_TIMEOUT,
+    );
 
     assert!(!message.is_expired());
 
-    message.expires_at =
-      Instant::now().checked_sub(Duration::from_secs(1)).unwrap();
+    message.expires_at = Instant::now().checked_sub(Duration::from_secs(1));
 
     assert!(message.is_expired());
   }
+
+  #[test]
+  fn persistent_messages_never_expire() {
+    let message = TransientMessage::persistent("a".into(), "b".into());
+
+    assert!(message.expires_at.is_none());
+    assert!(!message.is_expired());
+  }
 }
@@ -0,0 +1,62 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum TreeGuides {
+  Ascii,
+  None,
+  #[default]
+  Unicode,
+}
+
+impl TreeGuides {
+  fn glyphs(self) -> Option<(char, char, char, char)> {
+    match self {
+      Self::Ascii => Some(('|', '|', '`', '-')),
+      Self::None => None,
+      Self::Unicode => Some(('│', '├', '└', '─')),
+    }
+  }
+
+  pub(crate) fn render(self, guides: &[Guide], width: usize) -> String {
+    let Some((pipe, branch, last, line)) = self.glyphs() else {
+      return " ".repeat(guides.len().saturating_mul(width));
+    };
+
+    let fill = |first: char, rest: char| {
+      iter::once(first)
+        .chain(iter::repeat_n(rest, width.saturating_sub(1)))
+        .take(width)
+        .collect::<String>()
+    };
+
+    guides
+      .iter()
+      .map(|guide| match guide {
+        Guide::Blank => " ".repeat(width),
+        Guide::Branch => fill(branch, line),
+        Guide::Last => fill(last, line),
+        Guide::Pipe => fill(pipe, ' '),
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const GUIDES: &[Guide] = &[Guide::Pipe, Guide::Blank, Guide::Branch];
+
+  #[test]
+  fn unicode_guides_draw_the_reply_tree() {
+    assert_eq!(TreeGuides::Unicode.render(GUIDES, 2), "│   ├─");
+    assert_eq!(TreeGuides::Unicode.render(&[Guide::Last], 3), "└──");
+  }
+
+  #[test]
+  fn ascii_and_disabled_guides_fall_back() {
+    assert_eq!(TreeGuides::Ascii.render(GUIDES, 2), "|   |-");
+    assert_eq!(TreeGuides::None.render(GUIDES, 2), "      ");
+  }
+}
@@ -1,5 +1,72 @@
 use super::*;
 
+pub(crate) fn clean_url(url: &str) -> String {
+  if reqwest::Url::parse(url).is_err() {
+    return url.to_string();
+  }
+
+  let (rest, fragment) = match url.split_once('#') {
+    Some((rest, fragment)) => (rest, Some(fragment)),
+    None => (url, None),
+  };
+
+  let Some((base, query)) = rest.split_once('?') else {
+    return url.to_string();
+  };
+
+  let params = query.split('&').collect::<Vec<&str>>();
+
+  let kept = params
+    .iter()
+    .copied()
+    .filter(|param| {
+      let key = param
+        .split_once('=')
+        .map_or(*param, |(key, _)| key)
+        .to_lowercase();
+
+      !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_str())
+    })
+    .collect::<Vec<&str>>();
+
+  if kept.len() == params.len() {
+    return url.to_string();
+  }
+
+  let mut cleaned = base.to_string();
+
+  if !kept.is_empty() {
+    cleaned.push('?');
+    cleaned.push_str(&kept.join("&"));
+  }
+
+  if let Some(fragment) = fragment {
+    cleaned.push('#');
+    cleaned.push_str(fragment);
+  }
+
+  cleaned
+}
+
+pub(crate) fn config_path(
+  override_var: &str,
+  file_name: &str,
+) -> Result<PathBuf> {
+  if let Ok(path) = env::var(override_var) {
+    return Ok(PathBuf::from(path));
+  }
+
+  let base_dir = if let Ok(dir) = env::var("XDG_CONFIG_HOME") {
+    PathBuf::from(dir)
+  } else if let Ok(home) = env::var("HOME") {
+    PathBuf::from(home).join(".config")
+  } else {
+    env::current_dir()?.join(".config")
+  };
+
+  Ok(base_dir.join("hn").join(file_name))
+}
+
 pub(crate) fn deserialize_optional_string<'de, D>(
   deserializer: D,
 ) -> Result<Option<String>, D::Error>
@@ -27,6 +94,114 @@ where
   }
 }
 
+pub(crate) fn ensure_parent_dir(path: &Path) -> Result {
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+
+  Ok(())
+}
+
+pub(crate) fn extract_links(html: &str) -> Vec<String> {
+  let mut links = Vec::new();
+
+  let mut rest = html;
+
+  while let Some(start) = rest.find("href=\"") {
+    rest = &rest[start + "href=\"".len()..];
+
+    let Some(end) = rest.find('"') else {
+      break;
+    };
+
+    let link = unescape_html(&rest[..end]);
+
+    rest = &rest[end..];
+
+    if (link.starts_with("http://") || link.starts_with("https://"))
+      && !links.contains(&link)
+    {
+      links.push(link);
+    }
+  }
+
+  links
+}
+
+pub(crate) fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+  fn match_end(text: &str, start: usize, query: &[char]) -> Option<usize> {
+    let mut expected = query.iter();
+
+    for (offset, ch) in text[start..].char_indices() {
+      for lower in ch.to_lowercase() {
+        if expected.next() != Some(&lower) {
+          return None;
+        }
+      }
+
+      if expected.len() == 0 {
+        return Some(start + offset + ch.len_utf8());
+      }
+    }
+
+    None
+  }
+
+  let query = query
+    .chars()
+    .flat_map(char::to_lowercase)
+    .collect::<Vec<_>>();
+
+  let mut matches = Vec::new();
+
+  if query.is_empty() {
+    return matches;
+  }
+
+  let mut position = 0;
+
+  while let Some(ch) = text[position..].chars().next() {
+    if let Some(end) = match_end(text, position, &query) {
+      matches.push(position..end);
+      position = end;
+    } else {
+      position += ch.len_utf8();
+    }
+  }
+
+  matches
+}
+
+pub(crate) fn format_comments(count: u64) -> String {
+  match count {
+    1 => "1 comment".to_string(),
+    _ => format!("{count} comments"),
+  }
+}
+
+pub(crate) fn format_date(timestamp: u64) -> String {
+  let days = i64::try_from(timestamp / DAY).unwrap_or(i64::MAX) + 719_468;
+
+  let era = days.div_euclid(146_097);
+  let day_of_era = days.rem_euclid(146_097);
+  let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524
+    - day_of_era / 146_096)
+    / 365;
+  let day_of_year =
+    day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let month_index = (5 * day_of_year + 2) / 153;
+
+  let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+  let month = if month_index < 10 {
+    month_index + 3
+  } else {
+    month_index - 9
+  };
+  let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+  format!("{year:04}-{month:02}-{day:02}")
+}
+
 pub(crate) fn format_points(score: u64) -> String {
   match score {
     1 => "1 point".to_string(),
@@ -34,6 +209,153 @@ pub(crate) fn format_points(score: u64) -> String {
   }
 }
 
+pub(crate) fn format_relative_time(now: u64, then: u64) -> String {
+  let elapsed = now.saturating_sub(then);
+
+  match elapsed {
+    0..60 => format!("{elapsed}s ago"),
+    60..3600 => format!("{}m ago", elapsed / 60),
+    3600..86400 => format!("{}h ago", elapsed / 3600),
+    _ => format!("{}d ago", elapsed / 86400),
+  }
+}
+
+pub(crate) fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+  let text = text.to_lowercase().chars().collect::<Vec<char>>();
+
+  let mut score = 0;
+  let mut position = 0;
+  let mut previous = None;
+
+  for ch in query.to_lowercase().chars() {
+    let found = position + text[position..].iter().position(|&c| c == ch)?;
+
+    score += 1;
+
+    if found == 0 {
+      score += 3;
+    }
+
+    if previous.is_some_and(|previous| previous + 1 == found) {
+      score += 2;
+    }
+
+    previous = Some(found);
+    position = found + 1;
+  }
+
+  Some(score)
+}
+
+pub(crate) fn hn_item_id(text: &str) -> Option<u64> {
+  let url = reqwest::Url::parse(text.trim()).ok()?;
+
+  if !matches!(
+    url.host_str(),
+    Some("news.ycombinator.com" | "ycombinator.com")
+  ) || url.path() != "/item"
+  {
+    return None;
+  }
+
+  url
+    .query_pairs()
+    .find(|(key, _)| key == "id")
+    .and_then(|(_, value)| value.parse().ok())
+}
+
+pub(crate) fn markdown_link(text: &str, url: &str) -> String {
+  let text = text.replace('[', "\\[").replace(']', "\\]");
+  let url = url.replace('(', "%28").replace(')', "%29");
+
+  format!("[{text}]({url})")
+}
+
+pub(crate) fn markdown_quote(text: &str) -> String {
+  text
+    .lines()
+    .map(|line| {
+      if line.is_empty() {
+        ">".to_string()
+      } else {
+        format!("> {line}")
+      }
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn read_file_or_backup<T, F>(
+  path: &Path,
+  parse: F,
+) -> Result<(T, Option<PathBuf>)>
+where
+  T: Default,
+  F: FnOnce(&[u8]) -> Option<Result<T>>,
+{
+  if !path.exists() {
+    return Ok((T::default(), None));
+  }
+
+  let data = fs::read(path)?;
+
+  if data.is_empty() {
+    return Ok((T::default(), None));
+  }
+
+  if let Some(value) = parse(&data) {
+    return Ok((value?, None));
+  }
+
+  let mut backup = path.as_os_str().to_owned();
+  backup.push(".bak");
+
+  let backup = PathBuf::from(backup);
+
+  fs::rename(path, &backup)?;
+
+  Ok((T::default(), Some(backup)))
+}
+
+pub(crate) fn parse_clamped(
+  value: &str,
+  range: RangeInclusive<usize>,
+) -> Result<usize> {
+  let value = value
+    .trim()
+    .parse::<usize>()
+    .with_context(|| format!("expected a whole number, got `{value}`"))?;
+
+  Ok(value.clamp(*range.start(), *range.end()))
+}
+
+pub(crate) fn read_json_file<T>(path: &Path) -> Result<(T, Option<PathBuf>)>
+where
+  T: DeserializeOwned + Default,
+{
+  read_file_or_backup(path, |data| serde_json::from_slice(data).ok().map(Ok))
+}
+
+pub(crate) fn read_toml_file<T>(path: &Path) -> Result<(T, Option<PathBuf>)>
+where
+  T: DeserializeOwned + Default,
+{
+  read_file_or_backup(path, |data| {
+    let text = str::from_utf8(data).ok()?;
+
+    text.parse::<toml::Table>().ok()?;
+
+    Some(
+      toml::from_str(text)
+        .with_context(|| format!("invalid settings in `{}`", path.display())),
+    )
+  })
+}
+
+pub(crate) fn strip_tracking(url: String, keep_tracking: bool) -> String {
+  if keep_tracking { url } else { clean_url(&url) }
+}
+
 pub(crate) fn truncate(text: &str, max_chars: usize) -> String {
   if text.chars().count() <= max_chars {
     return text.to_string();
@@ -53,6 +375,82 @@ pub(crate) fn truncate(text: &str, max_chars: usize) -> String {
   result.trim_end().to_string()
 }
 
+pub(crate) fn truncate_words(text: &str, max_chars: usize) -> String {
+  if text.chars().count() <= max_chars {
+    return text.to_string();
+  }
+
+  let cut = text
+    .char_indices()
+    .nth(max_chars)
+    .map_or(text.len(), |(index, _)| index);
+
+  let (head, rest) = text.split_at(cut);
+
+  let head = if rest.starts_with(char::is_whitespace) {
+    head
+  } else {
+    head
+      .rfind(char::is_whitespace)
+      .map_or(head, |boundary| &head[..boundary])
+  }
+  .trim_end();
+
+  if head.is_empty() {
+    return truncate(text, max_chars);
+  }
+
+  format!("{head}...")
+}
+
+fn unescape_html(text: &str) -> String {
+  let mut result = String::with_capacity(text.len());
+
+  let mut rest = text;
+
+  while let Some(start) = rest.find('&') {
+    result.push_str(&rest[..start]);
+
+    rest = &rest[start..];
+
+    let Some(end) = rest.find(';') else {
+      break;
+    };
+
+    let entity = &rest[1..end];
+
+    let decoded = match entity {
+      "amp" => Some('&'),
+      "lt" => Some('<'),
+      "gt" => Some('>'),
+      "quot" => Some('"'),
+      "apos" => Some('\''),
+      _ => entity
+        .strip_prefix("#x")
+        .or_else(|| entity.strip_prefix("#X"))
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .or_else(|| {
+          entity
+            .strip_prefix('#')
+            .and_then(|decimal| decimal.parse::<u32>().ok())
+        })
+        .and_then(char::from_u32),
+    };
+
+    if let Some(ch) = decoded {
+      result.push(ch);
+    } else {
+      result.push_str(&rest[..=end]);
+    }
+
+    rest = &rest[end + 1..];
+  }
+
+  result.push_str(rest);
+
+  result
+}
+
 pub(crate) fn wrap_text(text: &str, width: usize) -> Vec<String> {
   if text.is_empty() || width == 0 {
     return Vec::new();
@@ -118,6 +516,142 @@ mod tests {
     value: Option<String>,
   }
 
+  #[test]
+  fn clean_url_removes_tracking_params_only() {
+    assert_eq!(
+      clean_url(
+        "https://example.com/post?id=7&utm_source=hn&fbclid=abc&Sort=new#comments"
+      ),
+      "https://example.com/post?id=7&Sort=new#comments"
+    );
+
+    assert_eq!(
+      clean_url("https://example.com/post?utm_medium=social"),
+      "https://example.com/post"
+    );
+  }
+
+  #[test]
+  fn clean_url_leaves_urls_without_a_query_untouched() {
+    assert_eq!(
+      clean_url("https://example.com/a/b#utm_source=x"),
+      "https://example.com/a/b#utm_source=x"
+    );
+  }
+
+  #[test]
+  fn clean_url_passes_malformed_urls_through() {
+    assert_eq!(
+      clean_url("not a url?utm_source=x"),
+      "not a url?utm_source=x"
+    );
+  }
+
+  #[test]
+  fn fuzzy_score_prefers_prefix_and_consecutive_matches() {
+    assert_eq!(fuzzy_score("", "top"), Some(0));
+    assert_eq!(fuzzy_score("xyz", "top"), None);
+    assert!(fuzzy_score("sh", "show") > fuzzy_score("sh", "search"));
+    assert!(fuzzy_score("as", "ask") > fuzzy_score("as", "bookmarks"));
+  }
+
+  #[test]
+  fn hn_item_id_parses_item_urls_only() {
+    assert_eq!(
+      hn_item_id("https://news.ycombinator.com/item?id=12345"),
+      Some(12345)
+    );
+    assert_eq!(
+      hn_item_id(" https://news.ycombinator.com/item?foo=bar&id=7 "),
+      Some(7)
+    );
+    assert_eq!(hn_item_id("https://news.ycombinator.com/user?id=pg"), None);
+    assert_eq!(hn_item_id("https://example.com/item?id=12345"), None);
+    assert_eq!(hn_item_id("https://news.ycombinator.com/item?id=x"), None);
+    assert_eq!(hn_item_id("rust async"), None);
+  }
+
+  #[test]
+  fn markdown_link_escapes_brackets_and_parentheses() {
+    assert_eq!(
+      markdown_link("[Show HN] Foo", "https://example.com/a_(b)"),
+      "[\\[Show HN\\] Foo](https://example.com/a_%28b%29)"
+    );
+  }
+
+  #[test]
+  fn markdown_quote_prefixes_every_line() {
+    assert_eq!(markdown_quote("a\n\nb"), "> a\n>\n> b");
+  }
+
+  #[test]
+  fn parse_clamped_clamps_to_the_range_and_rejects_garbage() {
+    assert_eq!(parse_clamped("50", 10..=100).unwrap(), 50);
+    assert_eq!(parse_clamped(" 5 ", 10..=100).unwrap(), 10);
+    assert_eq!(parse_clamped("1000", 10..=100).unwrap(), 100);
+
+    assert_eq!(
+      parse_clamped("lots", 10..=100).unwrap_err().to_string(),
+      "expected a whole number, got `lots`"
+    );
+  }
+
+  #[test]
+  fn read_json_file_backs_up_corrupt_files() {
+    let path = env::temp_dir()
+      .join(format!("hn_read_json_file_test_{}.json", process::id()));
+
+    fs::write(&path, "{ not json").unwrap();
+
+    let (value, backup) = read_json_file::<Vec<String>>(&path).unwrap();
+
+    assert!(value.is_empty());
+    assert!(!path.exists());
+
+    let backup = backup.expect("backup is written");
+
+    assert_eq!(fs::read_to_string(&backup).unwrap(), "{ not json");
+
+    let _ = fs::remove_file(backup);
+  }
+
+  #[test]
+  fn read_toml_file_backs_up_only_syntax_errors() {
+    #[derive(Debug, Default, Deserialize)]
+    struct Settings {
+      depth: usize,
+    }
+
+    let path = env::temp_dir()
+      .join(format!("hn_read_toml_file_test_{}.toml", process::id()));
+
+    fs::write(&path, "depth = 3").unwrap();
+
+    assert_eq!(read_toml_file::<Settings>(&path).unwrap().0.depth, 3);
+
+    fs::write(&path, "depth = \"deep\"").unwrap();
+
+    let error = read_toml_file::<Settings>(&path).unwrap_err();
+
+    assert_eq!(
+      error.to_string(),
+      format!("invalid settings in `{}`", path.display())
+    );
+    assert!(path.exists());
+
+    fs::write(&path, "depth = [").unwrap();
+
+    let (_, backup) = read_toml_file::<Settings>(&path).unwrap();
+
+    assert!(!path.exists());
+
+    let backup = backup.expect("backup is written");
+
+    assert_eq!(fs::read_to_string(&backup).unwrap(), "depth = [");
+
+    let _ = fs::remove_file(backup);
+  }
+
   #[test]
   fn truncate_returns_original_when_within_limit() {
     assert_eq!(truncate("short", 10), "short");
@@ -133,6 +667,23 @@ mod tests {
     assert_eq!(truncate("exact", 5), "exact");
   }
 
+  #[test]
+  fn truncate_words_backs_off_to_the_previous_word() {
+    assert_eq!(truncate_words("This is a longer line", 11), "This is a...");
+  }
+
+  #[test]
+  fn truncate_words_keeps_a_word_ending_at_the_limit() {
+    assert_eq!(truncate_words("This is a longer line", 9), "This is a...");
+    assert_eq!(truncate_words("exact fit", 9), "exact fit");
+  }
+
+  #[test]
+  fn truncate_words_cuts_a_single_overlong_word() {
+    assert_eq!(truncate_words("Supercalifragilistic", 5), "Super...");
+    assert_eq!(truncate_words(" Supercalifragilistic", 5), " Supe...");
+  }
+
   #[test]
   fn wrap_text_returns_empty_for_empty_input() {
     assert_eq!(wrap_text("", 10), Vec::<String>::new());
@@ -168,6 +719,54 @@ mod tests {
     assert_eq!(wrap_text("short text", 20), vec!["short text".to_string()]);
   }
 
+  #[test]
+  fn extract_links_decodes_and_deduplicates_hrefs() {
+    let html = concat!(
+      "See <a href=\"https:&#x2F;&#x2F;example.com&#x2F;a?b=1&amp;c=2\" ",
+      "rel=\"nofollow\">this</a> and ",
+      "<a href=\"https:&#x2F;&#x2F;example.com&#x2F;a?b=1&amp;c=2\">again</a>",
+      "<a href=\"item?id=1\">relative</a>",
+      "<a href=\"http://other.org\">other</a>"
+    );
+
+    assert_eq!(
+      extract_links(html),
+      vec![
+        "https://example.com/a?b=1&c=2".to_string(),
+        "http://other.org".to_string(),
+      ]
+    );
+  }
+
+  #[test]
+  fn extract_links_handles_text_without_links() {
+    assert!(extract_links("no links &amp; nothing else").is_empty());
+  }
+
+  #[test]
+  fn find_matches_ignores_case_and_handles_multibyte_text() {
+    assert_eq!(find_matches("Rust and rust", "RUST"), vec![0..4, 9..13]);
+    assert_eq!(find_matches("naïve Naïve", "naïve"), vec![0..6, 7..13]);
+    assert_eq!(find_matches("aaa", "aa"), vec![0..2]);
+    assert!(find_matches("text", "").is_empty());
+    assert!(find_matches("text", "other").is_empty());
+  }
+
+  #[test]
+  fn format_comments_handles_singular_and_plural() {
+    assert_eq!(format_comments(1), "1 comment");
+    assert_eq!(format_comments(45), "45 comments");
+    assert_eq!(format_comments(0), "0 comments");
+  }
+
+  #[test]
+  fn format_date_converts_timestamps_to_utc_dates() {
+    assert_eq!(format_date(0), "1970-01-01");
+    assert_eq!(format_date(951_782_400), "2000-02-29");
+    assert_eq!(format_date(1_717_286_399), "2024-06-01");
+    assert_eq!(format_date(1_717_286_400), "2024-06-02");
+  }
+
   #[test]
   fn format_points_handles_singular_and_plural() {
     assert_eq!(format_points(1), "1 point");
@@ -175,6 +774,21 @@ mod tests {
     assert_eq!(format_points(0), "0 points");
   }
 
+  #[test]
+  fn format_relative_time_uses_largest_whole_unit() {
+    let now = 1_000_000;
+
+    assert_eq!(format_relative_time(now, now), "0s ago");
+    assert_eq!(format_relative_time(now, now - 59), "59s ago");
+    assert_eq!(format_relative_time(now, now - 60), "1m ago");
+    assert_eq!(format_relative_time(now, now - 3599), "59m ago");
+    assert_eq!(format_relative_time(now, now - 3600), "1h ago");
+    assert_eq!(format_relative_time(now, now - 86399), "23h ago");
+    assert_eq!(format_relative_time(now, now - 86400), "1d ago");
+    assert_eq!(format_relative_time(now, now - 3 * 86400), "3d ago");
+    assert_eq!(format_relative_time(now, now + 10), "0s ago");
+  }
+
   fn parse_value(input: &str) -> Result<Option<String>, serde_json::Error> {
     serde_json::from_str::<OptionalWrapper>(input).map(|wrapper| wrapper.value)
   }